@@ -2,7 +2,7 @@
 
 use std::path::Path;
 
-pub use crate::val::{Palette, Speed};
+pub use crate::val::{Palette, Rewind, Speed, Tilt};
 use crate::Conf;
 
 /// Application options.
@@ -26,8 +26,9 @@ pub struct Application {
 
     /// 2-bit color palette.
     ///
-    /// Select from a list of preset 2-bit color palettes for the DMG model.
-    /// Custom values can be defined in the configuration file.
+    /// Select from a list of preset 2-bit color palettes for the DMG model,
+    /// or have them chosen per-game as done by the CGB. Custom values can be
+    /// defined in the configuration file.
     #[cfg_attr(
         feature = "clap",
         clap(short, long = "palette", value_name = "COLOR", value_enum)
@@ -45,6 +46,20 @@ pub struct Application {
     )]
     #[cfg_attr(feature = "serde", serde(rename = "speed"))]
     pub spd: Option<Speed>,
+
+    /// Tilt key bindings.
+    ///
+    /// Keys used to simulate tilting cartridges with a built-in motion
+    /// sensor. Can only be defined in the configuration file.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    pub tilt: Option<Tilt>,
+
+    /// Rewind options.
+    ///
+    /// Controls how often snapshots are captured for rewinding, and how much
+    /// history is kept. Can only be defined in the configuration file.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    pub rewind: Option<Rewind>,
 }
 
 impl Conf for Application {
@@ -54,5 +69,7 @@ impl Conf for Application {
         self.log = self.log.take().or(other.log);
         self.pal = self.pal.take().or(other.pal);
         self.spd = self.spd.take().or(other.spd);
+        self.tilt = self.tilt.take().or(other.tilt);
+        self.rewind = self.rewind.take().or(other.rewind);
     }
 }
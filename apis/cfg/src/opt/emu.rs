@@ -2,7 +2,7 @@
 
 use std::path::{Path, PathBuf};
 
-pub use crate::val::Tristate;
+pub use crate::val::{Clock, Tristate};
 use crate::Conf;
 
 /// Emulation options.
@@ -130,6 +130,14 @@ pub struct Cart {
     /// with the same path and name as the ROM, but using the ".sav" extension.
     #[cfg_attr(feature = "clap", clap(long, value_name = "WHEN", value_enum))]
     pub save: Option<Tristate>,
+
+    /// Real-time clock source.
+    ///
+    /// Selects how the time kept by a cartridge's real-time clock advances. By
+    /// default, the clock follows emulated cycles, such that it remains in sync
+    /// with the emulator.
+    #[cfg_attr(feature = "clap", clap(long, value_name = "SOURCE", value_enum))]
+    pub rtc: Option<Clock>,
 }
 
 impl Cart {
@@ -151,5 +159,6 @@ impl Conf for Cart {
         self.check |= other.check;
         self.force |= other.force;
         self.save = self.save.take().or(other.save);
+        self.rtc = self.rtc.take().or(other.rtc);
     }
 }
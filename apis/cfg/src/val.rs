@@ -1,5 +1,7 @@
 //! Configurable values.
 
+use rugby::core::dmg::cart::header::Header;
+use rugby::core::dmg::cart::mbc::rtc;
 use rugby::core::dmg::FREQ;
 use rugby::pal;
 
@@ -21,6 +23,39 @@ pub enum Tristate {
     Always,
 }
 
+/// Real-time clock time source.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum Clock {
+    /// Emulated cycles.
+    ///
+    /// Time advances along with the emulator, even when running faster or
+    /// slower than actual hardware.
+    #[default]
+    Cycle,
+    /// Wall-clock time.
+    ///
+    /// Time advances with the host's system clock, regardless of emulation
+    /// speed.
+    Wall,
+}
+
+impl Clock {
+    /// Converts the `Clock` to its corresponding time source.
+    #[must_use]
+    pub fn source(self) -> rtc::Source {
+        match self {
+            Clock::Cycle => rtc::Source::Cycle,
+            Clock::Wall => rtc::Source::Wall,
+        }
+    }
+}
+
 /// Emulator palette selection.
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
@@ -31,6 +66,8 @@ pub enum Tristate {
 )]
 #[non_exhaustive]
 pub enum Palette {
+    /// Per-game colors chosen by the CGB.
+    AutoCgb,
     /// Nostalgic autumn sunsets.
     AutumnChill,
     /// Aquatic blues.
@@ -65,12 +102,34 @@ pub enum Palette {
     /// Whatever colors you want!
     #[cfg_attr(feature = "clap", clap(skip))]
     Custom(pal::Palette),
+    /// Separate palettes for each layer.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    Layered {
+        /// Background and window.
+        bg: Box<Palette>,
+        /// Object palette 0.
+        obj0: Box<Palette>,
+        /// Object palette 1.
+        obj1: Box<Palette>,
+    },
 }
 
-#[rustfmt::skip]
-impl From<Palette> for pal::Palette {
-    fn from(value: Palette) -> Self {
-        match value {
+impl Palette {
+    /// Resolves the palettes used to display a cartridge.
+    ///
+    /// Automatic palettes are selected using the cartridge's header, falling
+    /// back to the default when no cartridge is present. Layered palettes take
+    /// each layer from the corresponding resolved palette.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn resolve(self, head: Option<&Header>) -> pal::Scheme {
+        match self {
+            Palette::Layered { bg, obj0, obj1 } => return pal::Scheme {
+                bg:   bg.resolve(head).bg,
+                obj0: obj0.resolve(head).obj0,
+                obj1: obj1.resolve(head).obj1,
+            },
+            Palette::AutoCgb      => return head.map_or_else(pal::cgb::default, pal::cgb::lookup),
             Palette::AutumnChill  => pal::AUTUMN_CHILL,
             Palette::BlkAqu       => pal::BLK_AQU,
             Palette::BlueDream    => pal::BLUE_DREAM,
@@ -88,6 +147,7 @@ impl From<Palette> for pal::Palette {
             Palette::VelvetCherry => pal::VELVET_CHERRY,
             Palette::Custom(pal)  => pal,
         }
+        .into()
     }
 }
 
@@ -145,3 +205,67 @@ impl Speed {
         }
     }
 }
+
+/// Tilt key bindings.
+///
+/// Keys are specified by name, such as `"A"`, `"Up"`, or `"NumPad4"`.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Tilt {
+    /// Tilt left.
+    pub left: String,
+    /// Tilt right.
+    pub right: String,
+    /// Tilt up (away from the player).
+    pub up: String,
+    /// Tilt down (towards the player).
+    pub down: String,
+}
+
+impl Default for Tilt {
+    fn default() -> Self {
+        Self {
+            left: "J".into(),
+            right: "L".into(),
+            up: "I".into(),
+            down: "K".into(),
+        }
+    }
+}
+
+/// Rewind options.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Rewind {
+    /// Rewind key.
+    ///
+    /// Specified by name, as with tilt keys.
+    pub key: String,
+    /// Frames between snapshots.
+    pub every: u16,
+    /// Maximum history length, in seconds.
+    ///
+    /// A length of zero disables rewinding.
+    pub length: u32,
+    /// Maximum memory usage, in MiB.
+    pub memory: u32,
+}
+
+impl Default for Rewind {
+    fn default() -> Self {
+        Self {
+            key: "Backspace".into(),
+            every: 4,
+            length: 60,
+            memory: 64,
+        }
+    }
+}
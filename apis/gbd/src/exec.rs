@@ -1,7 +1,7 @@
 #![allow(clippy::unnecessary_wraps)]
 
 use std::fs::File;
-use std::io::{BufRead, Read, Write};
+use std::io::{BufRead, BufWriter, Read, Write};
 use std::ops::Not;
 use std::path::Path;
 
@@ -9,9 +9,10 @@ use rugby::arch::reg::Port;
 use rugby::arch::Block;
 use rugby::core::dmg::LCD;
 use rugby::prelude::*;
+use rugby::vid::{Format, Vid};
 use wrange::Wrange;
 
-use super::lang::{Keyword, Select, Serial, Value};
+use super::lang::{Keyword, Record, Select, Serial, Value};
 use super::{Debugger, Error, GameBoy, Result, Tick};
 use crate::Breakpoint;
 
@@ -286,6 +287,50 @@ pub fn read_range(emu: &mut GameBoy, range: Wrange<u16>) -> Result<()> {
     Ok(())
 }
 
+pub fn record(gbd: &mut Debugger, mode: Record) -> Result<()> {
+    match mode {
+        Record::Start(path, force) => {
+            // Check for an existing recording
+            if let Some((path, _)) = &gbd.vid {
+                advise::warn!("already recording to file: `{}`", path.display());
+                return Ok(());
+            }
+            // Extract recording options
+            let (pal, cfg) = gbd.rec.as_ref().ok_or(Error::CfgRecorder)?;
+            // Determine video format
+            let fmt = Format::infer(&path)?;
+            // Create video file
+            let file = if force {
+                // Forcefully overwrite existing files
+                File::create
+            } else {
+                // Error if the file exists
+                File::create_new
+            }(&path)?;
+            // Start recording
+            let vid = Vid::new(BufWriter::new(file), fmt, pal, *cfg)?;
+            advise::info!("recording to file: `{}`", path.display());
+            gbd.vid = Some((path, vid));
+        }
+        Record::Stop => {
+            // Extract the recording
+            let Some((path, mut vid)) = gbd.vid.take() else {
+                advise::warn!("not recording");
+                return Ok(());
+            };
+            // Finish recording
+            vid.flush()?;
+            advise::info!(
+                "wrote {} frames to file: `{}`",
+                vid.frames(),
+                path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
 pub fn reset(gbd: &mut Debugger, emu: &mut GameBoy) -> Result<()> {
     // Reset the console
     emu.reset();
@@ -86,6 +86,8 @@ pub enum Command {
     Read(u16),
     /// [Read][`Keyword::Read`] from an address range.
     ReadRange(Wrange<u16>),
+    /// [Record][`Keyword::Record`] a video.
+    Record(Record),
     /// [Reset][`Keyword::Reset`] the console.
     Reset,
     /// Perform [serial][`Keyword::Serial`] I/O.
@@ -124,6 +126,7 @@ pub enum Keyword {
      * * `log`,       `lo`       : Change the logging level.
      * * `quit`,              `q`: Quit the program.
      * * `read`,      `rd`,   `r`: Read from an address.
+     * * `record`,    `rec`      : Record a video.
      * * `reset`,     `res`      : Reset the console.
      * * `serial`,    `sx`       : Perform serial I/O.
      * * `step`,              `s`: Execute a single step.
@@ -350,6 +353,25 @@ pub enum Keyword {
      * See also: `write`
      */
     Read,
+    /**
+     * `record[!] <start PATH | stop>`
+     *
+     * Start or stop recording video to the provided path.
+     *
+     * Every frame is recorded once drawn, after palette mapping. The format is
+     * determined by the file extension, and must be one of:
+     * * `gif`: Animated GIF.
+     * * `png`: Animated PNG.
+     * * `y4m`: Raw YUV4MPEG2 video.
+     *
+     * To forcefully overwrite the file at the selected path, pass the `!`
+     * argument.
+     *
+     * Aliases: `rec`
+     *
+     * See also: `capture`
+     */
+    Record,
     /**
      * `reset`
      *
@@ -446,6 +468,12 @@ pub enum Value {
     Word(u16),
 }
 
+#[derive(Clone, Debug)]
+pub enum Record {
+    Start(PathBuf, bool),
+    Stop,
+}
+
 #[derive(Clone, Debug)]
 pub enum Serial {
     Peek,
@@ -7,7 +7,7 @@ use pest::Parser;
 use pest_derive::Parser;
 use thiserror::Error;
 
-use super::{Command, Keyword, Program, Select, Serial, Tick, Value};
+use super::{Command, Keyword, Program, Record, Select, Serial, Tick, Value};
 
 mod imp;
 
@@ -65,6 +65,7 @@ impl Display for Rule {
             KLog      => write!(f, "{Log}"),
             KQuit     => write!(f, "{Quit}"),
             KRead     => write!(f, "{Read}"),
+            KRecord   => write!(f, "{Record}"),
             KReset    => write!(f, "{Reset}"),
             KSerial   => write!(f, "{Serial}"),
             KStep     => write!(f, "{Step}"),
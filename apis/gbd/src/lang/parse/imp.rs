@@ -11,7 +11,7 @@ use rugby::core::dmg::{cpu, pic, ppu, serial, timer};
 use thiserror::Error;
 use wrange::Wrange;
 
-use super::{Command, Keyword, Result, Rule, Select, Serial, Tick, Value};
+use super::{Command, Keyword, Record, Result, Rule, Select, Serial, Tick, Value};
 
 #[allow(clippy::cast_sign_loss)]
 #[allow(clippy::too_many_lines)]
@@ -127,6 +127,29 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
                 rule => return rule.exception(),
             }
         }
+        Rule::Record => {
+            let force = args
+                .peek()
+                .filter(|pair| pair.as_rule() == Rule::Force)
+                .inspect(|_| {
+                    args.next(); // consume only if found
+                })
+                .is_some();
+            let pair = args.next().exception()?;
+            let mode = match pair.as_rule() {
+                Rule::Start => {
+                    let path = pair
+                        .into_inner()
+                        .next()
+                        .map(|pair| PathBuf::from(pair.as_span().as_str().to_string()))
+                        .exception()?;
+                    Record::Start(path, force)
+                }
+                Rule::Stop => Record::Stop,
+                rule => return rule.exception(),
+            };
+            Command::Record(mode)
+        }
         Rule::Reset => Command::Reset,
         Rule::Serial => {
             let pair = args.next().exception()?;
@@ -354,6 +377,7 @@ pub fn keyword(pair: Pair<Rule>) -> Result<Keyword> {
         Rule::KLog      => Keyword::Log,
         Rule::KQuit     => Keyword::Quit,
         Rule::KRead     => Keyword::Read,
+        Rule::KRecord   => Keyword::Record,
         Rule::KReset    => Keyword::Reset,
         Rule::KSerial   => Keyword::Serial,
         Rule::KStep     => Keyword::Step,
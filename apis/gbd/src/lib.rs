@@ -4,13 +4,18 @@
 #![allow(clippy::result_large_err)]
 
 use std::fmt::{Debug, Display, Write};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
 
 use indexmap::IndexMap;
 use log::debug;
 use rugby::arch::reg::Port;
 use rugby::arch::{Block, Clock};
 use rugby::core::dmg::{cpu, ppu, GameBoy};
+use rugby::pal::Scheme;
 use rugby::prelude::*;
+use rugby::vid::{self, Vid};
 use thiserror::Error;
 
 use self::lang::Program;
@@ -30,6 +35,7 @@ pub struct Debugger {
     cycle: usize,
     line: Option<Box<dyn Prompt>>,
     log: Option<Portal<String>>,
+    rec: Option<(Scheme, vid::Options)>,
     // Console
     pc: u16,
     state: State,
@@ -40,6 +46,7 @@ pub struct Debugger {
     prog: Option<Program>,
     prev: Option<Program>,
     bpts: IndexMap<u16, Option<Breakpoint>>,
+    vid: Option<(PathBuf, Vid<BufWriter<File>>)>,
 }
 
 impl Debugger {
@@ -63,6 +70,13 @@ impl Debugger {
         self.line = Some(line);
     }
 
+    /// Sets the video recording options.
+    ///
+    /// Used to record video with the palettes used for display.
+    pub fn recorder(&mut self, pal: Scheme, cfg: vid::Options) {
+        self.rec = Some((pal, cfg));
+    }
+
     /// Enables the debugger.
     pub fn enable(&mut self) {
         self.step = Some(0);
@@ -96,6 +110,16 @@ impl Debugger {
             dot: ppu.dot(),
             ppu: ppu.mode().clone(),
         };
+
+        // Record completed frames
+        if ppu.vsync() {
+            if let Some((path, vid)) = self.vid.as_mut() {
+                if let Err(err) = vid.write(ppu.layers()) {
+                    advise::error!("failed to record video: `{}`: {err}", path.display());
+                    self.vid = None;
+                }
+            }
+        }
     }
 
     /// Informs the user of the current emulation context.
@@ -251,6 +275,7 @@ impl Debugger {
             Quit                    => exec::quit(),
             Read(addr)              => exec::read(emu, addr),
             ReadRange(range)        => exec::read_range(emu, range),
+            Record(mode)            => exec::record(self, mode),
             Reset                   => exec::reset(self, emu),
             Serial(mode)            => exec::serial(emu, mode),
             Step(many)              => exec::step(self, many),
@@ -435,6 +460,9 @@ pub enum Error {
     /// Logger has not been configured.
     #[error("logger not configured")]
     CfgLogger,
+    /// Recorder has not been configured.
+    #[error("recorder not configured")]
+    CfgRecorder,
     /// Prompt returned empty string.
     #[error("no input provided")]
     Empty,
@@ -450,6 +478,9 @@ pub enum Error {
     /// Prompt returned an error.
     #[error(transparent)]
     Prompt(#[from] prompt::Error),
+    /// Video recording error.
+    #[error(transparent)]
+    Video(#[from] vid::Error),
     /// Quit requested by user.
     #[error("quit requested by user")]
     Quit,
@@ -1,16 +1,22 @@
 //! Application frontend.
 
-use std::io::{self, Read, Write};
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
 
 use log::{debug, trace};
-use minifb::Key;
 use rugby::app::audio::Audio;
 use rugby::app::joypad::Joypad;
+use rugby::app::motion::Motion;
 use rugby::app::serial::Serial;
 use rugby::app::video::Video;
+use rugby::core::dmg::ppu::Source;
 use rugby::core::dmg::{self, Button};
+use rugby::emu::part::audio::Sample;
 use rugby::emu::part::joypad::Event;
-use rugby::pal::Palette;
+use rugby::emu::part::motion;
+use rugby::pal::Scheme;
+use rugby::vid::Vid;
+use rugby::wav::Wav;
 
 mod win;
 
@@ -18,13 +24,42 @@ pub use std::net::UdpSocket as Cable;
 
 #[cfg(feature = "win")]
 pub use self::win::dbg;
-pub use self::win::Graphics;
+pub use self::win::{key, Graphics, Key};
+
+/// Audio sample rate.
+pub const RATE: u32 = 48_000;
+
+/// Simulated tilt (in g) while a tilt key is held.
+const TILT: f32 = 0.5;
+
+/// Audio recording.
+pub type Recording = Wav<BufWriter<File>>;
+
+/// Video recording.
+pub type Footage = Vid<BufWriter<File>>;
 
 /// Frontend options.
 #[derive(Debug)]
 pub struct Options {
-    /// Color palette.
-    pub pal: Palette,
+    /// Color palettes.
+    pub pal: Scheme,
+    /// Tilt keys.
+    pub tilt: Tilt,
+    /// Rewind key.
+    pub rewind: Key,
+}
+
+/// Tilt key bindings.
+#[derive(Debug)]
+pub struct Tilt {
+    /// Tilt left.
+    pub left: Key,
+    /// Tilt right.
+    pub right: Key,
+    /// Tilt up (away from the player).
+    pub up: Key,
+    /// Tilt down (towards the player).
+    pub down: Key,
 }
 
 /// Application frontend.
@@ -34,11 +69,43 @@ pub struct Frontend {
     pub cfg: Options,
     /// Link cable.
     pub lnk: Option<Cable>,
+    /// Audio recording.
+    pub wav: Option<Recording>,
+    /// Video recording.
+    pub vid: Option<Footage>,
     /// Window graphics.
     pub win: Option<Graphics>,
 }
 
-impl Audio for Frontend {}
+impl Frontend {
+    /// Checks if the rewind key is held.
+    pub fn rewinding(&self) -> bool {
+        self.win
+            .as_ref()
+            .is_some_and(|gui| gui.alive() && gui.lcd.held(self.cfg.rewind))
+    }
+
+    /// Appends a frame to the video recording.
+    pub fn record(&mut self, frame: &[dmg::ppu::Tagged]) -> io::Result<()> {
+        // Extract video recording
+        let Some(vid) = self.vid.as_mut() else {
+            return Ok(());
+        };
+        // Append to recording
+        vid.write(frame)
+    }
+}
+
+impl Audio for Frontend {
+    fn play(&mut self, buf: &[Sample]) -> io::Result<()> {
+        // Extract audio recording
+        let Some(wav) = self.wav.as_mut() else {
+            return Ok(());
+        };
+        // Append to recording
+        wav.play(buf)
+    }
+}
 
 impl Joypad for Frontend {
     type Button = Button;
@@ -67,6 +134,27 @@ impl Joypad for Frontend {
     }
 }
 
+impl Motion for Frontend {
+    fn tilt(&mut self) -> motion::Tilt {
+        // Extract GUI
+        let Some(gui) = self.win.as_ref() else {
+            return motion::Tilt::default();
+        };
+        // Combine opposing keys
+        let axis = |neg, pos| match (gui.lcd.held(neg), gui.lcd.held(pos)) {
+            (true, false) => -TILT,
+            (false, true) => TILT,
+            _ => 0.,
+        };
+        // Calculate tilt
+        let keys = &self.cfg.tilt;
+        motion::Tilt {
+            x: axis(keys.left, keys.right),
+            y: axis(keys.up, keys.down),
+        }
+    }
+}
+
 impl Serial for Frontend {
     fn recv(&mut self, mut tx: impl Read) -> io::Result<usize> {
         // Extract remote link
@@ -111,7 +199,7 @@ impl Serial for Frontend {
 }
 
 impl Video for Frontend {
-    type Pixel = dmg::ppu::Color;
+    type Pixel = dmg::ppu::Tagged;
 
     fn draw(&mut self, frame: &[Self::Pixel]) {
         // Extract GUI
@@ -121,7 +209,14 @@ impl Video for Frontend {
         // Translate pixels
         let frame = frame
             .iter()
-            .map(|&pix| self.cfg.pal[pix as usize])
+            .map(|pix| {
+                let pal = match pix.src {
+                    Source::Background | Source::Window => &self.cfg.pal.bg,
+                    Source::Obp0 => &self.cfg.pal.obj0,
+                    Source::Obp1 => &self.cfg.pal.obj1,
+                };
+                pal[pix.col as usize]
+            })
             .map(u32::from)
             .collect::<Vec<_>>();
         // Redraw main window
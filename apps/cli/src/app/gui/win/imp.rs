@@ -69,6 +69,11 @@ impl Window {
         self.win.update_with_buffer(buf, self.asp.wd, self.asp.ht)
     }
 
+    /// Checks if a key is currently held down.
+    pub fn held(&self, key: Key) -> bool {
+        self.win.is_key_down(key)
+    }
+
     /// Update internal key events.
     pub fn keys(&self) -> Vec<Event<Key>> {
         // Get keys
@@ -87,3 +92,34 @@ impl Window {
         dn.chain(up).collect()
     }
 }
+
+/// Looks up a key by its name.
+///
+/// Names are case-insensitive, and include letters, digits, arrows, numpad
+/// digits, and some editing keys (e.g. `"A"`, `"0"`, `"Left"`, `"NumPad4"`,
+/// `"Backspace"`).
+#[rustfmt::skip]
+pub fn key(name: &str) -> Option<Key> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "a" => Key::A, "b" => Key::B, "c" => Key::C, "d" => Key::D,
+        "e" => Key::E, "f" => Key::F, "g" => Key::G, "h" => Key::H,
+        "i" => Key::I, "j" => Key::J, "k" => Key::K, "l" => Key::L,
+        "m" => Key::M, "n" => Key::N, "o" => Key::O, "p" => Key::P,
+        "q" => Key::Q, "r" => Key::R, "s" => Key::S, "t" => Key::T,
+        "u" => Key::U, "v" => Key::V, "w" => Key::W, "x" => Key::X,
+        "y" => Key::Y, "z" => Key::Z,
+        "0" => Key::Key0, "1" => Key::Key1, "2" => Key::Key2, "3" => Key::Key3,
+        "4" => Key::Key4, "5" => Key::Key5, "6" => Key::Key6, "7" => Key::Key7,
+        "8" => Key::Key8, "9" => Key::Key9,
+        "numpad0" => Key::NumPad0, "numpad1" => Key::NumPad1,
+        "numpad2" => Key::NumPad2, "numpad3" => Key::NumPad3,
+        "numpad4" => Key::NumPad4, "numpad5" => Key::NumPad5,
+        "numpad6" => Key::NumPad6, "numpad7" => Key::NumPad7,
+        "numpad8" => Key::NumPad8, "numpad9" => Key::NumPad9,
+        "left"  => Key::Left,  "right" => Key::Right,
+        "up"    => Key::Up,    "down"  => Key::Down,
+        "backspace" => Key::Backspace, "delete" => Key::Delete,
+        "tab"       => Key::Tab,
+        _ => return None,
+    })
+}
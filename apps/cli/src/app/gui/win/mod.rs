@@ -10,7 +10,7 @@ mod imp;
 #[cfg(feature = "win")]
 pub mod dbg;
 
-pub use self::imp::{Aspect, Window};
+pub use self::imp::{key, Aspect, Key, Window};
 
 /// Graphics window groups.
 #[derive(Debug)]
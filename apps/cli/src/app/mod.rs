@@ -4,16 +4,18 @@
 use std::io::Write;
 #[cfg(feature = "gbd")]
 use std::sync::mpsc;
-use std::time::Instant;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use anyhow::Context as _;
-use log::debug;
+use log::{debug, info};
 use rugby::arch::{Block, Clock};
 #[cfg(any(feature = "doc", feature = "win"))]
 use rugby::core::dmg;
 #[cfg(feature = "doc")]
 use rugby::core::dmg::cpu::Stage;
-use rugby::core::dmg::{Cartridge, GameBoy};
+use rugby::core::dmg::{ppu, Cartridge, GameBoy, FREQ};
+use rugby::emu::part::{rumble, speaker};
 use rugby::prelude::*;
 #[cfg(feature = "gbd")]
 use rugby_gbd::Debugger;
@@ -28,8 +30,10 @@ use crate::NAME;
 mod ctx;
 
 pub mod gui;
+pub mod rewind;
 
 pub use self::gui::{Frontend, Graphics};
+pub use self::rewind::Rewind;
 
 /// Clock divider.
 ///
@@ -46,6 +50,8 @@ pub struct App {
     pub emu: GameBoy,
     /// Graphical frontend.
     pub gui: Frontend,
+    /// Rewind history.
+    pub rew: Option<Rewind>,
     /// Debug features.
     #[cfg(feature = "debug")]
     pub dbg: Debug,
@@ -157,12 +163,15 @@ impl App {
             // Emulate a single cycle
             self.emu.cycle();
 
-            // Send joypad input
+            // Send joypad, motion input
             if count.cycle() % 40 == 0 {
                 // Fetch keys
                 let keys = self.gui.input();
                 // Update emulator
                 self.emu.inside_mut().joypad().recv(keys);
+                // Update motion sensor
+                let tilt = self.gui.tilt();
+                self.emu.inside_mut().motion().set_tilt(tilt);
             }
 
             // Sync serial data
@@ -176,13 +185,69 @@ impl App {
                 self.gui.send(tx).context("failed to send serial data")?;
             }
 
+            // Play buffered audio
+            //
+            // NOTE: Audio is drained independently of the PPU, as no frames
+            //       are produced while the LCD is disabled.
+            if count.cycle() % 0x10000 == 0 {
+                let audio = self.emu.inside_mut().audio().drain();
+                self.gui.play(&audio).context("failed to play audio")?;
+            }
+
             // Draw next frame
-            let video = self.emu.inside().video();
-            if video.vsync() {
+            if self.emu.inside().video().vsync() {
+                // Log rumble events
+                for event in self.emu.inside_mut().rumble().events() {
+                    match event {
+                        rumble::Event::Start => info!("rumble motor started"),
+                        rumble::Event::Stop => info!("rumble motor stopped"),
+                    }
+                }
+
+                // Log speaker events
+                for event in self.emu.inside_mut().speaker().events() {
+                    match event {
+                        speaker::Event::Play(tone) => info!("speaker played tone: {tone}"),
+                        speaker::Event::Stop => info!("speaker stopped"),
+                    }
+                }
+
                 // Borrow frame
-                let frame = video.frame();
+                let frame = self.emu.inside().video().layers();
                 // Redraw screen
                 self.gui.draw(frame);
+                // Record video
+                self.gui.record(frame).context("failed to record video")?;
+
+                // Rewind while held
+                if let Some(rew) = self.rew.as_mut() {
+                    if self.gui.rewinding() {
+                        // Pause emulator clock
+                        clk.as_mut().map(Clock::pause);
+                        // Unwind history, one snapshot per frame
+                        while self.gui.rewinding() {
+                            if let Some(state) = rew.pop() {
+                                self.emu
+                                    .load_state(state.as_slice())
+                                    .context("failed to load snapshot")?;
+                            }
+                            // Redraw screen
+                            let frame = self.emu.inside().video().layers();
+                            self.gui.draw(frame);
+                            // Delay until next frame
+                            thread::sleep(Duration::from_secs(1) * ppu::RATE / FREQ);
+                        }
+                        // Resume emulator clock
+                        clk.as_mut().map(Clock::resume);
+                    } else if rew.tick() {
+                        // Capture snapshot
+                        let mut state = Vec::new();
+                        self.emu
+                            .save_state(&mut state)
+                            .context("failed to save snapshot")?;
+                        rew.push(state);
+                    }
+                }
 
                 // Redraw debug windows
                 #[cfg(feature = "win")]
@@ -191,7 +256,8 @@ impl App {
                         // Gather debug info
                         let info = dmg::dbg::ppu(&mut self.emu);
                         // Extract PPU state
-                        let recolor = |col: dmg::ppu::Color| self.gui.cfg.pal[col as usize].into();
+                        let recolor =
+                            |col: dmg::ppu::Color| self.gui.cfg.pal.bg[col as usize].into();
                         let tdat = info.tdat.into_iter().map(recolor).collect::<Box<_>>();
                         let map1 = info.map1.into_iter().map(recolor).collect::<Box<_>>();
                         let map2 = info.map2.into_iter().map(recolor).collect::<Box<_>>();
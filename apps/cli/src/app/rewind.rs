@@ -0,0 +1,287 @@
+//! Rewind history.
+
+use std::collections::VecDeque;
+
+/// Rewind options.
+#[derive(Debug)]
+pub struct Options {
+    /// Frames between snapshots.
+    pub every: u16,
+    /// Maximum number of snapshots.
+    pub limit: usize,
+    /// Maximum memory usage, in bytes.
+    pub memory: usize,
+}
+
+/// Rewind history.
+///
+/// Snapshots are kept in a ring buffer, with only the latest stored in full.
+/// Each older snapshot is delta-encoded against its successor, allowing the
+/// history to be unwound one snapshot at a time. When full, the oldest
+/// snapshots are discarded.
+#[derive(Debug)]
+pub struct Rewind {
+    /// Rewind options.
+    cfg: Options,
+    /// Frame counter.
+    frame: u16,
+    /// Latest snapshot.
+    last: Option<Vec<u8>>,
+    /// Older snapshot deltas, from oldest to newest.
+    hist: VecDeque<Vec<u8>>,
+    /// Size of stored deltas.
+    size: usize,
+}
+
+impl Rewind {
+    /// Constructs a new `Rewind`.
+    pub fn new(cfg: Options) -> Self {
+        Self {
+            cfg,
+            frame: 0,
+            last: None,
+            hist: VecDeque::new(),
+            size: 0,
+        }
+    }
+
+    /// Advances by a frame, returning whether a snapshot is due.
+    pub fn tick(&mut self) -> bool {
+        self.frame = self.frame.saturating_add(1);
+        let due = self.frame >= self.cfg.every;
+        if due {
+            self.frame = 0;
+        }
+        due
+    }
+
+    /// Pushes a new snapshot.
+    pub fn push(&mut self, state: Vec<u8>) {
+        if self.cfg.limit == 0 {
+            return;
+        }
+        // Replace the latest snapshot, keeping its delta
+        if let Some(prev) = self.last.replace(state) {
+            let delta = encode(&prev, self.last.as_deref().unwrap_or_default());
+            self.size += delta.len();
+            self.hist.push_back(delta);
+        }
+        // Discard the oldest snapshots
+        let full = self.last.as_ref().map_or(0, Vec::len);
+        while self.hist.len() >= self.cfg.limit || self.size + full > self.cfg.memory {
+            let Some(delta) = self.hist.pop_front() else {
+                break;
+            };
+            self.size -= delta.len();
+        }
+    }
+
+    /// Pops the latest snapshot.
+    ///
+    /// Returns `None` once the history has been exhausted.
+    pub fn pop(&mut self) -> Option<Vec<u8>> {
+        let last = self.last.take()?;
+        // Reconstruct the previous snapshot
+        if let Some(delta) = self.hist.pop_back() {
+            self.size -= delta.len();
+            self.last = Some(decode(&delta, &last));
+        }
+        // Restart the frame counter
+        self.frame = 0;
+        Some(last)
+    }
+}
+
+/// Encodes a snapshot as a delta against its successor.
+///
+/// The snapshots are combined by XOR, with runs of unchanged bytes then being
+/// run-length encoded. Each run is stored as its number of unchanged bytes,
+/// followed by the number and values of changed bytes.
+fn encode(old: &[u8], new: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    put(&mut out, old.len());
+    // Compare against successor
+    let mut xor = old
+        .iter()
+        .enumerate()
+        .map(|(idx, byte)| byte ^ new.get(idx).copied().unwrap_or_default())
+        .peekable();
+    // Encode runs
+    while xor.peek().is_some() {
+        let mut same = 0;
+        while xor.next_if_eq(&0).is_some() {
+            same += 1;
+        }
+        let diff: Vec<_> = std::iter::from_fn(|| xor.next_if(|&byte| byte != 0)).collect();
+        put(&mut out, same);
+        put(&mut out, diff.len());
+        out.extend(diff);
+    }
+    out
+}
+
+/// Decodes a snapshot from a delta against its successor.
+fn decode(delta: &[u8], new: &[u8]) -> Vec<u8> {
+    let mut delta = delta.iter().copied();
+    let len = get(&mut delta).unwrap_or_default();
+    let mut out = Vec::with_capacity(len);
+    // Compare against successor
+    let base = |idx: usize| new.get(idx).copied().unwrap_or_default();
+    // Decode runs
+    while out.len() < len {
+        let (Some(same), Some(diff)) = (get(&mut delta), get(&mut delta)) else {
+            break;
+        };
+        for _ in 0..same {
+            out.push(base(out.len()));
+        }
+        for byte in delta.by_ref().take(diff) {
+            out.push(byte ^ base(out.len()));
+        }
+    }
+    out
+}
+
+/// Writes a variable-length integer.
+fn put(out: &mut Vec<u8>, mut val: usize) {
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        let byte = (val & 0x7f) as u8;
+        val >>= 7;
+        if val == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Reads a variable-length integer.
+fn get(buf: &mut impl Iterator<Item = u8>) -> Option<usize> {
+    let mut val = 0;
+    for shift in (0..usize::BITS).step_by(7) {
+        let byte = buf.next()?;
+        val |= usize::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(val);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(limit: usize, memory: usize) -> Rewind {
+        Rewind::new(Options {
+            every: 1,
+            limit,
+            memory,
+        })
+    }
+
+    #[test]
+    fn varint_round_trip_works() {
+        for val in [0, 1, 0x7f, 0x80, 0x3fff, 0x4000, usize::MAX] {
+            let mut buf = Vec::new();
+            put(&mut buf, val);
+            assert_eq!(get(&mut buf.into_iter()), Some(val));
+        }
+    }
+
+    #[test]
+    fn varint_truncated_fails() {
+        let mut buf = Vec::new();
+        put(&mut buf, 0x4000);
+        buf.pop();
+        assert_eq!(get(&mut buf.into_iter()), None);
+    }
+
+    #[test]
+    fn delta_round_trip_works() {
+        let new: Vec<u8> = (0..=0xff).collect();
+        let cases: [Vec<u8>; 5] = [
+            // Same length
+            new.iter()
+                .map(|byte| byte ^ u8::from(byte % 3 == 0))
+                .collect(),
+            // Shorter
+            new[..0x40].to_vec(),
+            // Longer
+            (0..0x400)
+                .map(|idx: u16| idx.to_le_bytes()[0] ^ 0x5a)
+                .collect(),
+            // Unchanged
+            new.clone(),
+            // Empty
+            Vec::new(),
+        ];
+        for old in cases {
+            let delta = encode(&old, &new);
+            assert_eq!(decode(&delta, &new), old);
+        }
+    }
+
+    #[test]
+    fn delta_unchanged_works() {
+        let new = vec![0xaa; 0x1000];
+        let delta = encode(&new, &new);
+        assert!(delta.len() < 8);
+    }
+
+    #[test]
+    fn push_pop_works() {
+        let mut rwd = setup(8, usize::MAX);
+        let states: Vec<Vec<u8>> = (0..4u8)
+            .map(|idx| vec![idx; 0x10 + usize::from(idx)])
+            .collect();
+        for state in &states {
+            rwd.push(state.clone());
+        }
+        for state in states.iter().rev() {
+            assert_eq!(rwd.pop().as_ref(), Some(state));
+        }
+        assert_eq!(rwd.pop(), None);
+    }
+
+    #[test]
+    fn push_limit_works() {
+        let mut rwd = setup(3, usize::MAX);
+        for idx in 0..8u8 {
+            rwd.push(vec![idx; 0x10]);
+        }
+        // Only the latest snapshots are kept
+        assert_eq!(rwd.pop(), Some(vec![7; 0x10]));
+        assert_eq!(rwd.pop(), Some(vec![6; 0x10]));
+        assert_eq!(rwd.pop(), Some(vec![5; 0x10]));
+        assert_eq!(rwd.pop(), None);
+    }
+
+    #[test]
+    fn push_memory_works() {
+        let mut rwd = setup(usize::MAX, 0x100);
+        for idx in 0..8u8 {
+            rwd.push((0..0x80).map(|byte: u8| byte.wrapping_mul(idx)).collect());
+        }
+        // Stored deltas remain within the budget
+        assert!(rwd.size + 0x80 <= 0x100);
+        assert!(rwd.hist.len() < 7);
+        // Remaining snapshots are intact
+        let mut idx = 8;
+        while let Some(state) = rwd.pop() {
+            idx -= 1;
+            let want: Vec<u8> = (0..0x80).map(|byte: u8| byte.wrapping_mul(idx)).collect();
+            assert_eq!(state, want);
+        }
+        assert!(idx > 0);
+        assert_eq!(rwd.size, 0);
+    }
+
+    #[test]
+    fn push_disabled_works() {
+        let mut rwd = setup(0, usize::MAX);
+        rwd.push(vec![0; 0x10]);
+        assert_eq!(rwd.pop(), None);
+    }
+}
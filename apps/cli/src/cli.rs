@@ -65,6 +65,67 @@ pub struct Runtime {
     /// Starts without initializing or opening the UI.
     #[clap(short = 'H', long)]
     pub headless: bool,
+
+    /// Record audio to a file.
+    ///
+    /// Writes the emulator's audio output to the provided path as a WAV file.
+    /// As this does not require any audio device, it can be used alongside
+    /// `--headless`.
+    #[clap(long)]
+    #[clap(value_name = "PATH")]
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub record_audio: Option<PathBuf>,
+
+    /// Record video to a file.
+    ///
+    /// Writes every frame to the provided path, after palette mapping. The
+    /// format is determined by the file extension: `.gif` for an animated GIF,
+    /// `.png` for an animated PNG, or `.y4m` for raw video suitable for piping
+    /// into other encoders. As this does not require any display, it can be
+    /// used alongside `--headless`.
+    #[clap(long)]
+    #[clap(value_name = "PATH")]
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub record_video: Option<PathBuf>,
+
+    /// Video frames to skip.
+    ///
+    /// Skips this many frames after each recorded frame, reducing the frame
+    /// rate of video recordings.
+    #[clap(long)]
+    #[clap(value_name = "COUNT")]
+    #[clap(default_value_t = 0)]
+    pub video_skip: u16,
+
+    /// Video scaling factor.
+    ///
+    /// Scales video recordings by an integer factor.
+    #[clap(long)]
+    #[clap(value_name = "FACTOR")]
+    #[clap(default_value_t = 1)]
+    #[clap(value_parser = clap::value_parser!(u8).range(1..))]
+    pub video_scale: u8,
+
+    /// Record an input movie to a file.
+    ///
+    /// Records every joypad input along with the exact cycle it was applied,
+    /// starting from power-on. The movie is written to the provided path on
+    /// exit.
+    #[clap(long)]
+    #[clap(value_name = "PATH")]
+    #[clap(value_hint = ValueHint::FilePath)]
+    #[clap(conflicts_with = "play_movie")]
+    pub record_movie: Option<PathBuf>,
+
+    /// Play back an input movie from a file.
+    ///
+    /// Replays a previously recorded movie, reproducing its run exactly. Input
+    /// from the frontend is ignored until playback has finished. Cartridge RAM
+    /// is restored from the movie, and is not saved on exit.
+    #[clap(long)]
+    #[clap(value_name = "PATH")]
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub play_movie: Option<PathBuf>,
 }
 
 /// Serial connection.
@@ -50,22 +50,43 @@ fn run() -> Result<()> {
     trace!("{args:#?}");
 
     // Prepare emulator
-    let emu = build::emu(&args)?;
+    let mut emu = build::emu(&args)?;
     // Perform early exit
     if args.run.exit {
         return Ok(());
     }
+    // Start input movie
+    build::movie(&args.run, &mut emu).context("could not start movie")?;
     // Prepare application
     let mut app = build::app(&args, emu, log)?;
     // Run application
     app.run()?;
+    // Finish audio recording
+    if let Some(wav) = app.gui.wav.as_mut() {
+        wav.flush().context("could not save audio recording")?;
+    }
+    // Finish video recording
+    if let Some(vid) = app.gui.vid.as_mut() {
+        vid.flush().context("could not save video recording")?;
+    }
+    // Finish movie recording
+    if let Some(path) = args.run.record_movie.as_deref() {
+        build::save(path, app.emu.stop()).context("could not save movie")?;
+    }
     // Dump cartridge RAM
-    build::dump(
-        app.emu.eject().as_ref(),
-        args.cfg.emu.cart.ram().as_deref(),
-        args.cfg.emu.cart.save.unwrap_or_default(),
-    )
-    .context("could not dump RAM")?;
+    //
+    // NOTE: During playback, RAM is restored from the movie, so dumping it
+    //       would overwrite the save file.
+    if args.run.play_movie.is_some() {
+        warn!("skipped dumping RAM after movie playback");
+    } else {
+        build::dump(
+            app.emu.eject().as_ref(),
+            args.cfg.emu.cart.ram().as_deref(),
+            args.cfg.emu.cart.save.unwrap_or_default(),
+        )
+        .context("could not dump RAM")?;
+    }
 
     // Terminate normally
     Ok(())
@@ -74,7 +95,7 @@ fn run() -> Result<()> {
 /// Build helpers.
 mod build {
     use std::fs::File;
-    use std::io::Read;
+    use std::io::{BufReader, BufWriter, Read};
     use std::net::UdpSocket;
     use std::ops::Not;
     use std::path::Path;
@@ -82,15 +103,20 @@ mod build {
     use anyhow::{anyhow, ensure, Context, Result};
     use log::{debug, error, info, warn};
     use rugby::core::dmg::cart::mbc::Mbc;
-    use rugby::core::dmg::{Boot, Cartridge, GameBoy, LCD};
+    use rugby::core::dmg::movie::Movie;
+    use rugby::core::dmg::{ppu, Boot, Cartridge, GameBoy, FREQ, LCD};
     use rugby::emu::part::video;
-    use rugby_cfg::opt::emu::Tristate;
+    use rugby::pal::Scheme;
+    use rugby::prelude::*;
+    use rugby::vid;
+    use rugby_cfg::opt::app::{Rewind, Tilt};
+    use rugby_cfg::opt::emu::{Clock, Tristate};
     #[cfg(feature = "gbd")]
     use rugby_gbd::{Debugger, Portal};
     use tracing_subscriber::filter::LevelFilter;
     use tracing_subscriber::EnvFilter;
 
-    use crate::app::gui::Cable;
+    use crate::app::gui::{Cable, Footage, Recording};
     use crate::app::{self, App, Graphics};
     use crate::cli::{self, Cli};
     #[cfg(feature = "doc")]
@@ -144,7 +170,7 @@ mod build {
             let cart = &args.cfg.emu.cart;
             cart.rom
                 .as_deref()
-                .map(|path| self::cart(path, cart.check, cart.force))
+                .map(|path| self::cart(path, cart.check, cart.force, cart.rtc.unwrap_or_default()))
                 .transpose()
         }
         .context("could not load cartridge")?;
@@ -211,7 +237,7 @@ mod build {
     }
 
     /// Read and load a cartridge instance from a file.
-    fn cart(path: &Path, check: bool, force: bool) -> Result<Cartridge> {
+    fn cart(path: &Path, check: bool, force: bool, clock: Clock) -> Result<Cartridge> {
         // Read ROM file
         let rom = {
             // Open ROM file
@@ -236,14 +262,16 @@ mod build {
                 warn!("use of `--force` overrides `--check`");
             }
             // Force cartridge construction
-            Cartridge::unchecked
+            Cartridge::builder().check(false)
         } else if check {
             // Check cartridge integrity
-            Cartridge::checked
+            Cartridge::builder().check(true)
         } else {
             // Construct a cartridge
-            Cartridge::new
-        }(&rom)
+            Cartridge::builder()
+        }
+        .clock(clock.source())
+        .build(&rom)
         .with_context(|| format!("failed to load: `{}`", path.display()))?;
         info!("loaded cartridge:\n{}", cart.header());
 
@@ -253,7 +281,7 @@ mod build {
 
     /// Builds an application instance.
     #[allow(unused_variables)]
-    pub fn app(args: &Cli, emu: GameBoy, log: Log) -> Result<App> {
+    pub fn app(args: &Cli, mut emu: GameBoy, log: Log) -> Result<App> {
         // Initialize graphics
         let gui = args
             .run
@@ -268,6 +296,49 @@ mod build {
             .transpose()
             .context("could not open graphics")?;
 
+        // Open audio recording
+        let wav = args
+            .run
+            .record_audio
+            .as_deref()
+            .map(wav)
+            .transpose()
+            .context("could not open audio recording")?;
+        // Enable audio sampling
+        if let Some(wav) = &wav {
+            emu.inside_mut().audio().set_rate(Some(wav.rate()));
+        }
+
+        // Select color palettes
+        let pal = args.cfg.app.pal.clone().unwrap_or_default();
+        let pal = pal.resolve(emu.cart().map(Cartridge::header));
+
+        // Open video recording
+        let clip = vid::Options {
+            skip: args.run.video_skip,
+            scale: args.run.video_scale,
+        };
+        let vid = args
+            .run
+            .record_video
+            .as_deref()
+            .map(|path| vid(path, &pal, clip))
+            .transpose()
+            .context("could not open video recording")?;
+
+        // Map tilt keys
+        let tilt = tilt(&args.cfg.app.tilt.clone().unwrap_or_default())
+            .context("could not map tilt keys")?;
+
+        // Prepare rewind history
+        let (mut rew, rewind) = rewind(&args.cfg.app.rewind.clone().unwrap_or_default())
+            .context("could not prepare rewind")?;
+        // Rewinding would desynchronize input movies
+        if rew.is_some() && (args.run.record_movie.is_some() || args.run.play_movie.is_some()) {
+            warn!("rewind is disabled while using a movie");
+            rew = None;
+        }
+
         // Open link cable
         let lnk = args
             .link
@@ -291,7 +362,7 @@ mod build {
         let gbd = args
             .dbg
             .gbd
-            .then(|| gbd(log))
+            .then(|| gbd(log, &pal, clip))
             .transpose()
             .context("could not prepare debugger")?;
 
@@ -311,12 +382,13 @@ mod build {
             },
             emu,
             gui: app::Frontend {
-                cfg: app::gui::Options {
-                    pal: args.cfg.app.pal.clone().unwrap_or_default().into(),
-                },
+                cfg: app::gui::Options { pal, tilt, rewind },
                 win: gui,
                 lnk,
+                wav,
+                vid,
             },
+            rew,
         };
 
         // Return app
@@ -340,6 +412,41 @@ mod build {
         Ok(gui)
     }
 
+    /// Builds tilt key bindings.
+    fn tilt(keys: &Tilt) -> Result<app::gui::Tilt> {
+        // Look up each key by name
+        let key = |name: &str| app::gui::key(name).ok_or_else(|| anyhow!("unknown key: `{name}`"));
+        // Return key bindings
+        Ok(app::gui::Tilt {
+            left: key(&keys.left)?,
+            right: key(&keys.right)?,
+            up: key(&keys.up)?,
+            down: key(&keys.down)?,
+        })
+    }
+
+    /// Builds a rewind history instance, along with its key binding.
+    fn rewind(opts: &Rewind) -> Result<(Option<app::Rewind>, app::gui::Key)> {
+        // Look up key by name
+        let key = app::gui::key(&opts.key).ok_or_else(|| anyhow!("unknown key: `{}`", opts.key))?;
+        // Disable when no history is kept
+        if opts.length == 0 {
+            return Ok((None, key));
+        }
+        // Convert limits
+        let frames = u64::from(opts.length) * u64::from(FREQ / ppu::RATE);
+        let limit = frames / u64::from(opts.every.max(1));
+        let memory = usize::try_from(opts.memory)?.saturating_mul(0x0010_0000);
+        // Construct history
+        let rew = app::Rewind::new(app::rewind::Options {
+            every: opts.every.max(1),
+            limit: usize::try_from(limit)?,
+            memory,
+        });
+        // Return history and key
+        Ok((Some(rew), key))
+    }
+
     /// Builds a link cable instance.
     fn link(cli::Link { host, peer }: &cli::Link) -> Result<Cable> {
         // Bind host to local address
@@ -355,6 +462,72 @@ mod build {
         Ok(sock)
     }
 
+    /// Builds an audio recording instance.
+    fn wav(path: &Path) -> Result<Recording> {
+        // Create recording
+        Recording::create(path, app::gui::RATE)
+            .with_context(|| format!("failed to open: `{}`", path.display()))
+    }
+
+    /// Builds a video recording instance.
+    fn vid(path: &Path, pal: &Scheme, cfg: vid::Options) -> Result<Footage> {
+        // Create recording
+        Footage::create(path, pal, cfg)
+            .with_context(|| format!("failed to open: `{}`", path.display()))
+    }
+
+    /// Starts recording or playing back an input movie.
+    pub fn movie(run: &cli::Runtime, emu: &mut GameBoy) -> Result<()> {
+        // Start recording
+        if run.record_movie.is_some() {
+            emu.record().context("failed to start recording")?;
+            info!("recording movie");
+        }
+        // Start playback
+        if let Some(path) = run.play_movie.as_deref() {
+            // Open movie file
+            let file = File::open(path)
+                .with_context(|| format!("failed to open: `{}`", path.display()))?;
+            // Read movie
+            let movie = Movie::read(BufReader::new(file))
+                .with_context(|| format!("failed to read: `{}`", path.display()))?;
+            debug!(
+                "read {} input events: `{}`",
+                movie.input.len(),
+                path.display()
+            );
+            // Play movie
+            emu.play(movie)
+                .with_context(|| format!("failed to play: `{}`", path.display()))?;
+            info!("playing movie");
+        }
+
+        Ok(())
+    }
+
+    /// Saves a recorded input movie.
+    pub fn save(path: &Path, movie: Option<Movie>) -> Result<()> {
+        let Some(movie) = movie else {
+            return Ok(());
+        };
+
+        // Open movie file
+        let file =
+            File::create(path).with_context(|| format!("failed to open: `{}`", path.display()))?;
+
+        // Write movie
+        movie
+            .write(BufWriter::new(file))
+            .with_context(|| format!("failed to write: `{}`", path.display()))?;
+        info!(
+            "saved {} input events: `{}`",
+            movie.input.len(),
+            path.display()
+        );
+
+        Ok(())
+    }
+
     /// Builds a doctor logfile instance.
     #[cfg(feature = "doc")]
     fn doc(path: &Path) -> Result<Doctor> {
@@ -367,7 +540,7 @@ mod build {
 
     /// Builds a debugger instance.
     #[cfg(feature = "gbd")]
-    fn gbd(log: Log) -> Result<Debugger> {
+    fn gbd(log: Log, pal: &Scheme, clip: vid::Options) -> Result<Debugger> {
         // Construct a new `Debugger`
         let mut gbd = Debugger::new();
         // Initialize prompt handle
@@ -376,6 +549,8 @@ mod build {
         }));
         // Initialize logger handle
         gbd.logger(log);
+        // Initialize recorder options
+        gbd.recorder(pal.clone(), clip);
         // Return constructed debugger
         Ok(gbd)
     }
@@ -391,7 +566,7 @@ mod build {
         if save == Tristate::Never {
             return Ok(());
         }
-        if save == Tristate::Auto && cart.header().info.has_battery() {
+        if save == Tristate::Auto && !cart.header().info.has_battery() {
             return Ok(());
         }
         if !path.exists() {
@@ -427,7 +602,8 @@ mod build {
         if save == Tristate::Auto && !cart.header().info.has_battery() {
             return Ok(());
         }
-        if !cart.header().info.has_ram() {
+        // Cartridges with a real-time clock persist its state with RAM
+        if !(cart.header().info.has_ram() || cart.header().info.has_rtc()) {
             error!("cannot dump: cartridge does not support RAM");
             return Ok(());
         }
@@ -11,6 +11,7 @@ console.log("inserted a game cartridge");
 // Construct the application
 const app = {
     play: true,
+    rumble: false,
 };
 
 // Construct application state
@@ -79,6 +80,16 @@ setInterval(() => {
             }
             // Display the image in the canvas
             gui.screen.putImageData(image, 0, 0);
+
+            // Forward rumble to the device
+            const vib = emu.rumble();
+            if (vib !== undefined) {
+                app.rumble = vib;
+            }
+            if ((vib !== undefined || app.rumble) && "vibrate" in navigator) {
+                // Re-arm every frame while active, as each vibration expires
+                navigator.vibrate(app.rumble ? 100 : 0);
+            }
         }
     }
 }, 1000 / DIV);
@@ -1,5 +1,6 @@
 use rugby::arch::Block;
 use rugby::core::dmg;
+use rugby::emu::part::rumble::Event;
 use rugby::prelude::*;
 use wasm_bindgen::prelude::*;
 
@@ -56,3 +57,18 @@ impl GameBoy {
         )
     }
 }
+
+#[wasm_bindgen]
+impl GameBoy {
+    /// Polls the rumble motor, returning its state if it has changed.
+    ///
+    /// Intended to be forwarded to `navigator.vibrate`.
+    pub fn rumble(&mut self) -> Option<bool> {
+        self.0
+            .inside_mut()
+            .rumble()
+            .events()
+            .last()
+            .map(|&event| event == Event::Start)
+    }
+}
@@ -9,6 +9,7 @@ pub mod dev;
 pub mod mem;
 pub mod mio;
 pub mod reg;
+pub mod snap;
 
 use std::cell::RefCell;
 use std::ops::{Deref, DerefMut};
@@ -38,6 +38,12 @@ impl Bus {
         self.map.map(range, dev);
     }
 
+    /// Checks if an address is mapped to any device.
+    #[must_use]
+    pub fn contains(&self, addr: Word) -> bool {
+        self.map.select(addr).next().is_some()
+    }
+
     /// Unmaps and returns a device.
     ///
     /// Returns `None` if device is not mapped.
@@ -143,6 +149,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn map_shared_device_works() {
+        use std::hash::{BuildHasher, RandomState};
+
+        use super::imp::Entry;
+
+        // Entries sharing a device, but not a range, hash differently...
+        let dev = Shared::dev(Ram::from([0; 0x100]));
+        let a = Entry {
+            range: 0x000..=0x0ff,
+            entry: dev.clone(),
+        };
+        let b = Entry {
+            range: 0x000..=0x07f,
+            entry: dev.clone(),
+        };
+        let hash = RandomState::new();
+        assert_ne!(hash.hash_one(&a), hash.hash_one(&b));
+        // ...so must not compare equal
+        assert_ne!(a, b);
+
+        // Both mappings are retained
+        let mut bus = Bus::new();
+        bus.map(0x000..=0x0ff, dev.clone());
+        bus.map(0x000..=0x07f, dev);
+        assert_eq!(bus.map.iter().count(), 2);
+    }
+
+    #[test]
+    fn contains_works() {
+        let bus = setup();
+        assert!(bus.contains(0x000));
+        assert!(bus.contains(0x2ff));
+        assert!(!bus.contains(0x300));
+    }
+
     #[test]
     fn unmap_works() {
         let mut bus = Bus::new();
@@ -0,0 +1,258 @@
+//! Snapshot serialization.
+//!
+//! # Usage
+//!
+//! The [`Snapshot`] trait saves a component's state as compact little-endian
+//! binary data, which can later be restored in place.
+
+use std::collections::VecDeque;
+use std::io::{self, ErrorKind, Read, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::mem::{Memory, Ram};
+use crate::Shared;
+
+/// Serializable emulation state.
+///
+/// Snapshots are restored in place, overwriting the existing state. As such,
+/// [shared](Shared) state remains aliased between each of its owners (which
+/// should take care to save it only once).
+pub trait Snapshot {
+    /// Saves the state.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the state could not be written.
+    fn save(&self, buf: &mut impl Write) -> io::Result<()>;
+
+    /// Restores a previously saved state.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the state could not be read, or was invalid. When an error is
+    /// returned, the state may have been partially restored.
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()>;
+
+    /// Decodes a value from a previously saved state.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the state could not be read, or was invalid.
+    fn decode(buf: &mut impl Read) -> io::Result<Self>
+    where
+        Self: Default + Sized,
+    {
+        let mut this = Self::default();
+        this.restore(buf)?;
+        Ok(this)
+    }
+}
+
+macro_rules! int {
+    ($($int:ty),*) => {$(
+        impl Snapshot for $int {
+            fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+                buf.write_all(&self.to_le_bytes())
+            }
+
+            fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+                let mut data = [0; size_of::<$int>()];
+                buf.read_exact(&mut data)?;
+                *self = <$int>::from_le_bytes(data);
+                Ok(())
+            }
+        }
+    )*};
+}
+
+int!(u8, u16, u32, u64, u128, i8, i16, i32, i64);
+
+impl Snapshot for usize {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        (*self as u64).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = u64::decode(buf)?
+            .try_into()
+            .map_err(|_| ErrorKind::InvalidData)?;
+        Ok(())
+    }
+}
+
+impl Snapshot for bool {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        u8::from(*self).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = match u8::decode(buf)? {
+            0 => false,
+            1 => true,
+            _ => return Err(ErrorKind::InvalidData.into()),
+        };
+        Ok(())
+    }
+}
+
+/// Times are saved relative to the UNIX epoch.
+impl Snapshot for SystemTime {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        let time = self.duration_since(UNIX_EPOCH).unwrap_or_default();
+        time.as_secs().save(buf)?;
+        time.subsec_nanos().save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        let secs = u64::decode(buf)?;
+        let nanos = u32::decode(buf)?;
+        if nanos >= 1_000_000_000 {
+            return Err(ErrorKind::InvalidData.into());
+        }
+        *self = UNIX_EPOCH
+            .checked_add(Duration::new(secs, nanos))
+            .ok_or(ErrorKind::InvalidData)?;
+        Ok(())
+    }
+}
+
+impl<T: Snapshot> Snapshot for [T] {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.iter().try_for_each(|item| item.save(buf))
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.iter_mut().try_for_each(|item| item.restore(buf))
+    }
+}
+
+impl<T: Snapshot, const N: usize> Snapshot for [T; N] {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.as_slice().save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.as_mut_slice().restore(buf)
+    }
+}
+
+impl<T: Snapshot + ?Sized> Snapshot for Box<T> {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        (**self).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        (**self).restore(buf)
+    }
+}
+
+impl<T: Snapshot + Default> Snapshot for Vec<T> {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.len().save(buf)?;
+        self.as_slice().save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        let len = usize::decode(buf)?;
+        self.clear();
+        (0..len).try_for_each(|_| T::decode(buf).map(|item| self.push(item)))
+    }
+}
+
+impl<T: Snapshot + Default> Snapshot for VecDeque<T> {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.len().save(buf)?;
+        self.iter().try_for_each(|item| item.save(buf))
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        let len = usize::decode(buf)?;
+        self.clear();
+        (0..len).try_for_each(|_| T::decode(buf).map(|item| self.push_back(item)))
+    }
+}
+
+impl<T: Snapshot + Default> Snapshot for Option<T> {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.is_some().save(buf)?;
+        self.as_ref().map_or(Ok(()), |item| item.save(buf))
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        if bool::decode(buf)? {
+            self.get_or_insert_with(T::default).restore(buf)
+        } else {
+            self.take();
+            Ok(())
+        }
+    }
+}
+
+impl<T: Snapshot + ?Sized> Snapshot for Shared<T> {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.borrow().save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.borrow_mut().restore(buf)
+    }
+}
+
+impl<M: Memory + Snapshot> Snapshot for Ram<M> {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.inner().save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.inner_mut().restore(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_works() {
+        let data = (0x1234_u16, [0xaa_u8; 4], vec![true, false], Some(7_usize));
+        let mut buf = Vec::new();
+        data.0.save(&mut buf).unwrap();
+        data.1.save(&mut buf).unwrap();
+        data.2.save(&mut buf).unwrap();
+        data.3.save(&mut buf).unwrap();
+        assert_eq!(buf.len(), 2 + 4 + (8 + 2) + (1 + 8));
+
+        let mut buf = buf.as_slice();
+        assert_eq!(u16::decode(&mut buf).unwrap(), data.0);
+        assert_eq!(<[u8; 4]>::decode(&mut buf).unwrap(), data.1);
+        assert_eq!(Vec::<bool>::decode(&mut buf).unwrap(), data.2);
+        assert_eq!(Option::<usize>::decode(&mut buf).unwrap(), data.3);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn snapshot_shared_works() {
+        let ram = Shared::new(Ram::from([0_u8; 0x10]));
+        let alias = ram.clone();
+        let mut buf = Vec::new();
+        Shared::new(Ram::from([0x55_u8; 0x10]))
+            .save(&mut buf)
+            .unwrap();
+
+        // Restoring preserves aliasing
+        ram.clone().restore(&mut buf.as_slice()).unwrap();
+        assert_eq!(alias.read(0x0f), Ok(0x55));
+    }
+
+    #[test]
+    fn snapshot_invalid_fails() {
+        assert_eq!(
+            bool::decode(&mut [2].as_slice()).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+        assert_eq!(
+            u16::decode(&mut [0].as_slice()).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+}
@@ -16,6 +16,20 @@ pub trait Audio {
     fn audio_mut(&mut self) -> &mut Self::Audio;
 }
 
+/// Camera support.
+pub trait Camera {
+    /// Camera interface.
+    type Camera: part::camera::Camera;
+
+    /// Borrows the core's camera.
+    #[must_use]
+    fn camera(&self) -> &Self::Camera;
+
+    /// Mutably borrows the core's camera.
+    #[must_use]
+    fn camera_mut(&mut self) -> &mut Self::Camera;
+}
+
 /// Joypad support.
 pub trait Joypad {
     /// Joypad interface.
@@ -30,6 +44,20 @@ pub trait Joypad {
     fn joypad_mut(&mut self) -> &mut Self::Joypad;
 }
 
+/// Motion support.
+pub trait Motion {
+    /// Motion interface.
+    type Motion: part::motion::Motion;
+
+    /// Borrows the core's motion sensor.
+    #[must_use]
+    fn motion(&self) -> &Self::Motion;
+
+    /// Mutably borrows the core's motion sensor.
+    #[must_use]
+    fn motion_mut(&mut self) -> &mut Self::Motion;
+}
+
 /// Processor support.
 pub trait Processor {
     /// Compute interface.
@@ -44,6 +72,20 @@ pub trait Processor {
     fn proc_mut(&mut self) -> &mut Self::Proc;
 }
 
+/// Rumble support.
+pub trait Rumble {
+    /// Rumble interface.
+    type Rumble: part::rumble::Rumble;
+
+    /// Borrows the core's rumble motor.
+    #[must_use]
+    fn rumble(&self) -> &Self::Rumble;
+
+    /// Mutably borrows the core's rumble motor.
+    #[must_use]
+    fn rumble_mut(&mut self) -> &mut Self::Rumble;
+}
+
 /// Serial support.
 pub trait Serial {
     /// Serial interface.
@@ -58,6 +100,20 @@ pub trait Serial {
     fn serial_mut(&mut self) -> &mut Self::Serial;
 }
 
+/// Speaker support.
+pub trait Speaker {
+    /// Speaker interface.
+    type Speaker: part::speaker::Speaker;
+
+    /// Borrows the core's speaker.
+    #[must_use]
+    fn speaker(&self) -> &Self::Speaker;
+
+    /// Mutably borrows the core's speaker.
+    #[must_use]
+    fn speaker_mut(&mut self) -> &mut Self::Speaker;
+}
+
 /// Video support.
 pub trait Video {
     /// Video interface.
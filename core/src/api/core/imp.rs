@@ -16,6 +16,22 @@ impl<'a, C: Core + has::Audio> InsideMut<'a, C> {
     }
 }
 
+impl<'a, C: Core + has::Camera> Inside<'a, C> {
+    /// Borrow the core's camera.
+    #[must_use]
+    pub fn camera(self) -> &'a C::Camera {
+        self.0.camera()
+    }
+}
+
+impl<'a, C: Core + has::Camera> InsideMut<'a, C> {
+    /// Mutably borrow the core's camera.
+    #[must_use]
+    pub fn camera(self) -> &'a mut C::Camera {
+        self.0.camera_mut()
+    }
+}
+
 impl<'a, C: Core + has::Joypad> Inside<'a, C> {
     /// Borrow the core's joypad.
     #[must_use]
@@ -32,6 +48,22 @@ impl<'a, C: Core + has::Joypad> InsideMut<'a, C> {
     }
 }
 
+impl<'a, C: Core + has::Motion> Inside<'a, C> {
+    /// Borrow the core's motion sensor.
+    #[must_use]
+    pub fn motion(self) -> &'a C::Motion {
+        self.0.motion()
+    }
+}
+
+impl<'a, C: Core + has::Motion> InsideMut<'a, C> {
+    /// Mutably borrow the core's motion sensor.
+    #[must_use]
+    pub fn motion(self) -> &'a mut C::Motion {
+        self.0.motion_mut()
+    }
+}
+
 impl<'a, C: Core + has::Processor> Inside<'a, C> {
     /// Borrow the core's processor.
     #[must_use]
@@ -48,6 +80,22 @@ impl<'a, C: Core + has::Processor> InsideMut<'a, C> {
     }
 }
 
+impl<'a, C: Core + has::Rumble> Inside<'a, C> {
+    /// Borrow the core's rumble motor.
+    #[must_use]
+    pub fn rumble(self) -> &'a C::Rumble {
+        self.0.rumble()
+    }
+}
+
+impl<'a, C: Core + has::Rumble> InsideMut<'a, C> {
+    /// Mutably borrow the core's rumble motor.
+    #[must_use]
+    pub fn rumble(self) -> &'a mut C::Rumble {
+        self.0.rumble_mut()
+    }
+}
+
 impl<'a, C: Core + has::Serial> Inside<'a, C> {
     /// Borrow the core's serial.
     #[must_use]
@@ -64,6 +112,22 @@ impl<'a, C: Core + has::Serial> InsideMut<'a, C> {
     }
 }
 
+impl<'a, C: Core + has::Speaker> Inside<'a, C> {
+    /// Borrow the core's speaker.
+    #[must_use]
+    pub fn speaker(self) -> &'a C::Speaker {
+        self.0.speaker()
+    }
+}
+
+impl<'a, C: Core + has::Speaker> InsideMut<'a, C> {
+    /// Mutably borrow the core's speaker.
+    #[must_use]
+    pub fn speaker(self) -> &'a mut C::Speaker {
+        self.0.speaker_mut()
+    }
+}
+
 impl<'a, C: Core + has::Video> Inside<'a, C> {
     /// Borrow the core's video.
     #[must_use]
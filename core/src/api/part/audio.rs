@@ -1,4 +1,40 @@
 //! Audio API.
 
 /// Audio interface.
-pub trait Audio {}
+pub trait Audio {
+    /// Gets the output sample rate (in Hz).
+    ///
+    /// Returns `None` when sampling is disabled.
+    #[must_use]
+    fn rate(&self) -> Option<u32>;
+
+    /// Sets the output sample rate (in Hz).
+    ///
+    /// Output is resampled from the emulator's clock to the provided rate,
+    /// with any buffered samples being discarded. Sampling is disabled by
+    /// default, and can be disabled again using `None`.
+    ///
+    /// Rates outside of those supported by the emulator are clamped, such
+    /// that [`rate`](Self::rate) may differ from the requested rate.
+    fn set_rate(&mut self, rate: Option<u32>);
+
+    /// Drains buffered audio samples.
+    ///
+    /// Yields all samples produced since the previous call, in order. This
+    /// should be called at a regular interval of emulated cycles to prevent
+    /// the buffer from growing unbounded. Frames are not a suitable interval,
+    /// as none are produced while the LCD is disabled.
+    #[must_use]
+    fn drain(&mut self) -> Vec<Sample>;
+}
+
+/// Stereo audio sample.
+///
+/// Amplitudes are normalized to `-1.0..=1.0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Sample {
+    /// Left channel.
+    pub lt: f32,
+    /// Right channel.
+    pub rt: f32,
+}
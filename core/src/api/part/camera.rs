@@ -0,0 +1,29 @@
+//! Camera API.
+
+/// Image sensor width.
+pub const WIDTH: usize = 128;
+
+/// Image sensor height.
+pub const HEIGHT: usize = 112;
+
+/// Camera interface.
+///
+/// Connects an image [source](Source) to camera hardware, such as the sensor
+/// used by the Pocket Camera cartridge.
+pub trait Camera {
+    /// Sets the image source.
+    ///
+    /// All subsequent captures will be taken from the provided source. When
+    /// no source is connected, the sensor captures a blank image.
+    fn set_source(&mut self, src: Box<dyn Source>);
+}
+
+/// Image source.
+pub trait Source {
+    /// Captures an image.
+    ///
+    /// The produced image must contain [`WIDTH`] × [`HEIGHT`] pixels in
+    /// row-major order, each as an 8-bit luminance value (where `0x00` is
+    /// black and `0xff` is white).
+    fn capture(&mut self) -> Box<[u8]>;
+}
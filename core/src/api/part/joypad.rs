@@ -15,7 +15,7 @@ pub trait Joypad {
 pub trait Input: Copy + Eq + Hash {}
 
 /// Jopypad event.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Event<I>
 where
     I: Copy + Eq + Hash,
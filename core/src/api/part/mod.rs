@@ -1,7 +1,11 @@
 //! Parts interfaces.
 
 pub mod audio;
+pub mod camera;
 pub mod joypad;
+pub mod motion;
 pub mod proc;
+pub mod rumble;
 pub mod serial;
+pub mod speaker;
 pub mod video;
@@ -0,0 +1,29 @@
+//! Motion API.
+
+/// Motion interface.
+///
+/// Supplies readings to motion sensors, such as the accelerometer built into
+/// some cartridges.
+pub trait Motion {
+    /// Gets the current tilt.
+    #[must_use]
+    fn tilt(&self) -> Tilt;
+
+    /// Sets the current tilt.
+    ///
+    /// The provided tilt will be used for all subsequent sensor readings,
+    /// until it is updated again.
+    fn set_tilt(&mut self, tilt: Tilt);
+}
+
+/// Device tilt.
+///
+/// Measured as acceleration due to gravity along each axis, in units of
+/// standard gravity (g). When held flat, each axis reads as `0.0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tilt {
+    /// Horizontal axis (positive when tilted right).
+    pub x: f32,
+    /// Vertical axis (positive when tilted towards the player).
+    pub y: f32,
+}
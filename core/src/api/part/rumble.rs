@@ -0,0 +1,26 @@
+//! Rumble API.
+
+/// Rumble interface.
+///
+/// Reports activity of force feedback hardware, such as the motor built into
+/// some cartridges.
+pub trait Rumble {
+    /// Checks if the motor is running.
+    #[must_use]
+    fn active(&self) -> bool;
+
+    /// Polls motor events for the frontend.
+    ///
+    /// The produced vector contains an ordered list of all motor transitions
+    /// that occured since events were last polled.
+    fn events(&mut self) -> Vec<Event>;
+}
+
+/// Rumble motor event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// Motor started.
+    Start,
+    /// Motor stopped.
+    Stop,
+}
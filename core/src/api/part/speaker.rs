@@ -0,0 +1,26 @@
+//! Speaker API.
+
+/// Speaker interface.
+///
+/// Reports sounds played by auxiliary speakers, such as the piezo speaker
+/// built into some cartridges.
+pub trait Speaker {
+    /// Gets the tone being played, if any.
+    #[must_use]
+    fn tone(&self) -> Option<u8>;
+
+    /// Polls speaker events for the frontend.
+    ///
+    /// The produced vector contains an ordered list of all tones played, or
+    /// silenced, since events were last polled.
+    fn events(&mut self) -> Vec<Event>;
+}
+
+/// Speaker event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// Tone played.
+    Play(u8),
+    /// Speaker silenced.
+    Stop,
+}
@@ -15,4 +15,4 @@ pub mod api {
     pub mod part;
 }
 
-pub use crate::model::dmg;
+pub use crate::model::{cgb, dmg, sgb};
@@ -0,0 +1,115 @@
+//! Embedded memory blocks.
+
+use log::debug;
+use rugby_arch::mem::{Memory, Ram, Result};
+use rugby_arch::reg::Register;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+pub use crate::dmg::mem::{Bank, Hram, Oam};
+
+/// Work RAM.
+///
+/// 4 KiB RAM bank used as general-purpose transient memory.
+pub type Wram = Ram<[Byte; 0x1000]>;
+
+/// Bank select register.
+///
+/// Used to select which bank of [banked](Banked) memory is accessible, with
+/// unused bits reading as set.
+///
+/// | Address | Size | Name | Description        |
+/// |:-------:|------|------|--------------------|
+/// | `$FF4F` | 1bit | VBK  | VRAM bank select.  |
+/// | `$FF70` | 3bit | SVBK | WRAM bank select.  |
+#[derive(Debug)]
+pub struct Select {
+    /// Implemented bits.
+    mask: Byte,
+    /// Selected bank.
+    bank: Byte,
+}
+
+impl Select {
+    /// Constructs a new `Select` with the implemented bits.
+    #[must_use]
+    pub fn new(mask: Byte) -> Self {
+        Self { mask, bank: 0 }
+    }
+
+    /// Gets the selected bank.
+    #[must_use]
+    pub fn bank(&self) -> usize {
+        usize::from(self.bank)
+    }
+}
+
+impl Block for Select {
+    fn reset(&mut self) {
+        self.bank = 0;
+    }
+}
+
+impl Memory for Select {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Ok(self.load())
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Select {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        !self.mask | self.bank
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.bank = value & self.mask;
+        debug!("selected bank: {}", self.bank);
+    }
+}
+
+/// Banked memory.
+///
+/// Forwards accesses to whichever bank is chosen by its [select](Select)
+/// register.
+#[derive(Debug)]
+pub struct Banked<M> {
+    /// Bank select.
+    sel: Shared<Select>,
+    /// Memory banks.
+    mem: Box<[Shared<M>]>,
+}
+
+impl<M> Banked<M> {
+    /// Constructs a new `Banked`.
+    ///
+    /// Banks are indexed by the selected value, so must cover every value of
+    /// the select register.
+    #[must_use]
+    pub fn new(sel: Shared<Select>, mem: impl IntoIterator<Item = Shared<M>>) -> Self {
+        Self {
+            sel,
+            mem: mem.into_iter().collect(),
+        }
+    }
+
+    /// Gets the selected bank.
+    fn bank(&self) -> &Shared<M> {
+        &self.mem[self.sel.borrow().bank()]
+    }
+}
+
+impl<M: Memory> Memory for Banked<M> {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        self.bank().read(addr)
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        self.bank().borrow_mut().write(addr, data)
+    }
+}
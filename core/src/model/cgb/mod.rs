@@ -0,0 +1,262 @@
+//! CGB: [Game Boy Color].
+//!
+//! [Game Boy Color]: https://en.wikipedia.org/wiki/Game_Boy_Color
+
+use log::warn;
+use rugby_arch::mio::Mmio;
+use rugby_arch::reg::Port;
+use rugby_arch::{Block, Word};
+
+use self::apu::Apu;
+use self::cart::mbc::camera::Sensor;
+use self::cart::mbc::huc3::Piezo;
+use self::cart::mbc::mbc5::Motor;
+use self::cart::mbc::mbc7::Accelerometer;
+use self::cpu::Cpu;
+use self::joypad::Joypad;
+use self::pcb::Motherboard;
+use self::ppu::cgb::Lcd;
+use self::serial::Serial;
+use super::Peripherals;
+use crate::api::core::Core;
+use crate::api::part::proc::Processor;
+
+mod noc;
+mod soc;
+
+pub mod mem;
+pub mod pcb;
+
+pub use self::cart::Cartridge;
+pub use self::joypad::Button;
+pub use self::noc::Mmap;
+pub use self::soc::Chip;
+pub use crate::dmg::FREQ;
+pub use crate::parts::cpu::sm83 as cpu;
+pub use crate::parts::{apu, boot, cart, dma, hdma, joypad, pic, ppu, serial, timer};
+
+pub use self::ppu::LCD;
+
+/// Boot ROM.
+///
+/// 2304 byte ROM, of which the cartridge header's page (`$0100..=$01FF`) is
+/// unused.
+pub type Boot = boot::Boot<0x900>;
+
+/// Game Boy Color handheld game console.
+#[derive(Debug, Default)]
+pub struct GameBoy {
+    /// Boot ROM.
+    boot: Option<boot::Chip<0x900>>,
+    /// Game cartridge.
+    cart: Option<Cartridge>,
+    /// CGB-001 Motherboard.
+    main: Motherboard,
+    /// Color display.
+    lcd: Lcd,
+    /// Cartridge peripherals.
+    dev: Peripherals,
+}
+
+impl GameBoy {
+    /// Constructs a new `GameBoy`.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut this = Self::default();
+
+        // Simulate bootup sequence
+        this.boot();
+
+        this
+    }
+
+    /// Constructs a new `GameBoy`, initialized with the provided boot ROM.
+    #[must_use]
+    pub fn with(boot: Boot) -> Self {
+        let mut this = Self::default();
+
+        // Initialize boot ROM
+        let boot = boot::Chip::new(boot);
+        boot.attach(&mut this.main.noc.ibus.borrow_mut());
+        this.main.soc.key.borrow_mut().connect(boot.reg.clone());
+        this.boot = Some(boot);
+
+        this
+    }
+
+    /// Simulate the bootup sequence.
+    ///
+    /// This prepares the `GameBoy` to run the contents of a game cartridge.
+    /// When no [boot ROM](Boot) is installed, this must be called before
+    /// cartridge execution.
+    ///
+    /// Cartridges without the header's CGB flag are run in DMG compatibility
+    /// mode.
+    ///
+    /// Interrupts remain disabled once control is transferred, as the boot ROM
+    /// never enables them.
+    #[rustfmt::skip]
+    pub fn boot(&mut self) {
+        // Read cartridge header
+        self.scan();
+
+        // Select compatibility mode
+        let compat = self.cart.as_ref().is_some_and(|cart| !cart.header().cgb);
+        self.main.soc.key.borrow_mut().set(compat);
+
+        // Initialize color palettes
+        if let Some(cgb) = &self.main.soc.ppu.cgb {
+            let (mut bcp, mut ocp) = (cgb.bcp.borrow_mut(), cgb.ocp.borrow_mut());
+            if compat {
+                bcp.load(0, [0x7fff, 0x1bef, 0x0200, 0x0000]);
+                ocp.load(0, [0x7fff, 0x421f, 0x1cf2, 0x0000]);
+                ocp.load(1, [0x7fff, 0x421f, 0x1cf2, 0x0000]);
+            } else {
+                (0..8).for_each(|pal| bcp.load(pal, [0x7fff; 4]));
+            }
+        }
+
+        let cpu = &mut self.main.soc.cpu;
+
+        // Initialize registers
+        #[allow(clippy::items_after_statements)]
+        type Select = <Cpu as Port<Word>>::Select;
+        cpu.store(Select::AF, 0x1180_u16);
+        cpu.store(Select::BC, 0x0000_u16);
+        if compat {
+            cpu.store(Select::DE, 0x0008_u16);
+            cpu.store(Select::HL, 0x007c_u16);
+        } else {
+            cpu.store(Select::DE, 0xff56_u16);
+            cpu.store(Select::HL, 0x000d_u16);
+        }
+        cpu.store(Select::SP, 0xfffe_u16);
+
+        // Perform bootup sequence
+        cpu.write(0xff26, 0x80); // enable audio
+        cpu.write(0xff11, 0x80); // set CH1 duty cycle
+        cpu.write(0xff12, 0xf3); // set CH1 envelope
+        cpu.write(0xff25, 0xf3); // set panning
+        cpu.write(0xff24, 0x77); // set master volume
+        cpu.write(0xff13, 0xc1); // set CH1 period
+        cpu.write(0xff14, 0x87); // trigger CH1
+        cpu.write(0xff40, 0x91); // enable display
+        cpu.write(0xff50, 0x01); // disable boot ROM
+        cpu.goto(0x0100);        // transfer program control
+    }
+
+    /// Simulate the boot ROM's cartridge header reads.
+    ///
+    /// See [`dmg::GameBoy`](crate::dmg::GameBoy) for details. Unlike the DMG
+    /// and SGB, the CGB's boot ROM uses its own logo tiles and palettes, which
+    /// are not rendered.
+    fn scan(&self) {
+        if self.cart.is_none() {
+            return;
+        }
+        let cpu = &self.main.soc.cpu;
+        let logo = 0x0104..=0x0133;
+        for addr in logo.clone().chain(logo).chain(0x0134..=0x014d) {
+            let _ = cpu.read(addr);
+        }
+    }
+
+    /// Checks if running in DMG compatibility mode.
+    #[must_use]
+    pub fn compat(&self) -> bool {
+        !self.main.soc.ppu.native()
+    }
+
+    /// Gets the inserted game cartridge, if any.
+    #[must_use]
+    pub fn cart(&self) -> Option<&Cartridge> {
+        self.cart.as_ref()
+    }
+
+    /// Inserts a game cartridge.
+    ///
+    /// If a cartridge is already inserted, it will first be
+    /// [ejected](Self::eject).
+    pub fn insert(&mut self, cart: Cartridge) {
+        // Disconnect previous cartridge
+        if let Some(cart) = self.eject() {
+            warn!("ejected previous cartridge: {}", cart.header());
+        }
+        // Connect cartridge peripherals
+        self.dev.connect(&cart);
+        // Insert supplied cartridge
+        cart.attach(&mut self.main.noc.ebus.borrow_mut());
+        self.cart = Some(cart);
+        // Simulate boot ROM header reads and mode selection
+        if self.boot.is_none() {
+            self.boot();
+        }
+    }
+
+    /// Ejects the inserted game cartridge, if any.
+    pub fn eject(&mut self) -> Option<Cartridge> {
+        // Disconnect from bus
+        let ebus = &mut *self.main.noc.ebus.borrow_mut();
+        if let Some(cart) = &self.cart {
+            cart.detach(ebus);
+        }
+        // Remove inserted cartridge
+        self.cart.take()
+    }
+}
+
+impl Block for GameBoy {
+    fn ready(&self) -> bool {
+        self.main.ready()
+    }
+
+    fn cycle(&mut self) {
+        self.main.cycle();
+        self.lcd.sync(&self.main.soc.ppu);
+        // Cartridge: 4 MiHz
+        if let Some(cart) = self.cart.as_mut().filter(|cart| cart.ready()) {
+            cart.cycle();
+        }
+    }
+
+    #[rustfmt::skip]
+    fn reset(&mut self) {
+        self.main.reset();
+        self.cart.as_mut().map(Block::reset);
+        self.boot.as_mut().map(Block::reset).unwrap_or_else(|| self.boot());
+    }
+}
+
+impl Core for GameBoy {}
+
+has! {
+    GameBoy {
+        Audio(Audio = Apu) { audio, audio_mut } => main.soc.apu;
+        Motion(Motion = Accelerometer) { motion, motion_mut } => dev.acc;
+        Processor(Proc = Cpu) { proc, proc_mut } => main.soc.cpu;
+        Camera(Camera = Sensor) { camera, camera_mut } => dev.cam;
+        Joypad(Joypad = Joypad) { joypad, joypad_mut } => main.soc.joy;
+        Rumble(Rumble = Motor) { rumble, rumble_mut } => dev.vib;
+        Serial(Serial = Serial) { serial, serial_mut } => main.soc.ser;
+        Speaker(Speaker = Piezo) { speaker, speaker_mut } => dev.spk;
+        Video(Video = Lcd) { video, video_mut } => lcd;
+    }
+}
+
+/// Debug introspection.
+#[cfg(feature = "debug")]
+impl GameBoy {
+    /// Borrows the core's system-on-chip.
+    #[must_use]
+    pub fn chip(&self) -> &Chip {
+        &self.main.soc
+    }
+
+    /// Mutably borrows the core's system-on-chip.
+    pub fn chip_mut(&mut self) -> &mut Chip {
+        &mut self.main.soc
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,113 @@
+//! Network-on-chip.
+
+use rugby_arch::mio::Bus;
+use rugby_arch::Shared;
+
+use crate::parts::dma::{Arbiter, Control as Dma, Lock};
+use crate::parts::ppu::{Access, Guard, Region};
+
+/// Memory-mapped I/O.
+///
+/// As on the [DMG](crate::dmg::Mmap), memory is divided across three distinct
+/// buses:
+/// - [Internal](Self::ibus): Embedded within the CGB-CPU. Usable only by the
+///   [CPU](super::cpu).
+/// - [External](Self::ebus): Accessible to on-board components.
+/// - [Video](Self::vbus):    Connected only to VRAM, controlled by the
+///   [PPU](super::ppu).
+///
+/// # Memory Map
+///
+/// See more details [here][map].
+///
+/// |     Address     |  Size  |  Module  | Description      |    Bus    |
+/// |:---------------:|-------:|----------|------------------|-----------|
+/// | `$0000..=$00FF` |  256 B | `boot`   | Boot ROM         | Internal  |
+/// | `$0000..=$7FFF` | 32 KiB | `cart`   | Cartridge ROM    | External  |
+/// | `$0200..=$08FF` | 1792 B | `boot`   | Boot ROM         | Internal  |
+/// | `$8000..=$9FFF` |  8 KiB | `vram`   | Video RAM (0-1)  | Video     |
+/// | `$A000..=$BFFF` |  8 KiB | `cart`   | External RAM     | External  |
+/// | `$C000..=$CFFF` |  4 KiB | `wram`   | Work RAM (0)     | External  |
+/// | `$D000..=$DFFF` |  4 KiB | `wram`   | Work RAM (1-7)   | External  |
+/// | `$E000..=$FDFF` | 7680 B | `wram`   | Echo RAM         | External  |
+/// | `$FE00..=$FEA0` |  160 B | `oam`    | Object memory    | Internal  |
+/// | `$FEA0..=$FEFF` |   96 B | ---      | ---              | ---       |
+/// | `$FF00..=$FFFF` |   96 B | `soc`    | I/O registers    | Internal  |
+///
+/// ## I/O Registers
+///
+/// |     Address     |  Size  |  Module  | Description      |    Bus    |
+/// |:---------------:|-------:|----------|------------------|-----------|
+/// | `$FF00..=$FF00` |    1 B | `joypad` | Controller       | Internal  |
+/// | `$FF01..=$FF02` |    2 B | `serial` | Serial I/O       | Internal  |
+/// | `$FF03..=$FF03` |    1 B | ---      | ---              | ---       |
+/// | `$FF04..=$FF07` |    4 B | `timer`  | Timer I/O        | Internal  |
+/// | `$FF08..=$FF0E` |    7 B | ---      | ---              | ---       |
+/// | `$FF0F..=$FF0F` |    1 B | `pic`    | Interrupt flag   | Internal  |
+/// | `$FF10..=$FF26` |   23 B | `apu`    | Audio            | Internal  |
+/// | `$FF27..=$FF2F` |    9 B | ---      | ---              | ---       |
+/// | `$FF30..=$FF3F` |   16 B | `apu`    | Wave RAM         | Internal  |
+/// | `$FF40..=$FF4B` |   12 B | `ppu`    | LCD              | Internal  |
+/// | `$FF4C..=$FF4C` |    1 B | `soc`    | Compatibility    | Internal  |
+/// | `$FF4D..=$FF4D` |    1 B | `cpu`    | Speed switch     | Internal  |
+/// | `$FF4E..=$FF4E` |    1 B | ---      | ---              | ---       |
+/// | `$FF4F..=$FF4F` |    1 B | `vram`   | VRAM bank        | Internal  |
+/// | `$FF50..=$FF50` |    1 B | `boot`   | Boot disable     | Internal  |
+/// | `$FF51..=$FF55` |    5 B | `hdma`   | VRAM DMA         | Internal  |
+/// | `$FF56..=$FF67` |   18 B | ---      | ---              | ---       |
+/// | `$FF68..=$FF6B` |    4 B | `ppu`    | Color palettes   | Internal  |
+/// | `$FF6C..=$FF6F` |    4 B | ---      | ---              | ---       |
+/// | `$FF70..=$FF70` |    1 B | `wram`   | WRAM bank        | Internal  |
+/// | `$FF71..=$FF7F` |   15 B | ---      | ---              | ---       |
+/// | `$FF80..=$FFFE` |  127 B | `cpu`    | High RAM         | Internal  |
+/// | `$FFFF..=$FFFF` |    1 B | `pic`    | Interrupt enable | Internal  |
+///
+/// [map]: https://gbdev.io/pandocs/Memory_Map.html
+#[derive(Debug, Default)]
+pub struct Mmap {
+    /// Internal bus.
+    pub ibus: Shared<Bus>,
+    /// External bus.
+    pub ebus: Shared<Bus>,
+    /// Video bus.
+    pub vbus: Shared<Bus>,
+}
+
+impl Mmap {
+    /// Constructs a new `Network`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a bus for the CPU.
+    ///
+    /// Accesses to the external and video buses are arbitrated with the
+    /// [DMA](crate::parts::dma), which also blocks access to OAM while active.
+    /// Graphics memory is additionally blocked while in use by the
+    /// [PPU](crate::parts::ppu).
+    pub(super) fn cpu(&self, dma: &Shared<Dma>, ppu: &Shared<Access>) -> Bus {
+        let ebus = Arbiter::new(dma.clone(), self.ebus.clone());
+        let vbus = Arbiter::new(dma.clone(), self.vbus.clone());
+        let lock = Lock::new(dma.clone());
+        let vram = Guard::new(ppu.clone(), Region::Vram);
+        let oam = Guard::new(ppu.clone(), Region::Oam);
+        Bus::from([
+            (0x0000..=0x08ff, self.ibus.clone().into()), // Boot ROM
+            (0x0000..=0xffff, self.ibus.clone().into()), // Internal
+            (0x0000..=0xfdff, Shared::dev(ebus)),        // External
+            (0x0000..=0xfdff, Shared::dev(vbus)),        // Video
+            (0x8000..=0x9fff, Shared::dev(vram)),        // VRAM
+            (0xfe00..=0xfe9f, Shared::dev(oam)),         // OAM
+            (0xfe00..=0xfeff, Shared::dev(lock)),        // OAM (DMA)
+        ])
+    }
+
+    /// Constructs a bus for the DMA.
+    pub(super) fn dma(&self) -> Bus {
+        Bus::from([
+            (0x0000..=0xffff, self.ebus.clone().into()), // External
+            (0x0000..=0xffff, self.vbus.clone().into()), // Video
+        ])
+    }
+}
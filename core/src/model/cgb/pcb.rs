@@ -0,0 +1,170 @@
+//! CGB-001 motherboard.
+
+use log::warn;
+use rugby_arch::mem::Ram;
+use rugby_arch::mio::Mmio;
+use rugby_arch::{Block, Byte, Shared};
+
+use super::cpu::Status;
+use super::mem::{Banked, Select, Wram};
+use super::noc::Mmap;
+use super::ppu::{Mode, Vram};
+use super::soc::Chip;
+use crate::api::part::proc::Processor;
+
+/// CGB-CPU-01 PCB.
+#[derive(Debug)]
+pub struct Motherboard {
+    /// Crystal oscillator.
+    pub clk: u128,
+    /// Network-on-chip.
+    pub noc: Mmap,
+    /// System-on-chip.
+    pub soc: Chip,
+    /// Video RAM.
+    pub vram: [Shared<Vram>; 2],
+    /// Video RAM bank select.
+    pub vbk: Shared<Select>,
+    /// Work RAM.
+    pub wram: [Shared<Wram>; 8],
+    /// Work RAM bank select.
+    pub svbk: Shared<Select>,
+}
+
+impl Default for Motherboard {
+    fn default() -> Self {
+        let noc = Mmap::new();
+        let vram = [(); 2].map(|()| Shared::new(Ram::from([Byte::default(); 0x2000])));
+        Self {
+            clk: u128::default(),
+            soc: Chip::new(&noc, vram.clone()),
+            noc,
+            vram,
+            vbk: Shared::new(Select::new(0x01)),
+            wram: [(); 8].map(|()| Shared::new(Ram::from([Byte::default(); 0x1000]))),
+            svbk: Shared::new(Select::new(0x07)),
+        }
+        .prep()
+    }
+}
+
+impl Motherboard {
+    /// Constructs a new `Motherboard`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prepares a `Motherboard`.
+    #[must_use]
+    fn prep(self) -> Self {
+        self.attach();
+        self
+    }
+
+    /// Connect memory-mapped I/O to the network-on-chip.
+    fn attach(&self) {
+        // Borrow network
+        let Mmap { ibus, ebus, vbus } = &self.noc;
+        let ibus = &mut *ibus.borrow_mut();
+        let ebus = &mut *ebus.borrow_mut();
+        let vbus = &mut *vbus.borrow_mut();
+        // Construct banks
+        let vram = Shared::new(Banked::new(self.vbk.clone(), self.vram.clone()));
+        let wram = Shared::new(Banked::new(
+            self.svbk.clone(),
+            // selecting bank 0 instead selects bank 1
+            [1, 1, 2, 3, 4, 5, 6, 7].map(|bank| self.wram[bank].clone()),
+        ));
+        // Attach modules
+        vbus.map(0x8000..=0x9fff, vram.into()); // VRAM
+        ebus.map(0xc000..=0xcfff, self.wram[0].clone().into()); // WRAM (0)
+        ebus.map(0xd000..=0xdfff, wram.clone().into()); // WRAM (1-7)
+        ebus.map(0xe000..=0xefff, self.wram[0].clone().into()); // ECHO (0)
+        ebus.map(0xf000..=0xffff, wram.into()); // ECHO (1-7)
+        ibus.map(0xff4f..=0xff4f, self.vbk.clone().into()); // VBK
+        ibus.map(0xff70..=0xff70, self.svbk.clone().into()); // SVBK
+        self.soc.attach(ibus);
+    }
+}
+
+impl Block for Motherboard {
+    fn ready(&self) -> bool {
+        self.soc.ready()
+    }
+
+    fn cycle(&mut self) {
+        // Wake on pending interrupt (HALT) or joypad input (STOP)
+        match self.soc.cpu.status() {
+            Status::Halted if self.soc.pic.line.pending() => self.soc.cpu.wake(),
+            Status::Stopped if self.soc.joy.held() => self.soc.cpu.wake(),
+            _ => (),
+        }
+
+        // Determine CPU clock divider
+        let div = if self.soc.cpu.spd.borrow().double() {
+            2
+        } else {
+            4
+        };
+
+        // Joypad: 4 MiHz
+        if self.soc.joy.ready() {
+            self.soc.joy.cycle();
+        }
+        // APU: 4 MiHz
+        if self.soc.apu.ready() {
+            self.soc.apu.cycle();
+        }
+        // HDMA: 1 MiHz
+        if self.soc.hdma.ready() && self.clk.is_multiple_of(4) {
+            self.soc.hdma.cycle();
+        }
+        // CPU: 1 MiHz (2 MiHz in double speed)
+        //
+        // NOTE: The CPU is stalled during HDMA transfers.
+        if self.soc.cpu.ready() && !self.soc.hdma.ready() && self.clk.is_multiple_of(div) {
+            self.soc.cpu.cycle();
+        }
+        // DMA: 1 MiHz (2 MiHz in double speed)
+        if self.soc.dma.ready() && self.clk.is_multiple_of(div) {
+            self.soc.dma.cycle();
+        }
+        // PPU: 4 MiHz
+        if self.soc.ppu.ready() {
+            let hblank = matches!(self.soc.ppu.mode(), Mode::HBlank(_));
+            self.soc.ppu.cycle();
+            // Signal HDMA upon entering horizontal blank
+            if !hblank && matches!(self.soc.ppu.mode(), Mode::HBlank(_)) {
+                self.soc.hdma.hblank();
+            }
+        } else {
+            self.soc.ppu.idle();
+        }
+        // Serial: 8192 Hz (16384 Hz in double speed)
+        if self.soc.ser.ready() && self.clk.is_multiple_of(128 * div) {
+            self.soc.ser.cycle();
+        }
+        // Timer: 4 MiHz (8 MiHz in double speed)
+        //
+        // NOTE: The timer is halted while the CPU is stopped.
+        for _ in 0..4 / div {
+            if self.soc.tma.ready() && self.soc.cpu.status() != Status::Stopped {
+                self.soc.tma.cycle();
+            }
+        }
+
+        // Update executed cycle count
+        let (clock, carry) = self.clk.overflowing_add(1);
+        if carry {
+            warn!("internal cycle counter overflowed; resetting");
+        }
+        self.clk = clock;
+    }
+
+    fn reset(&mut self) {
+        self.soc.reset();
+        self.vbk.reset();
+        self.svbk.reset();
+    }
+}
@@ -0,0 +1,186 @@
+//! System-on-chip.
+
+use log::debug;
+use rugby_arch::mem::{Memory, Result};
+use rugby_arch::mio::{Bus, Mmio};
+use rugby_arch::reg::Register;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::boot;
+use super::cpu::Cpu;
+use super::hdma::Hdma;
+use super::joypad::Joypad;
+use super::mem::Bank;
+use super::noc::Mmap;
+use super::pic::Pic;
+use super::ppu::cgb::Cgb;
+use super::ppu::{Access, Ppu, Vram};
+use super::serial::Serial;
+use super::timer::Timer;
+use crate::parts::apu::Apu;
+use crate::parts::dma::Dma;
+
+/// Sharp LR35902 (CGB-CPU).
+#[derive(Debug)]
+pub struct Chip {
+    /// Audio processing unit.
+    pub apu: Apu,
+    /// Central processing unit.
+    pub cpu: Cpu,
+    /// Direct memory access controller.
+    pub dma: Dma,
+    /// VRAM direct memory access controller.
+    pub hdma: Hdma,
+    /// Joypad controller.
+    pub joy: Joypad,
+    /// Compatibility mode.
+    pub key: Shared<Key0>,
+    /// Embedded memory.
+    pub mem: Bank,
+    /// Programmable interrupt controller.
+    pub pic: Pic,
+    /// Picture processing unit
+    pub ppu: Ppu,
+    /// Serial communications port.
+    pub ser: Serial,
+    /// Hardware timer.
+    pub tma: Timer,
+}
+
+impl Chip {
+    /// Constructs a new `SoC`.
+    #[must_use]
+    pub fn new(noc: &Mmap, vram: [Shared<Vram>; 2]) -> Self {
+        let [vram0, vram1] = vram;
+        let mem = Bank::new();
+        let dma = Dma::new(noc.dma(), mem.oam.clone());
+        let pic = Pic::new();
+        let tma = Timer::new(pic.line.clone());
+        let cgb = Cgb::new(vram1);
+        let acc = Shared::new(Access::default());
+        let cpu = Cpu::new(noc.cpu(&dma.reg, &acc), pic.line.clone());
+        Self {
+            apu: Apu::new(tma.reg.div.clone()),
+            hdma: Hdma::new(noc.dma()),
+            joy: Joypad::new(pic.line.clone()),
+            key: Shared::new(Key0::new(cgb.compat.clone())),
+            ppu: Ppu::with(
+                vram0,
+                mem.oam.clone(),
+                dma.reg.clone(),
+                pic.line.clone(),
+                cpu.idu.clone(),
+                acc,
+                cgb,
+            ),
+            ser: Serial::new(pic.line.clone()),
+            cpu,
+            dma,
+            mem,
+            pic,
+            tma,
+        }
+    }
+}
+
+impl Block for Chip {
+    fn ready(&self) -> bool {
+        self.cpu.ready()
+    }
+
+    fn reset(&mut self) {
+        self.apu.reset();
+        self.cpu.reset();
+        self.dma.reset();
+        self.hdma.reset();
+        self.joy.reset();
+        self.key.reset();
+        self.ppu.reset();
+        self.ser.reset();
+        self.tma.reset();
+    }
+}
+
+impl Mmio for Chip {
+    fn attach(&self, bus: &mut Bus) {
+        self.apu.attach(bus);
+        self.hdma.attach(bus);
+        self.joy.attach(bus);
+        self.mem.attach(bus);
+        self.pic.attach(bus);
+        self.ppu.attach(bus);
+        self.ser.attach(bus);
+        self.tma.attach(bus);
+        bus.map(0xff4c..=0xff4c, self.key.clone().into());
+        bus.map(0xff4d..=0xff4d, self.cpu.spd.clone().into());
+    }
+}
+
+/// Compatibility mode register (KEY0).
+///
+/// Selects DMG compatibility mode when bit 2 is set. Only writable while the
+/// boot ROM is mapped, after which the mode is locked.
+#[derive(Debug)]
+pub struct Key0 {
+    /// Boot ROM disable.
+    boot: Option<Shared<boot::Control>>,
+    /// Compatibility mode.
+    compat: Shared<bool>,
+}
+
+impl Key0 {
+    /// Constructs a new `Key0`.
+    #[must_use]
+    pub fn new(compat: Shared<bool>) -> Self {
+        Self { boot: None, compat }
+    }
+
+    /// Connects the boot ROM's disable register.
+    pub fn connect(&mut self, boot: Shared<boot::Control>) {
+        self.boot = Some(boot);
+    }
+
+    /// Sets compatibility mode.
+    ///
+    /// Used when simulating the boot ROM, as the register is otherwise locked.
+    pub fn set(&mut self, compat: bool) {
+        *self.compat.borrow_mut() = compat;
+        debug!("compatibility mode: {compat}");
+    }
+}
+
+impl Block for Key0 {
+    fn reset(&mut self) {
+        self.compat.take();
+    }
+}
+
+impl Memory for Key0 {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Ok(self.load())
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Key0 {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        0xfb | Byte::from(*self.compat.borrow()) << 2
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        // Only writable while the boot ROM is mapped
+        let enabled = self
+            .boot
+            .as_ref()
+            .is_some_and(|boot| boot.load() & 0x01 == 0);
+        if enabled {
+            self.set(value & 0x04 != 0);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use rugby_arch::mem::Memory;
+use rugby_arch::Byte;
+
+use super::*;
+
+/// Sample ROM header.
+const GAME: &[Byte; 0x8000] = include_bytes!("../../../../roms/games/2048/2048.gb");
+
+fn setup() -> GameBoy {
+    // Instantiate a `Cartridge`
+    let cart = Cartridge::new(GAME).unwrap();
+    // Create a `GameBoy` instance
+    let mut emu = GameBoy::new();
+    // Load the cartridge into the emulator
+    emu.insert(cart);
+
+    emu
+}
+
+#[test]
+fn compat_mode_works() {
+    // DMG cartridges run in compatibility mode
+    let emu = setup();
+    assert!(emu.compat());
+    assert_eq!(emu.main.soc.cpu.read(0xff4c), 0xff);
+
+    // CGB cartridges run in native mode
+    let mut rom = GAME.to_vec();
+    rom[0x143] = 0x80;
+    rom[0x14d] = cart::header::hchk(&rom);
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(&rom).unwrap());
+    assert!(!emu.compat());
+    assert_eq!(emu.main.soc.cpu.read(0xff4c), 0xfb);
+
+    // Mode is locked once booted
+    emu.main.soc.cpu.write(0xff4c, 0x04);
+    assert!(!emu.compat());
+}
+
+#[test]
+fn vram_banking_works() {
+    let mut emu = setup();
+    let cpu = &mut emu.main.soc.cpu;
+
+    // Write to each bank
+    for bank in 0..2 {
+        cpu.write(0xff4f, bank);
+        assert_eq!(cpu.read(0xff4f), 0xfe | bank);
+        (0x8000..=0x9fff).for_each(|addr| cpu.write(addr, 0x10 | bank));
+    }
+    // Check each bank
+    for bank in 0..2 {
+        (0x0000..=0x1fff)
+            .map(|addr| emu.main.vram[usize::from(bank)].read(addr).unwrap())
+            .for_each(|byte| assert_eq!(byte, 0x10 | bank));
+    }
+}
+
+#[test]
+fn wram_banking_works() {
+    let mut emu = setup();
+    let cpu = &mut emu.main.soc.cpu;
+
+    // Write to each bank
+    cpu.write(0xc000, 0xaa);
+    for bank in 1..8 {
+        cpu.write(0xff70, bank);
+        assert_eq!(cpu.read(0xff70), 0xf8 | bank);
+        cpu.write(0xd000, bank);
+    }
+    // Check each bank
+    for bank in 1..8 {
+        cpu.write(0xff70, bank);
+        assert_eq!(cpu.read(0xc000), 0xaa);
+        assert_eq!(cpu.read(0xd000), bank);
+        assert_eq!(cpu.read(0xf000), bank); // echo
+    }
+    // Selecting bank 0 selects bank 1
+    cpu.write(0xff70, 0);
+    assert_eq!(cpu.read(0xd000), 1);
+}
+
+#[test]
+fn cram_works() {
+    let mut emu = setup();
+    let cpu = &mut emu.main.soc.cpu;
+
+    // Write palette with auto-increment
+    cpu.write(0xff68, 0x88);
+    for data in [0x1f, 0x00, 0xe0, 0x03] {
+        cpu.write(0xff69, data);
+    }
+    assert_eq!(cpu.read(0xff68), 0xcc);
+    // Read palette without auto-increment
+    cpu.write(0xff68, 0x0a);
+    assert_eq!(cpu.read(0xff69), 0xe0);
+    assert_eq!(cpu.read(0xff69), 0xe0);
+    // Check colors
+    let cram = emu.main.soc.ppu.cgb.as_ref().unwrap().bcp.borrow();
+    assert_eq!(cram.color(1, ppu::Color::C0).red(), 0x1f);
+    assert_eq!(cram.color(1, ppu::Color::C1).green(), 0x1f);
+}
+
+#[test]
+fn hdma_general_works() {
+    let mut emu = setup();
+
+    // Prepare source data
+    let cpu = &mut emu.main.soc.cpu;
+    for (addr, data) in (0xc000..0xc020).zip(0x00..) {
+        cpu.write(addr, data);
+    }
+    // Start transfer of two blocks to $8100
+    cpu.write(0xff4f, 0x01);
+    [0xc0, 0x00, 0x81, 0x00, 0x01]
+        .into_iter()
+        .zip(0xff51..=0xff55)
+        .for_each(|(data, addr)| cpu.write(addr, data));
+    assert_eq!(cpu.read(0xff55), 0x01);
+    // Run until transfer completes
+    while emu.main.soc.hdma.ready() {
+        emu.main.cycle();
+    }
+    assert_eq!(emu.main.soc.cpu.read(0xff55), 0xff);
+    // Check copied data
+    (0x00..0x20)
+        .map(|idx| emu.main.vram[1].read(0x0100 + idx).unwrap())
+        .zip(0x00..)
+        .for_each(|(byte, idx)| assert_eq!(byte, idx));
+}
+
+#[test]
+fn speed_switch_works() {
+    let mut emu = setup();
+    let cpu = &mut emu.main.soc.cpu;
+
+    // Arm speed switch
+    assert_eq!(cpu.read(0xff4d), 0x7e);
+    cpu.write(0xff4d, 0x01);
+    assert_eq!(cpu.read(0xff4d), 0x7f);
+    // Execute STOP
+    cpu.exec(0x10);
+    assert_eq!(cpu.read(0xff4d), 0xfe);
+    assert!(cpu.spd.borrow().double());
+    // Pause while the clock stabilizes
+    let pc = cpu.reg.pc;
+    (0..2050).for_each(|_| cpu.cycle());
+    assert_eq!(cpu.reg.pc, pc);
+    // Resume execution
+    cpu.cycle();
+    assert_ne!(cpu.reg.pc, pc);
+}
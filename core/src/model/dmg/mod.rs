@@ -2,18 +2,28 @@
 //!
 //! [Game Boy]: https://en.wikipedia.org/wiki/Game_Boy
 
-use log::warn;
+use std::io::{self, Read, Write};
+
+use log::{info, warn};
 use rugby_arch::mio::Mmio;
 use rugby_arch::reg::Port;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Word};
 
 use self::apu::Apu;
+use self::cart::mbc::camera::Sensor;
+use self::cart::mbc::huc3::Piezo;
+use self::cart::mbc::mbc5::Motor;
+use self::cart::mbc::mbc7::Accelerometer;
 use self::cpu::Cpu;
 use self::joypad::Joypad;
+use self::movie::{Input, Movie, Session, Start};
 use self::pcb::Motherboard;
 use self::ppu::Ppu;
 use self::serial::Serial;
-use crate::api::core::{self, Core};
+use super::Peripherals;
+use crate::api::core::Core;
+use crate::api::part::joypad::{Event, Joypad as _, State};
 use crate::api::part::proc::Processor;
 
 mod noc;
@@ -22,7 +32,9 @@ mod soc;
 #[cfg(feature = "debug")]
 pub mod dbg;
 pub mod mem;
+pub mod movie;
 pub mod pcb;
+pub mod state;
 
 pub use self::boot::Boot;
 pub use self::cart::Cartridge;
@@ -49,6 +61,10 @@ pub struct GameBoy {
     cart: Option<Cartridge>,
     /// DMG-01 Motherboard.
     main: Motherboard,
+    /// Cartridge peripherals.
+    dev: Peripherals,
+    /// Movie session.
+    mov: Option<Session>,
 }
 
 impl GameBoy {
@@ -81,8 +97,14 @@ impl GameBoy {
     /// This prepares the `GameBoy` to run the contents of a game cartridge.
     /// When no [boot ROM](Boot) is installed, this must be called before
     /// cartridge execution.
+    ///
+    /// Interrupts remain disabled once control is transferred, as the boot ROM
+    /// never enables them.
     #[rustfmt::skip]
     pub fn boot(&mut self) {
+        // Read cartridge header
+        self.scan();
+
         let cpu = &mut self.main.soc.cpu;
 
         // Initialize registers
@@ -95,12 +117,38 @@ impl GameBoy {
         cpu.store(Select::SP, 0xfffe_u16);
 
         // Perform bootup sequence
+        cpu.write(0xff26, 0x80); // enable audio
+        cpu.write(0xff11, 0x80); // set CH1 duty cycle
+        cpu.write(0xff12, 0xf3); // set CH1 envelope
+        cpu.write(0xff25, 0xf3); // set panning
+        cpu.write(0xff24, 0x77); // set master volume
+        cpu.write(0xff13, 0xc1); // set CH1 period
+        cpu.write(0xff14, 0x87); // trigger CH1
         cpu.write(0xff40, 0x91); // enable display
         cpu.write(0xff50, 0x01); // disable boot ROM
-        cpu.exec(0xfb);          // enable interrupts
         cpu.goto(0x0100);        // transfer program control
     }
 
+    /// Simulate the boot ROM's cartridge header reads.
+    ///
+    /// The logo is read twice (once to render it, then again to verify it),
+    /// followed by the remainder of the header to verify its checksum. Some
+    /// unlicensed cartridges rely on these reads to unlock their mapper.
+    ///
+    /// As with the boot ROM, the logo is also left rendered in VRAM.
+    fn scan(&self) {
+        if self.cart.is_none() {
+            return;
+        }
+        let cpu = &self.main.soc.cpu;
+        let logo = 0x0104..=0x0133;
+        let data: Vec<_> = logo.clone().map(|addr| cpu.read(addr)).collect();
+        for addr in logo.chain(0x0134..=0x014d) {
+            let _ = cpu.read(addr);
+        }
+        self.main.logo(&data);
+    }
+
     /// Gets the inserted game cartridge, if any.
     #[must_use]
     pub fn cart(&self) -> Option<&Cartridge> {
@@ -115,11 +163,16 @@ impl GameBoy {
         // Disconnect previous cartridge
         if let Some(cart) = self.eject() {
             warn!("ejected previous cartridge: {}", cart.header());
-        };
+        }
+        // Connect cartridge peripherals
+        self.dev.connect(&cart);
         // Insert supplied cartridge
-        let ebus = &mut *self.main.noc.ebus.borrow_mut();
-        cart.attach(ebus);
+        cart.attach(&mut self.main.noc.ebus.borrow_mut());
         self.cart = Some(cart);
+        // Simulate boot ROM header reads
+        if self.boot.is_none() {
+            self.scan();
+        }
     }
 
     /// Ejects the inserted game cartridge, if any.
@@ -132,84 +185,213 @@ impl GameBoy {
         // Remove inserted cartridge
         self.cart.take()
     }
-}
-
-impl Block for GameBoy {
-    fn ready(&self) -> bool {
-        self.main.ready()
-    }
 
-    fn cycle(&mut self) {
-        self.main.cycle();
+    /// Saves the emulator's state.
+    ///
+    /// The state is bound to the inserted cartridge, and can only be
+    /// [loaded](Self::load_state) while it remains inserted.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the state could not be written.
+    pub fn save_state(&self, mut buf: impl Write) -> state::Result<()> {
+        state::Header::new(self.cart()).write(&mut buf)?;
+        self.save(&mut buf)?;
+        buf.flush()?;
+        Ok(())
     }
 
-    #[rustfmt::skip]
-    fn reset(&mut self) {
-        self.main.reset();
-        self.boot.as_mut().map(Block::reset).unwrap_or_else(|| self.boot());
-        self.cart.as_mut().map(Block::reset);
+    /// Loads a previously saved state.
+    ///
+    /// On error, the emulator is left unchanged. Otherwise, any movie being
+    /// recorded or played back is stopped, although it can still be retrieved
+    /// using [`stop`](Self::stop).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the emulator's previous state could not be recovered after
+    /// an error.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the state could not be read, is invalid, or was saved with a
+    /// different cartridge.
+    pub fn load_state(&mut self, mut buf: impl Read) -> state::Result<()> {
+        state::Header::read(&mut buf)?.check(self.cart())?;
+        // Back up the current state
+        let mut prev = Vec::new();
+        self.save(&mut prev)?;
+        // Restore the saved state
+        if let Err(err) = self.restore(&mut buf) {
+            self.restore(&mut prev.as_slice())
+                .expect("backup state should be valid");
+            return Err(err.into());
+        }
+        // Stop any movie, as it no longer follows the emulator's clock
+        if let Some(mov) = self.mov.take() {
+            warn!("stopped movie after loading a state");
+            self.mov = Some(mov.halt());
+        }
+        Ok(())
     }
 }
 
-impl Core for GameBoy {}
-
-impl core::has::Audio for GameBoy {
-    type Audio = Apu;
-
-    fn audio(&self) -> &Self::Audio {
-        &self.main.soc.apu
-    }
-
-    fn audio_mut(&mut self) -> &mut Self::Audio {
-        &mut self.main.soc.apu
+/// Input movies.
+impl GameBoy {
+    /// Starts recording an input movie.
+    ///
+    /// Movies recorded before the emulator has been cycled start at power-on;
+    /// otherwise, they start from an embedded save state. Any previous movie
+    /// is [stopped](Self::stop).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the starting state could not be saved.
+    pub fn record(&mut self) -> movie::Result<()> {
+        // Capture starting state
+        let start = if self.main.clk == 0 {
+            let mut cart = Vec::new();
+            if let Some(this) = &self.cart {
+                this.save(&mut cart)?;
+            }
+            Start::Power(cart)
+        } else {
+            let mut state = Vec::new();
+            self.save_state(&mut state)?;
+            Start::State(state)
+        };
+        let mut movie = Movie::new(self.cart(), start);
+        // Buttons held at the start are pressed immediately
+        let held = self.main.soc.joy.pressed().into_iter().map(|input| Input {
+            cycle: 0,
+            event: Event {
+                input,
+                state: State::Dn,
+            },
+        });
+        movie.input.extend(held);
+        // Begin recording
+        self.mov = Some(Session::Record {
+            movie,
+            base: self.main.clk,
+        });
+        Ok(())
     }
-}
 
-impl core::has::Processor for GameBoy {
-    type Proc = Cpu;
-
-    fn proc(&self) -> &Self::Proc {
-        &self.main.soc.cpu
+    /// Starts playing back an input movie.
+    ///
+    /// While playing, input received from the frontend is ignored. Playback
+    /// stops once all recorded input has been applied. Any previous movie is
+    /// [stopped](Self::stop).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the movie was recorded with a different cartridge, or if its
+    /// starting state could not be restored.
+    pub fn play(&mut self, movie: Movie) -> movie::Result<()> {
+        movie.check(self.cart())?;
+        // Restore starting state
+        match &movie.start {
+            Start::Power(_) if self.main.clk != 0 => return Err(movie::Error::Started),
+            Start::Power(cart) => {
+                if let Some(this) = &mut self.cart {
+                    this.restore(&mut cart.as_slice())
+                        .map_err(|_| movie::Error::Invalid)?;
+                }
+            }
+            Start::State(state) => self.load_state(state.as_slice())?,
+        }
+        // Release any held buttons
+        let joy = &mut self.main.soc.joy;
+        joy.discard();
+        joy.recv(joy.pressed().into_iter().map(|input| Event {
+            input,
+            state: State::Up,
+        }));
+        joy.cycle();
+        // Begin playback
+        self.mov = Some(Session::Play {
+            movie,
+            base: self.main.clk,
+            next: 0,
+        });
+        Ok(())
     }
 
-    fn proc_mut(&mut self) -> &mut Self::Proc {
-        &mut self.main.soc.cpu
+    /// Stops recording or playing back an input movie, returning it.
+    pub fn stop(&mut self) -> Option<Movie> {
+        self.mov.take().map(Session::into_movie)
     }
 }
 
-impl core::has::Joypad for GameBoy {
-    type Joypad = Joypad;
-
-    fn joypad(&self) -> &Self::Joypad {
-        &self.main.soc.joy
+/// The boot ROM and cartridge's contents are not saved.
+impl Snapshot for GameBoy {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        match &self.boot {
+            Some(boot) => boot.reg.save(buf)?,
+            None => true.save(buf)?,
+        }
+        self.main.save(buf)?;
+        if let Some(cart) = &self.cart {
+            cart.save(buf)?;
+        }
+        Ok(())
     }
 
-    fn joypad_mut(&mut self) -> &mut Self::Joypad {
-        &mut self.main.soc.joy
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        match &mut self.boot {
+            Some(boot) => boot.reg.restore(buf)?,
+            None => drop(bool::decode(buf)?),
+        }
+        self.main.restore(buf)?;
+        if let Some(cart) = &mut self.cart {
+            cart.restore(buf)?;
+        }
+        Ok(())
     }
 }
 
-impl core::has::Serial for GameBoy {
-    type Serial = Serial;
+impl Block for GameBoy {
+    fn ready(&self) -> bool {
+        self.main.ready()
+    }
 
-    fn serial(&self) -> &Self::Serial {
-        &self.main.soc.ser
+    fn cycle(&mut self) {
+        // Synchronize input movie
+        if let Some(mov) = self.mov.as_mut() {
+            if mov.sync(self.main.clk, &mut self.main.soc.joy) {
+                info!("finished movie playback");
+                self.mov = None;
+            }
+        }
+        self.main.cycle();
+        // Cartridge: 4 MiHz
+        if let Some(cart) = self.cart.as_mut().filter(|cart| cart.ready()) {
+            cart.cycle();
+        }
     }
 
-    fn serial_mut(&mut self) -> &mut Self::Serial {
-        &mut self.main.soc.ser
+    #[rustfmt::skip]
+    fn reset(&mut self) {
+        self.main.reset();
+        self.cart.as_mut().map(Block::reset);
+        self.boot.as_mut().map(Block::reset).unwrap_or_else(|| self.boot());
     }
 }
 
-impl core::has::Video for GameBoy {
-    type Video = Ppu;
-
-    fn video(&self) -> &Self::Video {
-        &self.main.soc.ppu
-    }
+impl Core for GameBoy {}
 
-    fn video_mut(&mut self) -> &mut Self::Video {
-        &mut self.main.soc.ppu
+has! {
+    GameBoy {
+        Audio(Audio = Apu) { audio, audio_mut } => main.soc.apu;
+        Motion(Motion = Accelerometer) { motion, motion_mut } => dev.acc;
+        Processor(Proc = Cpu) { proc, proc_mut } => main.soc.cpu;
+        Camera(Camera = Sensor) { camera, camera_mut } => dev.cam;
+        Joypad(Joypad = Joypad) { joypad, joypad_mut } => main.soc.joy;
+        Rumble(Rumble = Motor) { rumble, rumble_mut } => dev.vib;
+        Serial(Serial = Serial) { serial, serial_mut } => main.soc.ser;
+        Speaker(Speaker = Piezo) { speaker, speaker_mut } => dev.spk;
+        Video(Video = Ppu) { video, video_mut } => main.soc.ppu;
     }
 }
 
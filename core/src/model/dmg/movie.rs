@@ -0,0 +1,327 @@
+//! Input movies.
+//!
+//! A movie records every joypad input event along with the exact cycle it was
+//! applied, such that it can later be played back to reproduce a run of the
+//! emulator bit-for-bit.
+//!
+//! Movies begin either at power-on, or from an embedded [save
+//! state](super::state). Power-on movies embed the cartridge's state, such
+//! that its RAM and real-time clock match those loaded when recording. Only
+//! joypad input is recorded; other inputs, such as the image and motion
+//! sensors, are not.
+//!
+//! # Format
+//!
+//! Movies are stored as compact little-endian binary data:
+//!
+//! | Offset | Size | Description               |
+//! |:------:|------|---------------------------|
+//! |  `+0`  |  4 B | Magic number (`RGBM`)     |
+//! |  `+4`  |  2 B | Format version            |
+//! |  `+6`  |  1 B | Cartridge inserted        |
+//! |  `+7`  |  2 B | Cartridge global checksum |
+//! |  `+9`  |  1 B | Start kind                |
+//! | `+10`  |  8 B | Start state length        |
+//! | `+18`  |  N B | Start state               |
+//! |   ..   |  8 B | Input event count         |
+//! |   ..   | 10 B | Input events              |
+//!
+//! A start kind of `0` indicates power-on, in which case the start state is
+//! the cartridge's state; `1` indicates an embedded save state. Each input event is stored as
+//! its 8-byte cycle (relative to the start of the movie), followed by the
+//! button and its state (`0` for pressed, `1` for released).
+
+use std::io::{self, ErrorKind, Read, Write};
+
+use rugby_arch::snap::Snapshot;
+use rugby_arch::Word;
+use thiserror::Error;
+
+use super::joypad::Joypad;
+use super::{state, Button, Cartridge};
+use crate::api::part::joypad::{Event, Joypad as _, State};
+
+/// Magic number.
+pub const MAGIC: [u8; 4] = *b"RGBM";
+
+/// Format version.
+///
+/// Incremented whenever the format changes incompatibly.
+pub const VERSION: u16 = 2;
+
+/// Input movie.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Movie {
+    /// Cartridge global checksum, if inserted.
+    pub gchk: Option<Word>,
+    /// Starting state.
+    pub start: Start,
+    /// Recorded input.
+    pub input: Vec<Input>,
+}
+
+impl Movie {
+    /// Constructs a new, empty `Movie` for the inserted cartridge.
+    #[must_use]
+    pub fn new(cart: Option<&Cartridge>, start: Start) -> Self {
+        Self {
+            gchk: cart.map(|cart| cart.header().gchk),
+            start,
+            input: Vec::new(),
+        }
+    }
+
+    /// Reads a movie.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the movie could not be read, or is invalid.
+    pub fn read(mut buf: impl Read) -> Result<Self> {
+        let buf = &mut buf;
+        // Header
+        if <[u8; 4]>::decode(buf)? != MAGIC {
+            return Err(Error::Magic);
+        }
+        let version = u16::decode(buf)?;
+        if version != VERSION {
+            return Err(Error::Version(version));
+        }
+        let some = bool::decode(buf)?;
+        let gchk = Word::decode(buf)?;
+        // Start
+        let start = match u8::decode(buf)? {
+            0 => Start::Power(Vec::decode(buf)?),
+            1 => Start::State(Vec::decode(buf)?),
+            _ => return Err(Error::Invalid),
+        };
+        // Input
+        let len = usize::decode(buf)?;
+        let input = (0..len)
+            .map(|_| Input::read(buf))
+            .collect::<io::Result<_>>()?;
+        Ok(Self {
+            gchk: some.then_some(gchk),
+            start,
+            input,
+        })
+    }
+
+    /// Writes the movie.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the movie could not be written.
+    pub fn write(&self, mut buf: impl Write) -> Result<()> {
+        let buf = &mut buf;
+        // Header
+        MAGIC.save(buf)?;
+        VERSION.save(buf)?;
+        self.gchk.is_some().save(buf)?;
+        self.gchk.unwrap_or_default().save(buf)?;
+        // Start
+        match &self.start {
+            Start::Power(cart) => {
+                0u8.save(buf)?;
+                cart.save(buf)?;
+            }
+            Start::State(state) => {
+                1u8.save(buf)?;
+                state.save(buf)?;
+            }
+        }
+        // Input
+        self.input.len().save(buf)?;
+        for input in &self.input {
+            input.write(buf)?;
+        }
+        buf.flush()?;
+        Ok(())
+    }
+
+    /// Checks the movie was recorded with the inserted cartridge.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the cartridge does not match.
+    pub fn check(&self, cart: Option<&Cartridge>) -> Result<()> {
+        let found = cart.map(|cart| cart.header().gchk);
+        if found == self.gchk {
+            Ok(())
+        } else {
+            Err(Error::Cartridge {
+                found,
+                expected: self.gchk,
+            })
+        }
+    }
+}
+
+/// Movie starting state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Start {
+    /// Power-on.
+    ///
+    /// The emulator must be freshly constructed, with the same boot ROM. The
+    /// cartridge's state, including its RAM and real-time clock, is embedded
+    /// and restored before playback.
+    Power(Vec<u8>),
+    /// Embedded save state.
+    State(Vec<u8>),
+}
+
+/// Recorded input event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Input {
+    /// Cycle applied, relative to the start of the movie.
+    pub cycle: u64,
+    /// Input event.
+    pub event: Event<Button>,
+}
+
+impl Input {
+    /// Reads an input event.
+    fn read(buf: &mut impl Read) -> io::Result<Self> {
+        let cycle = u64::decode(buf)?;
+        let code = u8::decode(buf)?;
+        let input = Button::ALL
+            .into_iter()
+            .find(|&btn| btn as u8 == code)
+            .ok_or(ErrorKind::InvalidData)?;
+        let state = if bool::decode(buf)? {
+            State::Up
+        } else {
+            State::Dn
+        };
+        Ok(Self {
+            cycle,
+            event: Event { input, state },
+        })
+    }
+
+    /// Writes the input event.
+    fn write(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.cycle.save(buf)?;
+        (self.event.input as u8).save(buf)?;
+        (self.event.state == State::Up).save(buf)
+    }
+}
+
+/// Movie session.
+#[derive(Debug)]
+pub(super) enum Session {
+    /// Recording input.
+    Record {
+        /// Recorded movie.
+        movie: Movie,
+        /// Starting cycle.
+        base: u128,
+    },
+    /// Playing back input.
+    Play {
+        /// Played movie.
+        movie: Movie,
+        /// Starting cycle.
+        base: u128,
+        /// Next input index.
+        next: usize,
+    },
+    /// Stopped early.
+    ///
+    /// Once a state is loaded, the emulator no longer follows the movie. Its
+    /// input is left untouched until the movie is retrieved.
+    Stopped {
+        /// Stopped movie.
+        movie: Movie,
+    },
+}
+
+impl Session {
+    /// Synchronizes input with the joypad.
+    ///
+    /// Returns whether the session has finished.
+    pub(super) fn sync(&mut self, clk: u128, joy: &mut Joypad) -> bool {
+        match self {
+            Session::Record { movie, base } => {
+                // Record pending input
+                let cycle = u64::try_from(clk - *base).unwrap_or(u64::MAX);
+                let input = joy.pending().iter().map(|&event| Input { cycle, event });
+                movie.input.extend(input);
+                false
+            }
+            Session::Play { movie, base, next } => {
+                // Ignore frontend input
+                joy.discard();
+                // Replay due input
+                let cycle = u64::try_from(clk - *base).unwrap_or(u64::MAX);
+                let due: Vec<_> = movie.input[*next..]
+                    .iter()
+                    .take_while(|input| input.cycle <= cycle)
+                    .map(|input| input.event)
+                    .collect();
+                *next += due.len();
+                joy.recv(due);
+                *next >= movie.input.len()
+            }
+            Session::Stopped { .. } => false,
+        }
+    }
+
+    /// Stops the session, retaining its movie.
+    pub(super) fn halt(self) -> Self {
+        Session::Stopped {
+            movie: self.into_movie(),
+        }
+    }
+
+    /// Extracts the session's movie.
+    pub(super) fn into_movie(self) -> Movie {
+        match self {
+            Session::Record { movie, .. }
+            | Session::Play { movie, .. }
+            | Session::Stopped { movie } => movie,
+        }
+    }
+}
+
+/// A convenient type alias for [`Result`](std::result::Result).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error caused by recording or playing a movie.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Invalid magic number.
+    #[error("not a movie")]
+    Magic,
+    /// Unsupported format version.
+    #[error("unsupported movie version: {0}")]
+    Version(u16),
+    /// Mismatched cartridge.
+    #[error("movie is for another cartridge (found {found:04x?}, expected {expected:04x?})")]
+    Cartridge {
+        /// Inserted cartridge's checksum.
+        found: Option<Word>,
+        /// Recorded cartridge's checksum.
+        expected: Option<Word>,
+    },
+    /// Emulator not at power-on.
+    #[error("movie starts at power-on, but emulator has already started")]
+    Started,
+    /// Invalid movie data.
+    #[error("invalid movie data")]
+    Invalid,
+    /// Invalid starting state.
+    #[error("invalid starting state")]
+    State(#[from] state::Error),
+    /// I/O error.
+    #[error(transparent)]
+    Io(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            ErrorKind::InvalidData | ErrorKind::UnexpectedEof => Self::Invalid,
+            _ => Self::Io(err),
+        }
+    }
+}
@@ -3,14 +3,17 @@
 use rugby_arch::mio::Bus;
 use rugby_arch::Shared;
 
+use crate::parts::dma::{Arbiter, Control as Dma, Lock};
+use crate::parts::ppu::{Access, Guard, Region};
+
 /// Memory-mapped I/O.
 ///
 /// The Game Boy's memory architecture is divided across three distinct buses:
 /// - [Internal](Self::ibus): Embedded within the Sharp LR35902. Usable only by
-///                           the [CPU](super::cpu).
+///   the [CPU](super::cpu).
 /// - [External](Self::ebus): Accessible to on-board components.
 /// - [Video](Self::vbus):    Connected only to VRAM, controlled by the
-///                           [PPU](super::ppu).
+///   [PPU](super::ppu).
 ///
 /// # Memory Map
 ///
@@ -67,12 +70,25 @@ impl Mmap {
     }
 
     /// Constructs a bus for the CPU.
-    pub(super) fn cpu(&self) -> Bus {
+    ///
+    /// Accesses to the external and video buses are arbitrated with the
+    /// [DMA](crate::parts::dma), which also blocks access to OAM while active.
+    /// Graphics memory is additionally blocked while in use by the
+    /// [PPU](crate::parts::ppu).
+    pub(super) fn cpu(&self, dma: &Shared<Dma>, ppu: &Shared<Access>) -> Bus {
+        let ebus = Arbiter::new(dma.clone(), self.ebus.clone());
+        let vbus = Arbiter::new(dma.clone(), self.vbus.clone());
+        let lock = Lock::new(dma.clone());
+        let vram = Guard::new(ppu.clone(), Region::Vram);
+        let oam = Guard::new(ppu.clone(), Region::Oam);
         Bus::from([
             (0x0000..=0x00ff, self.ibus.clone().into()), // Boot ROM
             (0x0000..=0xffff, self.ibus.clone().into()), // Internal
-            (0x0000..=0xfdff, self.ebus.clone().into()), // External
-            (0x0000..=0xfdff, self.vbus.clone().into()), // Video
+            (0x0000..=0xfdff, Shared::dev(ebus)),        // External
+            (0x0000..=0xfdff, Shared::dev(vbus)),        // Video
+            (0x8000..=0x9fff, Shared::dev(vram)),        // VRAM
+            (0xfe00..=0xfe9f, Shared::dev(oam)),         // OAM
+            (0xfe00..=0xfeff, Shared::dev(lock)),        // OAM (DMA)
         ])
     }
 
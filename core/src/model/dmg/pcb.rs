@@ -1,10 +1,14 @@
 //! DMG-01 motherboard.
 
+use std::io::{self, Read, Write};
+
 use log::warn;
-use rugby_arch::mem::Ram;
+use rugby_arch::mem::{Memory, Ram};
 use rugby_arch::mio::Mmio;
-use rugby_arch::{Block, Byte, Shared};
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
 
+use super::cpu::Status;
 use super::noc::Mmap;
 use super::ppu::Vram;
 use super::soc::Chip;
@@ -55,6 +59,38 @@ impl Motherboard {
         Self::default()
     }
 
+    /// Renders the boot ROM's logo into VRAM.
+    ///
+    /// The logo is decompressed from the cartridge header into VRAM, followed
+    /// by the trademark symbol. Some software relies on these tiles still
+    /// being present once the boot ROM has finished.
+    pub(crate) fn logo(&self, data: &[Byte]) {
+        let mut vram = self.vram.borrow_mut();
+        // Writes are within VRAM, so they cannot fail
+        let mut put = |addr, data| {
+            let _ = vram.write(addr, data);
+        };
+        // Each nibble expands into a pair of rows with every pixel doubled
+        let rows = data.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]);
+        for (idx, nib) in (0..).zip(rows) {
+            let row = (0..4).fold(0, |row, bit| (row << 2) | (0b11 * ((nib >> (3 - bit)) & 1)));
+            let addr = 0x0010 + 4 * idx;
+            put(addr, row);
+            put(addr + 2, row);
+        }
+        // The trademark symbol is stored within the boot ROM
+        let mark = [0x3c, 0x42, 0xb9, 0xa5, 0xb9, 0xa5, 0x42, 0x3c];
+        for (idx, row) in (0..).zip(mark) {
+            put(0x0190 + 2 * idx, row);
+        }
+        // Arrange the tiles in the tile map
+        put(0x1910, 0x19);
+        for tile in 0x01..=0x0c {
+            put(0x1903 + Word::from(tile), tile);
+            put(0x1923 + Word::from(tile), tile + 0x0c);
+        }
+    }
+
     /// Prepares a `Motherboard`.
     #[must_use]
     fn prep(self) -> Self {
@@ -83,11 +119,21 @@ impl Block for Motherboard {
     }
 
     fn cycle(&mut self) {
-        // Wake on pending interrupt
-        if !self.soc.cpu.ready() && self.soc.pic.line.pending() {
-            self.soc.cpu.wake();
+        // Wake on pending interrupt (HALT) or joypad input (STOP)
+        match self.soc.cpu.status() {
+            Status::Halted if self.soc.pic.line.pending() => self.soc.cpu.wake(),
+            Status::Stopped if self.soc.joy.held() => self.soc.cpu.wake(),
+            _ => (),
         }
 
+        // Joypad: 4 MiHz
+        if self.soc.joy.ready() {
+            self.soc.joy.cycle();
+        }
+        // APU: 4 MiHz
+        if self.soc.apu.ready() {
+            self.soc.apu.cycle();
+        }
         // CPU: 1 MiHz
         if self.soc.cpu.ready() && self.clk % 4 == 0 {
             self.soc.cpu.cycle();
@@ -99,13 +145,17 @@ impl Block for Motherboard {
         // PPU: 4 MiHz
         if self.soc.ppu.ready() {
             self.soc.ppu.cycle();
+        } else {
+            self.soc.ppu.idle();
         }
         // Serial: 8192 Hz
         if self.soc.ser.ready() && self.clk % 512 == 0 {
             self.soc.ser.cycle();
         }
         // Timer: 4 MiHz
-        if self.soc.tma.ready() {
+        //
+        // NOTE: The timer is halted while the CPU is stopped.
+        if self.soc.tma.ready() && self.soc.cpu.status() != Status::Stopped {
             self.soc.tma.cycle();
         }
 
@@ -121,3 +171,19 @@ impl Block for Motherboard {
         self.soc.reset();
     }
 }
+
+impl Snapshot for Motherboard {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.clk.save(buf)?;
+        self.soc.save(buf)?;
+        self.vram.save(buf)?;
+        self.wram.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.clk.restore(buf)?;
+        self.soc.restore(buf)?;
+        self.vram.restore(buf)?;
+        self.wram.restore(buf)
+    }
+}
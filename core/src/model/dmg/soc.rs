@@ -1,6 +1,9 @@
 //! System-on-chip.
 
+use std::io::{self, Read, Write};
+
 use rugby_arch::mio::{Bus, Mmio};
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Shared};
 
 use super::cpu::Cpu;
@@ -8,7 +11,7 @@ use super::joypad::Joypad;
 use super::mem::Bank;
 use super::noc::Mmap;
 use super::pic::Pic;
-use super::ppu::{Ppu, Vram};
+use super::ppu::{Access, Ppu, Vram};
 use super::serial::Serial;
 use super::timer::Timer;
 use crate::parts::apu::Apu;
@@ -44,16 +47,26 @@ impl Chip {
         let mem = Bank::new();
         let dma = Dma::new(noc.dma(), mem.oam.clone());
         let pic = Pic::new();
+        let tma = Timer::new(pic.line.clone());
+        let acc = Shared::new(Access::default());
+        let cpu = Cpu::new(noc.cpu(&dma.reg, &acc), pic.line.clone());
         Self {
-            apu: Apu::new(),
-            cpu: Cpu::new(noc.cpu(), pic.line.clone()),
+            apu: Apu::new(tma.reg.div.clone()),
             joy: Joypad::new(pic.line.clone()),
-            ppu: Ppu::new(vram, mem.oam.clone(), dma.reg.clone(), pic.line.clone()),
+            ppu: Ppu::new(
+                vram,
+                mem.oam.clone(),
+                dma.reg.clone(),
+                pic.line.clone(),
+                cpu.idu.clone(),
+                acc,
+            ),
             ser: Serial::new(pic.line.clone()),
-            tma: Timer::new(pic.line.clone()),
+            cpu,
             dma,
             mem,
             pic,
+            tma,
         }
     }
 }
@@ -85,3 +98,29 @@ impl Mmio for Chip {
         self.tma.attach(bus);
     }
 }
+
+impl Snapshot for Chip {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.apu.save(buf)?;
+        self.cpu.save(buf)?;
+        self.dma.save(buf)?;
+        self.joy.save(buf)?;
+        self.mem.save(buf)?;
+        self.pic.save(buf)?;
+        self.ppu.save(buf)?;
+        self.ser.save(buf)?;
+        self.tma.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.apu.restore(buf)?;
+        self.cpu.restore(buf)?;
+        self.dma.restore(buf)?;
+        self.joy.restore(buf)?;
+        self.mem.restore(buf)?;
+        self.pic.restore(buf)?;
+        self.ppu.restore(buf)?;
+        self.ser.restore(buf)?;
+        self.tma.restore(buf)
+    }
+}
@@ -0,0 +1,146 @@
+//! Save states.
+//!
+//! A save state captures the entire emulated machine, such that it can later
+//! be resumed from exactly the same cycle.
+//!
+//! # Format
+//!
+//! States are stored as compact little-endian binary data, preceded by a
+//! header:
+//!
+//! | Offset | Size | Description               |
+//! |:------:|------|---------------------------|
+//! |  `+0`  |  4 B | Magic number (`RGBY`)     |
+//! |  `+4`  |  2 B | Format version            |
+//! |  `+6`  |  1 B | Cartridge inserted        |
+//! |  `+7`  |  2 B | Cartridge global checksum |
+//!
+//! As the cartridge's ROM is not saved, a state can only be loaded with the
+//! same cartridge inserted, as identified by its [global checksum][gchk].
+//!
+//! [gchk]: super::cart::header::Header::gchk
+
+use std::io::{self, ErrorKind, Read, Write};
+
+use rugby_arch::snap::Snapshot;
+use rugby_arch::Word;
+use thiserror::Error;
+
+use super::Cartridge;
+
+/// Magic number.
+pub const MAGIC: [u8; 4] = *b"RGBY";
+
+/// Format version.
+///
+/// Incremented whenever the format changes incompatibly.
+pub const VERSION: u16 = 10;
+
+/// Save state header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Header {
+    /// Format version.
+    pub version: u16,
+    /// Cartridge global checksum, if inserted.
+    pub gchk: Option<Word>,
+}
+
+impl Header {
+    /// Constructs a new `Header` for the inserted cartridge.
+    #[must_use]
+    pub fn new(cart: Option<&Cartridge>) -> Self {
+        Self {
+            version: VERSION,
+            gchk: cart.map(|cart| cart.header().gchk),
+        }
+    }
+
+    /// Reads a header.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the header could not be read, or is not a valid save state
+    /// header of a supported version.
+    pub fn read(buf: &mut impl Read) -> Result<Self> {
+        if <[u8; 4]>::decode(buf)? != MAGIC {
+            return Err(Error::Magic);
+        }
+        let version = u16::decode(buf)?;
+        if version != VERSION {
+            return Err(Error::Version(version));
+        }
+        let some = bool::decode(buf)?;
+        let gchk = Word::decode(buf)?;
+        Ok(Self {
+            version,
+            gchk: some.then_some(gchk),
+        })
+    }
+
+    /// Writes the header.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the header could not be written.
+    pub fn write(&self, buf: &mut impl Write) -> Result<()> {
+        MAGIC.save(buf)?;
+        self.version.save(buf)?;
+        self.gchk.is_some().save(buf)?;
+        self.gchk.unwrap_or_default().save(buf)?;
+        Ok(())
+    }
+
+    /// Checks the header was saved with the inserted cartridge.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the cartridge does not match.
+    pub fn check(&self, cart: Option<&Cartridge>) -> Result<()> {
+        let found = cart.map(|cart| cart.header().gchk);
+        if found == self.gchk {
+            Ok(())
+        } else {
+            Err(Error::Cartridge {
+                found,
+                expected: self.gchk,
+            })
+        }
+    }
+}
+
+/// A convenient type alias for [`Result`](std::result::Result).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error caused by saving or loading a state.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Invalid magic number.
+    #[error("not a save state")]
+    Magic,
+    /// Unsupported format version.
+    #[error("unsupported save state version: {0}")]
+    Version(u16),
+    /// Mismatched cartridge.
+    #[error("save state is for another cartridge (found {found:04x?}, expected {expected:04x?})")]
+    Cartridge {
+        /// Inserted cartridge's checksum.
+        found: Option<Word>,
+        /// Saved cartridge's checksum.
+        expected: Option<Word>,
+    },
+    /// Invalid state data.
+    #[error("invalid save state data")]
+    Invalid,
+    /// I/O error.
+    #[error(transparent)]
+    Io(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            ErrorKind::InvalidData | ErrorKind::UnexpectedEof => Self::Invalid,
+            _ => Self::Io(err),
+        }
+    }
+}
@@ -179,3 +179,318 @@ fn bus_unmapped_works() {
         }
     }
 }
+
+#[test]
+fn ppu_layers_works() {
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+
+    // Run for a few frames
+    for _ in 0..8 * 70_224 {
+        emu.cycle();
+    }
+
+    // Tagged pixels match the screen
+    let ppu = &emu.main.soc.ppu;
+    assert!(ppu
+        .layers()
+        .iter()
+        .zip(ppu.screen())
+        .all(|(tag, &col)| tag.col == col));
+    // Background pixels are colored by `BGP`
+    let bgp = emu.main.soc.cpu.read(0xff47);
+    for tag in ppu.layers() {
+        if tag.src == ppu::Source::Background {
+            assert_eq!(tag.col, ppu::Color::from(bgp >> (2 * tag.idx as Byte)));
+        }
+    }
+}
+
+#[test]
+fn dma_conflict_works() {
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+    let cpu = &mut emu.main.soc.cpu;
+    (0xc000..=0xc09f).for_each(|addr| cpu.write(addr, 0x5a));
+
+    // Start a transfer from WRAM
+    cpu.write(0xff46, 0xc0);
+    for _ in 0..32 {
+        emu.cycle();
+    }
+    // External bus accesses observe the transfer, even where disabled
+    let cpu = &mut emu.main.soc.cpu;
+    assert_eq!(cpu.read(0xa000), 0x5a);
+    assert_eq!(cpu.read(0x4000), 0x5a);
+    // Other buses are unaffected
+    assert_eq!(cpu.read(0xff47), *emu.main.soc.ppu.reg.bgp.borrow());
+}
+
+#[test]
+fn stop_wake_works() {
+    use crate::api::part::joypad::{Event, Joypad as _, State};
+
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+    let cpu = &mut emu.main.soc.cpu;
+    // Select all buttons, disabling interrupts
+    cpu.write(0xff00, 0x00);
+    cpu.write(0xffff, 0x00);
+
+    // Enter STOP
+    cpu.run(&[0x10]);
+    assert_eq!(cpu.status(), cpu::Status::Stopped);
+    // Remains stopped without input
+    for _ in 0..0x1000 {
+        emu.cycle();
+    }
+    assert_eq!(emu.main.soc.cpu.status(), cpu::Status::Stopped);
+    assert_eq!(emu.main.soc.cpu.read(0xff04), 0x00);
+
+    // Wakes upon a button press
+    emu.main.soc.joy.recv([Event {
+        input: Button::A,
+        state: State::Dn,
+    }]);
+    for _ in 0..0x10 {
+        emu.cycle();
+    }
+    assert_eq!(emu.main.soc.cpu.status(), cpu::Status::Enabled);
+}
+
+#[test]
+fn state_round_trip_works() {
+    let mut emu = setup();
+    for _ in 0..2 * 70_224 {
+        emu.cycle();
+    }
+
+    // Save the current state
+    let mut save = Vec::new();
+    emu.save_state(&mut save).unwrap();
+    // Record the state a few frames later
+    let mut want = Vec::new();
+    for _ in 0..4 * 70_224 {
+        emu.cycle();
+    }
+    emu.save_state(&mut want).unwrap();
+
+    // Loading into another emulator resumes identically
+    let mut emu = setup();
+    emu.load_state(save.as_slice()).unwrap();
+    for _ in 0..4 * 70_224 {
+        emu.cycle();
+    }
+    let mut have = Vec::new();
+    emu.save_state(&mut have).unwrap();
+    assert_eq!(have, want);
+
+    // Shared memory remains aliased
+    let cpu = &mut emu.main.soc.cpu;
+    cpu.write(0xff40, 0x00);
+    assert!(!emu.main.soc.ppu.lcdc(ppu::Lcdc::Enable));
+    emu.cycle();
+    let cpu = &mut emu.main.soc.cpu;
+    cpu.write(0x8000, 0xa5);
+    assert_eq!(emu.main.vram.read(0x0000), Ok(0xa5));
+}
+
+#[test]
+fn state_pending_input_works() {
+    use crate::api::part::joypad::{Event, Joypad as _, State};
+
+    let mut emu = setup();
+    let input = [
+        Event {
+            input: Button::A,
+            state: State::Dn,
+        },
+        Event {
+            input: Button::Down,
+            state: State::Up,
+        },
+    ];
+    emu.main.soc.joy.recv(input);
+
+    // Pending input is restored
+    let mut save = Vec::new();
+    emu.save_state(&mut save).unwrap();
+    let mut emu = setup();
+    emu.load_state(save.as_slice()).unwrap();
+    assert_eq!(emu.main.soc.joy.pending(), input);
+}
+
+#[test]
+fn state_invalid_fails() {
+    let mut emu = setup();
+    let mut save = Vec::new();
+    emu.save_state(&mut save).unwrap();
+    for _ in 0..70_224 {
+        emu.cycle();
+    }
+    let mut prev = Vec::new();
+    emu.save_state(&mut prev).unwrap();
+
+    // Mismatched cartridge
+    let mut rom = GAME.to_vec();
+    rom[0x14e] ^= 0xff;
+    let mut other = GameBoy::new();
+    other.insert(Cartridge::unchecked(&rom).unwrap());
+    assert!(matches!(
+        other.load_state(save.as_slice()),
+        Err(state::Error::Cartridge { .. })
+    ));
+    // Missing cartridge
+    assert!(matches!(
+        GameBoy::new().load_state(save.as_slice()),
+        Err(state::Error::Cartridge {
+            found: None,
+            expected: Some(_),
+        })
+    ));
+    // Invalid magic
+    assert!(matches!(
+        emu.load_state(&b"RGBZ"[..]),
+        Err(state::Error::Magic)
+    ));
+    // Truncated data leaves the emulator unchanged
+    assert!(matches!(
+        emu.load_state(&save[..save.len() / 2]),
+        Err(state::Error::Invalid)
+    ));
+    let mut have = Vec::new();
+    emu.save_state(&mut have).unwrap();
+    assert_eq!(have, prev);
+}
+
+/// Buttons pressed while driving the emulator.
+const KEYS: [Button; 4] = [Button::Start, Button::Right, Button::Up, Button::Left];
+
+/// Runs the emulator, pressing buttons along the way.
+fn drive(emu: &mut GameBoy, frames: u32, keys: [Button; 4]) {
+    use crate::api::part::joypad::{Event, Joypad as _, State};
+
+    for frame in 0..frames {
+        // Alternate pressing a button each frame
+        let input = keys[frame as usize % 4];
+        let state = if frame % 2 == 0 { State::Dn } else { State::Up };
+        for cycle in 0..70_224 {
+            if cycle == 1_234 * (frame + 1) {
+                emu.main.soc.joy.recv([Event { input, state }]);
+            }
+            emu.cycle();
+        }
+    }
+}
+
+#[test]
+fn movie_round_trip_works() {
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+
+    // Record from power-on
+    emu.record().unwrap();
+    drive(&mut emu, 16, KEYS);
+    let movie = emu.stop().unwrap();
+    assert!(matches!(movie.start, movie::Start::Power(_)));
+    assert_eq!(movie.input.len(), 16);
+    let mut want = Vec::new();
+    emu.save_state(&mut want).unwrap();
+
+    // Movies survive serialization
+    let mut buf = Vec::new();
+    movie.write(&mut buf).unwrap();
+    let movie = movie::Movie::read(buf.as_slice()).unwrap();
+
+    // Playback reproduces the run, ignoring frontend input
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+    emu.play(movie.clone()).unwrap();
+    drive(
+        &mut emu,
+        16,
+        [Button::A, Button::B, Button::Select, Button::Down],
+    );
+    let mut have = Vec::new();
+    emu.save_state(&mut have).unwrap();
+    assert_eq!(have, want);
+
+    // Power-on movies cannot be played once started
+    assert!(matches!(emu.play(movie), Err(movie::Error::Started)));
+}
+
+#[test]
+fn movie_cart_ram_works() {
+    // Load save data before recording
+    let mut cart = Cartridge::new(GAME).unwrap();
+    cart.body_mut()
+        .flash(&mut [0x5a; 0x800].as_slice())
+        .unwrap();
+    let mut emu = GameBoy::new();
+    emu.insert(cart);
+
+    // Record from power-on
+    emu.record().unwrap();
+    drive(&mut emu, 8, KEYS);
+    let movie = emu.stop().unwrap();
+    let mut want = Vec::new();
+    emu.save_state(&mut want).unwrap();
+
+    // Playback restores the recorded save data
+    let mut cart = Cartridge::new(GAME).unwrap();
+    cart.body_mut()
+        .flash(&mut [0xa5; 0x800].as_slice())
+        .unwrap();
+    let mut emu = GameBoy::new();
+    emu.insert(cart);
+    emu.play(movie).unwrap();
+    drive(&mut emu, 8, KEYS);
+    let mut have = Vec::new();
+    emu.save_state(&mut have).unwrap();
+    assert_eq!(have, want);
+}
+
+#[test]
+fn movie_load_state_stops() {
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+    drive(&mut emu, 2, KEYS);
+    let mut save = Vec::new();
+    emu.save_state(&mut save).unwrap();
+    drive(&mut emu, 2, KEYS);
+
+    // Loading a state from before the movie started stops recording
+    emu.record().unwrap();
+    drive(&mut emu, 4, KEYS);
+    emu.load_state(save.as_slice()).unwrap();
+    drive(&mut emu, 4, KEYS);
+    let movie = emu.stop().unwrap();
+    assert!(!movie.input.is_empty());
+    assert!(movie.input.iter().all(|input| input.cycle < 4 * 70_224));
+}
+
+#[test]
+fn movie_from_state_works() {
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+    drive(&mut emu, 4, KEYS);
+
+    // Record from a save state
+    emu.record().unwrap();
+    drive(&mut emu, 8, KEYS);
+    let movie = emu.stop().unwrap();
+    assert!(matches!(movie.start, movie::Start::State(_)));
+    let mut want = Vec::new();
+    emu.save_state(&mut want).unwrap();
+
+    // Playback restores the starting state
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+    emu.play(movie).unwrap();
+    for _ in 0..8 * 70_224 {
+        emu.cycle();
+    }
+    let mut have = Vec::new();
+    emu.save_state(&mut have).unwrap();
+    assert_eq!(have, want);
+}
@@ -1,3 +1,84 @@
 //! Game Boy models.
 
+use crate::api::part::camera::Camera;
+use crate::api::part::motion::Motion;
+use crate::parts::cart::mbc::camera::Sensor;
+use crate::parts::cart::mbc::huc3::Piezo;
+use crate::parts::cart::mbc::mbc5::Motor;
+use crate::parts::cart::mbc::mbc7::Accelerometer;
+use crate::parts::cart::mbc::Body;
+use crate::parts::cart::Cartridge;
+
+/// Implements [`has`](crate::api::core::has) traits by borrowing fields.
+///
+/// Each entry names the trait, its associated type and accessors, and the
+/// field path to borrow.
+macro_rules! has {
+    ($core:ty {$(
+        $trait:ident($assoc:ident = $part:ty) { $get:ident, $get_mut:ident } => $($field:ident).+;
+    )+}) => {$(
+        impl $crate::api::core::has::$trait for $core {
+            type $assoc = $part;
+
+            fn $get(&self) -> &Self::$assoc {
+                &self.$($field).+
+            }
+
+            fn $get_mut(&mut self) -> &mut Self::$assoc {
+                &mut self.$($field).+
+            }
+        }
+    )+};
+}
+
+pub mod cgb;
 pub mod dmg;
+pub mod sgb;
+
+/// Cartridge peripherals.
+///
+/// Persist across cartridge swaps, such that frontends may hold onto them.
+/// Any settings, such as the camera's image source or the accelerometer's
+/// tilt, carry over to the peripherals of each newly inserted cartridge.
+#[derive(Debug, Default)]
+struct Peripherals {
+    /// Image sensor.
+    cam: Sensor,
+    /// Motion sensor.
+    acc: Accelerometer,
+    /// Rumble motor.
+    vib: Motor,
+    /// Piezo speaker.
+    spk: Piezo,
+}
+
+impl Peripherals {
+    /// Connects the peripherals of a cartridge, if any.
+    fn connect(&mut self, cart: &Cartridge) {
+        match cart.body() {
+            // Connect image sensor
+            Body::Camera(mbc) => {
+                let mut cam = mbc.sensor().clone();
+                if let Some(src) = self.cam.take_source() {
+                    cam.set_source(src);
+                }
+                self.cam = cam;
+            }
+            // Connect motion sensor
+            Body::Mbc7(mbc) => {
+                let mut acc = mbc.accelerometer().clone();
+                acc.set_tilt(self.acc.tilt());
+                self.acc = acc;
+            }
+            // Connect rumble motor
+            Body::Mbc5(mbc) => {
+                if let Some(vib) = mbc.motor() {
+                    self.vib = vib.clone();
+                }
+            }
+            // Connect piezo speaker
+            Body::HuC3(mbc) => self.spk = mbc.speaker().clone(),
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Command packets.
+
+use log::warn;
+use rugby_arch::{Byte, Word};
+
+/// Packet size.
+pub const PACKET: usize = 16;
+
+/// Attribute block.
+///
+/// Region of the screen to be assigned palettes by [`ATTR_BLK`][attr].
+///
+/// [attr]: Command::AttrBlk
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Area {
+    /// Control flags.
+    ///
+    /// Whether to change the inside (bit 0), border (bit 1), and outside
+    /// (bit 2) of the area.
+    pub ctl: Byte,
+    /// Palette designation.
+    ///
+    /// Palettes used for the inside (bits 1:0), border (bits 3:2), and outside
+    /// (bits 5:4) of the area.
+    pub pal: Byte,
+    /// Top-left corner, in tiles.
+    pub beg: (Byte, Byte),
+    /// Bottom-right corner, in tiles.
+    pub end: (Byte, Byte),
+}
+
+/// Screen mask.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mask {
+    /// Show the screen.
+    #[default]
+    Cancel,
+    /// Freeze the current screen.
+    Freeze,
+    /// Blank the screen to black.
+    Black,
+    /// Blank the screen to color 0.
+    Color0,
+}
+
+/// System command.
+///
+/// Sent by the game over the joypad register as one or more 16 byte packets.
+/// See more details [here][cmds].
+///
+/// [cmds]: https://gbdev.io/pandocs/SGB_Command_Summary.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    /// `PAL01`, `PAL23`, `PAL03`, `PAL12`: Set a pair of palettes.
+    ///
+    /// Colors are given as color 0 (shared by all palettes), followed by
+    /// colors 1-3 of each palette.
+    Pal {
+        pals: (usize, usize),
+        cols: [Word; 7],
+    },
+    /// `ATTR_BLK`: Apply palettes to rectangular areas.
+    AttrBlk(Vec<Area>),
+    /// `ATTR_LIN`: Apply palettes to rows or columns.
+    ///
+    /// Each line is encoded as its index (bits 4:0), palette (bits 6:5), and
+    /// whether it is horizontal (bit 7).
+    AttrLin(Vec<Byte>),
+    /// `PAL_SET`: Apply system palettes.
+    PalSet { pals: [Word; 4], ctl: Byte },
+    /// `PAL_TRN`: Transfer system palettes from VRAM.
+    PalTrn,
+    /// `MLT_REQ`: Request multiplayer mode.
+    MltReq(Byte),
+    /// `CHR_TRN`: Transfer border tiles from VRAM.
+    ChrTrn { high: bool },
+    /// `PCT_TRN`: Transfer border map and palettes from VRAM.
+    PctTrn,
+    /// `MASK_EN`: Mask the screen.
+    MaskEn(Mask),
+}
+
+impl Command {
+    /// Gets the number of packets in a command.
+    #[must_use]
+    pub fn len(head: Byte) -> usize {
+        usize::from(head & 0x07).max(1)
+    }
+
+    /// Decodes a command from its packets.
+    ///
+    /// Returns `None` for unsupported commands.
+    #[must_use]
+    pub fn decode(data: &[Byte]) -> Option<Self> {
+        let word = |idx: usize| Word::from_le_bytes([data[idx], data[idx + 1]]);
+        let code = data[0] >> 3;
+        Some(match code {
+            // PAL01, PAL23, PAL03, PAL12
+            0x00..=0x03 => Self::Pal {
+                pals: [(0, 1), (2, 3), (0, 3), (1, 2)][usize::from(code)],
+                cols: std::array::from_fn(|idx| word(1 + 2 * idx)),
+            },
+            // ATTR_BLK
+            0x04 => Self::AttrBlk(
+                data[2..]
+                    .chunks_exact(6)
+                    .take(usize::from(data[1] & 0x1f))
+                    .map(|set| Area {
+                        ctl: set[0] & 0x07,
+                        pal: set[1] & 0x3f,
+                        beg: (set[2] & 0x1f, set[3] & 0x1f),
+                        end: (set[4] & 0x1f, set[5] & 0x1f),
+                    })
+                    .collect(),
+            ),
+            // ATTR_LIN
+            0x05 => Self::AttrLin(data[2..].iter().copied().take(data[1].into()).collect()),
+            // PAL_SET
+            0x0a => Self::PalSet {
+                pals: std::array::from_fn(|idx| word(1 + 2 * idx) & 0x01ff),
+                ctl: data[9],
+            },
+            // PAL_TRN
+            0x0b => Self::PalTrn,
+            // MLT_REQ
+            0x11 => Self::MltReq(data[1] & 0x03),
+            // CHR_TRN
+            0x13 => Self::ChrTrn {
+                high: data[1] & 0x01 != 0,
+            },
+            // PCT_TRN
+            0x14 => Self::PctTrn,
+            // MASK_EN
+            0x17 => Self::MaskEn(match data[1] & 0x03 {
+                0 => Mask::Cancel,
+                1 => Mask::Freeze,
+                2 => Mask::Black,
+                _ => Mask::Color0,
+            }),
+            _ => {
+                warn!("unsupported command: {code:#04x}");
+                return None;
+            }
+        })
+    }
+}
@@ -0,0 +1,194 @@
+//! Interface controller.
+
+use std::collections::VecDeque;
+
+use log::{debug, trace};
+use rugby_arch::mem::{Memory, Result};
+use rugby_arch::reg::Register;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::cmd::{Command, PACKET};
+use super::joypad;
+
+/// Super Game Boy interface controller.
+///
+/// Sits in front of the joypad register, which the game uses to transmit
+/// [command](Command) packets to the SNES one bit at a time:
+/// - A reset pulse (P14 and P15 both low) begins a packet.
+/// - Each bit is sent as P15 low (`0`) or P14 low (`1`), separated by both
+///   lines being released.
+/// - 128 bits are sent per packet (LSB first), followed by a `0` stop bit.
+///
+/// Additionally multiplexes up to four joypads once requested with
+/// [`MLT_REQ`][mlt].
+///
+/// [mlt]: Command::MltReq
+#[derive(Debug)]
+pub struct Icd {
+    /// Joypad registers.
+    pads: [Shared<joypad::Control>; 4],
+    /// Joypad select (mask).
+    mlt: Byte,
+    /// Joypad index.
+    idx: Byte,
+    /// Last written value.
+    p1: Byte,
+    /// Packet receiver.
+    rx: Receiver,
+    /// Received commands.
+    cmds: VecDeque<Command>,
+    /// Commands enable.
+    pub(super) enable: bool,
+}
+
+impl Icd {
+    /// Constructs a new `Icd`.
+    #[must_use]
+    pub fn new(pads: [Shared<joypad::Control>; 4]) -> Self {
+        Self {
+            pads,
+            mlt: Byte::default(),
+            idx: Byte::default(),
+            p1: 0xff,
+            rx: Receiver::default(),
+            cmds: VecDeque::default(),
+            enable: bool::default(),
+        }
+    }
+
+    /// Gets the number of active joypads.
+    #[must_use]
+    pub fn players(&self) -> usize {
+        usize::from(self.mlt) + 1
+    }
+
+    /// Takes the next received command, if any.
+    pub fn recv(&mut self) -> Option<Command> {
+        self.cmds.pop_front()
+    }
+
+    /// Handles a received command.
+    fn exec(&mut self, cmd: Command) {
+        debug!("received: {cmd:?}");
+        match cmd {
+            Command::MltReq(req) => {
+                // Select number of joypads
+                self.mlt = [0, 1, 0, 3][usize::from(req & 0x03)];
+                self.idx = 0;
+                debug!("players: {}", self.players());
+            }
+            cmd => self.cmds.push_back(cmd),
+        }
+    }
+}
+
+impl Block for Icd {
+    fn reset(&mut self) {
+        self.mlt = 0;
+        self.idx = 0;
+        self.p1 = 0xff;
+        self.rx = Receiver::default();
+        self.cmds.clear();
+    }
+}
+
+impl Memory for Icd {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Ok(self.load())
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Icd {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        let pad = self.pads[usize::from(self.idx)].load();
+        if self.mlt != 0 && self.p1 & 0x30 == 0x30 {
+            // Identify the selected joypad
+            pad & 0xf0 | (0x0f - self.idx)
+        } else {
+            pad
+        }
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        // Advance joypad on rising edge of P15
+        if self.mlt != 0 && value & !self.p1 & 0x20 != 0 {
+            self.idx = (self.idx + 1) & self.mlt;
+            trace!("selected joypad: {}", self.idx);
+        }
+        self.p1 = value;
+        // Forward to joypads
+        for pad in &self.pads {
+            pad.borrow_mut().store(value);
+        }
+        // Receive packet data
+        if !self.enable {
+            return;
+        }
+        if let Some(pkt) = self.rx.recv(value) {
+            trace!("packet: {pkt:02x?}");
+            self.rx.buf.extend(pkt);
+            if self.rx.buf.len() >= Command::len(self.rx.buf[0]) * PACKET {
+                let data = std::mem::take(&mut self.rx.buf);
+                if let Some(cmd) = Command::decode(&data) {
+                    self.exec(cmd);
+                }
+            }
+        }
+    }
+}
+
+/// Packet receiver.
+#[derive(Debug, Default)]
+struct Receiver {
+    /// Receiving a packet.
+    busy: bool,
+    /// Ready for next bit.
+    ready: bool,
+    /// Received bits.
+    bits: usize,
+    /// Packet data.
+    pkt: [Byte; PACKET],
+    /// Command data.
+    buf: Vec<Byte>,
+}
+
+impl Receiver {
+    /// Receives a write to the joypad register.
+    ///
+    /// Returns the packet once it has been completely received.
+    fn recv(&mut self, value: Byte) -> Option<[Byte; PACKET]> {
+        match value & 0x30 {
+            // Reset pulse
+            0x00 => {
+                self.busy = true;
+                self.ready = false;
+                self.bits = 0;
+                self.pkt = [0; PACKET];
+            }
+            // Release
+            0x30 => self.ready = true,
+            // Data bit
+            sel if self.busy && self.ready => {
+                self.ready = false;
+                let bit = sel == 0x10;
+                if self.bits < 8 * PACKET {
+                    self.pkt[self.bits / 8] |= Byte::from(bit) << (self.bits % 8);
+                    self.bits += 1;
+                } else {
+                    // Stop bit
+                    self.busy = false;
+                    return Some(self.pkt);
+                }
+            }
+            _ => (),
+        }
+        None
+    }
+}
@@ -0,0 +1,291 @@
+//! Super Game Boy display.
+
+use log::debug;
+use rugby_arch::{Byte, Word};
+
+use super::cmd::{Command, Mask};
+use super::ppu::{self, Ppu, Rgb};
+use crate::api::part::video::{self, Aspect, Video as Api};
+
+/// Display resolution.
+///
+/// The Game Boy's screen is centred within a border, for an output of 256x224
+/// pixels.
+pub const LCD: Aspect = Aspect { wd: 256, ht: 224 };
+
+/// Display framebuffer.
+pub type Frame = video::Frame<Rgb, { LCD.depth() }>;
+
+/// Allocates a blank display framebuffer.
+fn framebuffer() -> Box<Frame> {
+    vec![Rgb::default(); LCD.depth()]
+        .into_boxed_slice()
+        .try_into()
+        .unwrap()
+}
+
+/// Screen position within the border, in pixels.
+const ORIGIN: (usize, usize) = (48, 40);
+
+/// Screen width, in tiles.
+const COLS: usize = ppu::LCD.wd as usize / 8;
+
+/// Screen height, in tiles.
+const ROWS: usize = ppu::LCD.ht as usize / 8;
+
+/// Default palette.
+///
+/// Matches the SNES's palette `1-A`.
+const PALETTE: [Rgb; 4] = [Rgb(0x67bf), Rgb(0x265b), Rgb(0x10b5), Rgb(0x2866)];
+
+/// Super Game Boy display.
+///
+/// Latches the PPU's screen once each frame is complete, colouring it using
+/// palettes assigned to each tile, then surrounding it with a border.
+#[derive(Debug)]
+pub struct Lcd {
+    /// Output frame.
+    buf: Box<Frame>,
+    /// Vertical sync.
+    vsync: bool,
+    /// Latched screen.
+    scr: Box<ppu::Frame>,
+    /// Screen mask.
+    pub(super) mask: Mask,
+    /// Screen palettes.
+    pub(super) pal: [[Rgb; 4]; 4],
+    /// Palette attributes.
+    pub(super) attr: [Byte; COLS * ROWS],
+    /// System palettes.
+    sys: Box<[Rgb; 0x800]>,
+    /// Border tiles.
+    pub(super) chr: Box<[Byte; 0x2000]>,
+    /// Border map.
+    pub(super) map: Box<[Word; 0x400]>,
+    /// Border palettes.
+    pub(super) bpal: [[Rgb; 16]; 4],
+    /// Pending VRAM transfer.
+    xfer: Option<Command>,
+}
+
+impl Lcd {
+    /// Executes a display command.
+    pub fn exec(&mut self, cmd: Command) {
+        match cmd {
+            Command::Pal { pals, cols } => {
+                let cols = cols.map(|col| Rgb::from(col.to_le_bytes()));
+                for pal in &mut self.pal {
+                    pal[0] = cols[0];
+                }
+                self.pal[pals.0][1..].copy_from_slice(&cols[1..4]);
+                self.pal[pals.1][1..].copy_from_slice(&cols[4..7]);
+            }
+            Command::AttrBlk(areas) => {
+                for area in areas {
+                    let (x0, y0) = (usize::from(area.beg.0), usize::from(area.beg.1));
+                    let (x1, y1) = (usize::from(area.end.0), usize::from(area.end.1));
+                    for (idx, attr) in self.attr.iter_mut().enumerate() {
+                        let (x, y) = (idx % COLS, idx / COLS);
+                        let inside = (x0..=x1).contains(&x) && (y0..=y1).contains(&y);
+                        let border = inside && (x == x0 || x == x1 || y == y0 || y == y1);
+                        // Determine the tile's region
+                        let region = if border {
+                            1
+                        } else if inside {
+                            0
+                        } else {
+                            2
+                        };
+                        // Border follows the inside or outside when only one
+                        // of them is changed
+                        let region = match (region, area.ctl) {
+                            (1, 0b001) => 0,
+                            (1, 0b100) => 2,
+                            _ if area.ctl & (1 << region) == 0 => continue,
+                            _ => region,
+                        };
+                        *attr = (area.pal >> (2 * region)) & 0x03;
+                    }
+                }
+            }
+            Command::AttrLin(lines) => {
+                for line in lines {
+                    let idx = usize::from(line & 0x1f);
+                    let pal = (line >> 5) & 0x03;
+                    if line & 0x80 != 0 {
+                        // Horizontal line
+                        if let Some(row) = self.attr.chunks_exact_mut(COLS).nth(idx) {
+                            row.fill(pal);
+                        }
+                    } else if idx < COLS {
+                        // Vertical line
+                        for row in self.attr.chunks_exact_mut(COLS) {
+                            row[idx] = pal;
+                        }
+                    }
+                }
+            }
+            Command::PalSet { pals, ctl } => {
+                for (pal, sys) in self.pal.iter_mut().zip(pals) {
+                    let base = usize::from(sys) * 4;
+                    pal.copy_from_slice(&self.sys[base..base + 4]);
+                }
+                // Color 0 is shared by all palettes
+                let col = self.pal[0][0];
+                for pal in &mut self.pal {
+                    pal[0] = col;
+                }
+                if ctl & 0x40 != 0 {
+                    self.mask = Mask::Cancel;
+                }
+            }
+            Command::PalTrn | Command::ChrTrn { .. } | Command::PctTrn => {
+                // Transfer once the next frame is complete
+                self.xfer = Some(cmd);
+            }
+            Command::MaskEn(mask) => self.mask = mask,
+            Command::MltReq(_) => (),
+        }
+    }
+
+    /// Synchronizes with the PPU.
+    pub fn sync(&mut self, ppu: &Ppu) {
+        self.vsync = ppu.vsync();
+        if !self.vsync {
+            return;
+        }
+        let scr = ppu.screen();
+        // Perform pending transfer
+        if let Some(cmd) = self.xfer.take() {
+            self.transfer(&cmd, &capture(scr));
+        }
+        // Latch the screen
+        if self.mask != Mask::Freeze {
+            self.scr.copy_from_slice(scr);
+        }
+        self.draw();
+    }
+
+    /// Performs a VRAM transfer.
+    fn transfer(&mut self, cmd: &Command, data: &[Byte; 0x1000]) {
+        let pair = |idx: usize| [data[idx], data[idx + 1]];
+        debug!("transfer: {cmd:?}");
+        match *cmd {
+            Command::PalTrn => {
+                for (idx, col) in self.sys.iter_mut().enumerate() {
+                    *col = Rgb::from(pair(2 * idx));
+                }
+            }
+            Command::ChrTrn { high } => {
+                let base = usize::from(high) * data.len();
+                self.chr[base..base + data.len()].copy_from_slice(data);
+            }
+            Command::PctTrn => {
+                for (idx, tile) in self.map.iter_mut().enumerate() {
+                    *tile = Word::from_le_bytes(pair(2 * idx));
+                }
+                for (idx, col) in self.bpal.iter_mut().flatten().enumerate() {
+                    *col = Rgb::from(pair(0x800 + 2 * idx));
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Draws the output frame.
+    pub(super) fn draw(&mut self) {
+        let (wd, ht) = (usize::from(ppu::LCD.wd), usize::from(ppu::LCD.ht));
+        for (idx, px) in self.buf.iter_mut().enumerate() {
+            let (x, y) = (idx % usize::from(LCD.wd), idx / usize::from(LCD.wd));
+            // Look up border pixel
+            let tile = self.map[(y / 8) * 32 + x / 8];
+            let (mut col, mut row) = (x % 8, y % 8);
+            if tile & 0x4000 != 0 {
+                col = 7 - col;
+            }
+            if tile & 0x8000 != 0 {
+                row = 7 - row;
+            }
+            let chr = &self.chr[usize::from(tile & 0xff) * 32..][..32];
+            let color = (0..4).fold(0, |acc, plane| {
+                let byte = chr[(plane / 2) * 16 + 2 * row + plane % 2];
+                acc | usize::from((byte >> (7 - col)) & 1) << plane
+            });
+            // Determine pixel color
+            let (sx, sy) = (x.wrapping_sub(ORIGIN.0), y.wrapping_sub(ORIGIN.1));
+            *px = if color != 0 {
+                self.bpal[usize::from(tile >> 10) & 0x03][color]
+            } else if sx < wd && sy < ht {
+                match self.mask {
+                    Mask::Black => Rgb(0),
+                    Mask::Color0 => self.pal[0][0],
+                    Mask::Cancel | Mask::Freeze => {
+                        let pal = self.attr[(sy / 8) * COLS + sx / 8];
+                        self.pal[usize::from(pal)][self.scr[sy * wd + sx] as usize]
+                    }
+                }
+            } else {
+                self.pal[0][0]
+            };
+        }
+    }
+}
+
+/// Captures VRAM transfer data from the screen.
+///
+/// The game displays the data as 256 tiles across the top of the screen,
+/// which are reconstructed from the pixels' colors.
+fn capture(scr: &ppu::Frame) -> [Byte; 0x1000] {
+    let wd = usize::from(ppu::LCD.wd);
+    let mut data = [0; 0x1000];
+    for (idx, tile) in data.chunks_exact_mut(16).enumerate() {
+        let (tx, ty) = (idx % COLS, idx / COLS);
+        for (row, bytes) in tile.chunks_exact_mut(2).enumerate() {
+            let line = &scr[(8 * ty + row) * wd + 8 * tx..][..8];
+            for &px in line {
+                let px = px as Byte;
+                bytes[0] = bytes[0] << 1 | px & 0x01;
+                bytes[1] = bytes[1] << 1 | px >> 1;
+            }
+        }
+    }
+    data
+}
+
+impl Default for Lcd {
+    fn default() -> Self {
+        Self {
+            buf: framebuffer(),
+            vsync: bool::default(),
+            scr: vec![ppu::Color::default(); ppu::LCD.depth()]
+                .into_boxed_slice()
+                .try_into()
+                .unwrap(),
+            mask: Mask::default(),
+            pal: [PALETTE; 4],
+            attr: [0; COLS * ROWS],
+            sys: vec![Rgb::default(); 0x800]
+                .into_boxed_slice()
+                .try_into()
+                .unwrap(),
+            chr: vec![0; 0x2000].into_boxed_slice().try_into().unwrap(),
+            map: vec![0; 0x400].into_boxed_slice().try_into().unwrap(),
+            bpal: [[Rgb::default(); 16]; 4],
+            xfer: None,
+        }
+    }
+}
+
+impl Api for Lcd {
+    const SIZE: Aspect = LCD;
+
+    type Pixel = Rgb;
+
+    fn vsync(&self) -> bool {
+        self.vsync
+    }
+
+    fn frame(&self) -> &[Self::Pixel] {
+        &*self.buf
+    }
+}
@@ -0,0 +1,302 @@
+//! SGB: [Super Game Boy].
+//!
+//! [Super Game Boy]: https://en.wikipedia.org/wiki/Super_Game_Boy
+
+use log::warn;
+use rugby_arch::mio::Mmio;
+use rugby_arch::reg::Port;
+use rugby_arch::{Block, Shared, Word};
+
+use self::apu::Apu;
+use self::cart::mbc::camera::Sensor;
+use self::cart::mbc::huc3::Piezo;
+use self::cart::mbc::mbc5::Motor;
+use self::cart::mbc::mbc7::Accelerometer;
+use self::cpu::Cpu;
+use self::icd::Icd;
+use self::joypad::Joypad;
+use self::lcd::Lcd;
+use self::pcb::Motherboard;
+use self::serial::Serial;
+use super::Peripherals;
+use crate::api::core::Core;
+use crate::api::part::proc::Processor;
+
+pub mod cmd;
+pub mod icd;
+pub mod lcd;
+
+pub use self::cart::Cartridge;
+pub use self::joypad::Button;
+pub use self::lcd::LCD;
+pub use crate::dmg::{mem, pcb, Boot, Chip, Mmap};
+pub use crate::parts::cpu::sm83 as cpu;
+pub use crate::parts::{apu, boot, cart, dma, joypad, pic, ppu, serial, timer};
+
+/// Super Game Boy cartridge adapter.
+///
+/// Runs the DMG's hardware, with the SNES receiving commands from the game
+/// over the joypad register to colourize and frame the screen.
+#[derive(Debug)]
+pub struct GameBoy {
+    /// Boot ROM.
+    boot: Option<boot::Chip>,
+    /// Game cartridge.
+    cart: Option<Cartridge>,
+    /// SGB-CPU-01 Motherboard.
+    main: Motherboard,
+    /// Interface controller.
+    icd: Shared<Icd>,
+    /// Super Game Boy display.
+    lcd: Lcd,
+    /// Additional joypads.
+    pads: [Joypad; 3],
+    /// Cartridge peripherals.
+    dev: Peripherals,
+}
+
+impl Default for GameBoy {
+    fn default() -> Self {
+        let main = Motherboard::default();
+        let pads = [(); 3].map(|()| Joypad::new(main.soc.pic.line.clone()));
+        let icd = Shared::new(Icd::new([
+            main.soc.joy.con.clone(),
+            pads[0].con.clone(),
+            pads[1].con.clone(),
+            pads[2].con.clone(),
+        ]));
+        // Intercept joypad register
+        {
+            let ibus = &mut *main.noc.ibus.borrow_mut();
+            main.soc.joy.detach(ibus);
+            ibus.map(0xff00..=0xff00, icd.clone().into());
+        }
+        Self {
+            boot: Option::default(),
+            cart: Option::default(),
+            main,
+            icd,
+            lcd: Lcd::default(),
+            pads,
+            dev: Peripherals::default(),
+        }
+    }
+}
+
+impl GameBoy {
+    /// Constructs a new `GameBoy`.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut this = Self::default();
+
+        // Simulate bootup sequence
+        this.boot();
+
+        this
+    }
+
+    /// Constructs a new `GameBoy`, initialized with the provided boot ROM.
+    #[must_use]
+    pub fn with(boot: Boot) -> Self {
+        let mut this = Self::default();
+
+        // Initialize boot ROM
+        let boot = boot::Chip::new(boot);
+        boot.attach(&mut this.main.noc.ibus.borrow_mut());
+        this.boot = Some(boot);
+
+        this
+    }
+
+    /// Simulate the bootup sequence.
+    ///
+    /// This prepares the `GameBoy` to run the contents of a game cartridge.
+    /// When no [boot ROM](Boot) is installed, this must be called before
+    /// cartridge execution.
+    ///
+    /// Interrupts remain disabled once control is transferred, as the boot ROM
+    /// never enables them.
+    #[rustfmt::skip]
+    pub fn boot(&mut self) {
+        // Read cartridge header
+        self.scan();
+
+        let cpu = &mut self.main.soc.cpu;
+
+        // Initialize registers
+        #[allow(clippy::items_after_statements)]
+        type Select = <Cpu as Port<Word>>::Select;
+        cpu.store(Select::AF, 0x0100_u16);
+        cpu.store(Select::BC, 0x0014_u16);
+        cpu.store(Select::DE, 0x0000_u16);
+        cpu.store(Select::HL, 0xc060_u16);
+        cpu.store(Select::SP, 0xfffe_u16);
+
+        // Perform bootup sequence
+        cpu.write(0xff26, 0x80); // enable audio
+        cpu.write(0xff11, 0x80); // set CH1 duty cycle
+        cpu.write(0xff12, 0xf3); // set CH1 envelope
+        cpu.write(0xff25, 0xf3); // set panning
+        cpu.write(0xff24, 0x77); // set master volume
+        cpu.write(0xff13, 0xc1); // set CH1 period
+        cpu.write(0xff14, 0x87); // trigger CH1
+        cpu.write(0xff40, 0x91); // enable display
+        cpu.write(0xff50, 0x01); // disable boot ROM
+        cpu.goto(0x0100);        // transfer program control
+    }
+
+    /// Simulate the boot ROM's cartridge header reads.
+    ///
+    /// See [`dmg::GameBoy`](crate::dmg::GameBoy) for details.
+    fn scan(&self) {
+        if self.cart.is_none() {
+            return;
+        }
+        let cpu = &self.main.soc.cpu;
+        let logo = 0x0104..=0x0133;
+        let data: Vec<_> = logo.clone().map(|addr| cpu.read(addr)).collect();
+        for addr in logo.chain(0x0134..=0x014d) {
+            let _ = cpu.read(addr);
+        }
+        self.main.logo(&data);
+    }
+
+    /// Gets the inserted game cartridge, if any.
+    #[must_use]
+    pub fn cart(&self) -> Option<&Cartridge> {
+        self.cart.as_ref()
+    }
+
+    /// Inserts a game cartridge.
+    ///
+    /// If a cartridge is already inserted, it will first be
+    /// [ejected](Self::eject).
+    ///
+    /// Commands are only accepted from cartridges with the header's SGB flag.
+    pub fn insert(&mut self, cart: Cartridge) {
+        // Disconnect previous cartridge
+        if let Some(cart) = self.eject() {
+            warn!("ejected previous cartridge: {}", cart.header());
+        }
+        // Connect cartridge peripherals
+        self.dev.connect(&cart);
+        // Enable commands
+        self.icd.borrow_mut().enable = cart.header().sgb;
+        // Insert supplied cartridge
+        cart.attach(&mut self.main.noc.ebus.borrow_mut());
+        self.cart = Some(cart);
+        // Simulate boot ROM header reads
+        if self.boot.is_none() {
+            self.scan();
+        }
+    }
+
+    /// Ejects the inserted game cartridge, if any.
+    pub fn eject(&mut self) -> Option<Cartridge> {
+        // Disconnect from bus
+        let ebus = &mut *self.main.noc.ebus.borrow_mut();
+        if let Some(cart) = &self.cart {
+            cart.detach(ebus);
+        }
+        // Disable commands
+        self.icd.borrow_mut().enable = false;
+        // Remove inserted cartridge
+        self.cart.take()
+    }
+
+    /// Gets the number of active joypads.
+    ///
+    /// Games may request up to four joypads with
+    /// [`MLT_REQ`](cmd::Command::MltReq).
+    #[must_use]
+    pub fn players(&self) -> usize {
+        self.icd.borrow().players()
+    }
+
+    /// Borrows a player's joypad.
+    ///
+    /// Player 1's joypad is also available through [`core::has::Joypad`].
+    #[must_use]
+    pub fn player(&self, idx: usize) -> Option<&Joypad> {
+        match idx {
+            0 => Some(&self.main.soc.joy),
+            idx => self.pads.get(idx - 1),
+        }
+    }
+
+    /// Mutably borrows a player's joypad.
+    pub fn player_mut(&mut self, idx: usize) -> Option<&mut Joypad> {
+        match idx {
+            0 => Some(&mut self.main.soc.joy),
+            idx => self.pads.get_mut(idx - 1),
+        }
+    }
+}
+
+impl Block for GameBoy {
+    fn ready(&self) -> bool {
+        self.main.ready()
+    }
+
+    fn cycle(&mut self) {
+        self.main.cycle();
+        // Joypads: 4 MiHz
+        for pad in self.pads.iter_mut().filter(|pad| pad.ready()) {
+            pad.cycle();
+        }
+        // Interface: commands
+        while let Some(cmd) = self.icd.borrow_mut().recv() {
+            self.lcd.exec(cmd);
+        }
+        // Display: vsync
+        self.lcd.sync(&self.main.soc.ppu);
+        // Cartridge: 4 MiHz
+        if let Some(cart) = self.cart.as_mut().filter(|cart| cart.ready()) {
+            cart.cycle();
+        }
+    }
+
+    #[rustfmt::skip]
+    fn reset(&mut self) {
+        self.main.reset();
+        self.pads.iter_mut().for_each(Block::reset);
+        self.icd.borrow_mut().reset();
+        self.lcd = Lcd::default();
+        self.cart.as_mut().map(Block::reset);
+        self.boot.as_mut().map(Block::reset).unwrap_or_else(|| self.boot());
+    }
+}
+
+impl Core for GameBoy {}
+
+has! {
+    GameBoy {
+        Audio(Audio = Apu) { audio, audio_mut } => main.soc.apu;
+        Motion(Motion = Accelerometer) { motion, motion_mut } => dev.acc;
+        Processor(Proc = Cpu) { proc, proc_mut } => main.soc.cpu;
+        Camera(Camera = Sensor) { camera, camera_mut } => dev.cam;
+        Joypad(Joypad = Joypad) { joypad, joypad_mut } => main.soc.joy;
+        Rumble(Rumble = Motor) { rumble, rumble_mut } => dev.vib;
+        Serial(Serial = Serial) { serial, serial_mut } => main.soc.ser;
+        Speaker(Speaker = Piezo) { speaker, speaker_mut } => dev.spk;
+        Video(Video = Lcd) { video, video_mut } => lcd;
+    }
+}
+
+/// Debug introspection.
+#[cfg(feature = "debug")]
+impl GameBoy {
+    /// Borrows the core's system-on-chip.
+    #[must_use]
+    pub fn chip(&self) -> &Chip {
+        &self.main.soc
+    }
+
+    /// Mutably borrows the core's system-on-chip.
+    pub fn chip_mut(&mut self) -> &mut Chip {
+        &mut self.main.soc
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,174 @@
+use rugby_arch::Byte;
+
+use super::cmd::PACKET;
+use super::lcd::LCD;
+use super::ppu::Rgb;
+use super::*;
+use crate::api::part::joypad::{Event, Joypad as _, State};
+use crate::api::part::video::Video;
+
+/// Sample ROM header.
+const GAME: &[Byte; 0x8000] = include_bytes!("../../../../roms/games/2048/2048.gb");
+
+fn setup() -> GameBoy {
+    // Enable the cartridge's SGB functions
+    let mut rom = GAME.to_vec();
+    rom[0x146] = 0x03;
+    rom[0x14d] = cart::header::hchk(&rom);
+    // Create a `GameBoy` instance
+    let mut emu = GameBoy::new();
+    // Load the cartridge into the emulator
+    emu.insert(Cartridge::new(&rom).unwrap());
+
+    emu
+}
+
+/// Sends a command over the joypad register.
+fn send(emu: &mut GameBoy, data: &[Byte]) {
+    let cpu = &mut emu.main.soc.cpu;
+    for pkt in data.chunks(PACKET) {
+        // Reset pulse
+        cpu.write(0xff00, 0x00);
+        cpu.write(0xff00, 0x30);
+        // Packet data, followed by stop bit
+        let bits = pkt
+            .iter()
+            .flat_map(|byte| (0..8).map(move |bit| byte >> bit & 1));
+        for bit in bits.chain([0]) {
+            cpu.write(0xff00, if bit == 0 { 0x20 } else { 0x10 });
+            cpu.write(0xff00, 0x30);
+        }
+    }
+    // Process received commands
+    while let Some(cmd) = emu.icd.borrow_mut().recv() {
+        emu.lcd.exec(cmd);
+    }
+}
+
+#[test]
+fn pal_works() {
+    let mut emu = setup();
+
+    // PAL01
+    let mut data = [0; PACKET];
+    data[0] = 1;
+    for (idx, col) in (0x1000..).step_by(0x0101).take(7).enumerate() {
+        [data[1 + 2 * idx], data[2 + 2 * idx]] = Word::to_le_bytes(col);
+    }
+    send(&mut emu, &data);
+    assert_eq!(
+        emu.lcd.pal[0],
+        [Rgb(0x1000), Rgb(0x1101), Rgb(0x1202), Rgb(0x1303)]
+    );
+    assert_eq!(
+        emu.lcd.pal[1],
+        [Rgb(0x1000), Rgb(0x1404), Rgb(0x1505), Rgb(0x1606)]
+    );
+    // Color 0 is shared by all palettes
+    assert_eq!(emu.lcd.pal[3][0], Rgb(0x1000));
+}
+
+#[test]
+fn commands_require_sgb_flag() {
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+
+    // MASK_EN (black)
+    let mut data = [0; PACKET];
+    data[0] = 0x17 << 3 | 1;
+    data[1] = 0x02;
+    send(&mut emu, &data);
+    assert_eq!(emu.lcd.mask, cmd::Mask::Cancel);
+}
+
+#[test]
+fn attr_blk_works() {
+    let mut emu = setup();
+
+    // ATTR_BLK: inside 1, border 2, outside 3
+    let mut data = [0; PACKET];
+    data[0] = 0x04 << 3 | 1;
+    data[1] = 1;
+    data[2..8].copy_from_slice(&[0b111, 0b11_10_01, 2, 3, 6, 8]);
+    send(&mut emu, &data);
+    let attr = |x: usize, y: usize| emu.lcd.attr[y * 20 + x];
+    assert_eq!(attr(4, 5), 1);
+    assert_eq!(attr(2, 3), 2);
+    assert_eq!(attr(6, 6), 2);
+    assert_eq!(attr(0, 0), 3);
+    assert_eq!(attr(19, 17), 3);
+
+    // ATTR_LIN: row 0 uses palette 2, column 19 uses palette 1
+    let mut data = [0; PACKET];
+    data[0] = 0x05 << 3 | 1;
+    data[1] = 2;
+    data[2..4].copy_from_slice(&[0x80 | 2 << 5, 1 << 5 | 0x13]);
+    send(&mut emu, &data);
+    let attr = |x: usize, y: usize| emu.lcd.attr[y * 20 + x];
+    assert_eq!(attr(0, 0), 2);
+    assert_eq!(attr(19, 0), 1);
+    assert_eq!(attr(19, 17), 1);
+    assert_eq!(attr(4, 5), 1);
+}
+
+#[test]
+fn mlt_req_works() {
+    let mut emu = setup();
+    assert_eq!(emu.players(), 1);
+
+    // MLT_REQ (4 players)
+    let mut data = [0; PACKET];
+    data[0] = 0x11 << 3 | 1;
+    data[1] = 0x03;
+    send(&mut emu, &data);
+    assert_eq!(emu.players(), 4);
+
+    // Press A on player 3
+    emu.player_mut(2).unwrap().recv([Event {
+        input: Button::A,
+        state: State::Dn,
+    }]);
+    // Input is applied on the next cycle
+    emu.cycle();
+
+    // Joypads are selected on rising edges of P15
+    let cpu = &mut emu.main.soc.cpu;
+    let mut ids = Vec::new();
+    let mut keys = Vec::new();
+    for _ in 0..4 {
+        cpu.write(0xff00, 0x10);
+        keys.push(cpu.read(0xff00) & 0x0f);
+        cpu.write(0xff00, 0x30);
+        ids.push(cpu.read(0xff00) & 0x0f);
+    }
+    assert_eq!(ids, [0x0e, 0x0d, 0x0c, 0x0f]);
+    assert_eq!(keys, [0x0f, 0x0f, 0x0e, 0x0f]);
+}
+
+#[test]
+fn border_works() {
+    let mut emu = setup();
+
+    // Border uses color 0 of palette 0
+    emu.lcd.draw();
+    assert_eq!(emu.lcd.frame().len(), LCD.depth());
+    assert_eq!(emu.lcd.frame()[0], Rgb(0x67bf));
+
+    // Border tile 1 uses color 15 of palette 1
+    emu.lcd.chr[32..64].fill(0xff);
+    emu.lcd.map[0] = 1 << 10 | 1;
+    emu.lcd.bpal[1][15] = Rgb(0x7c00);
+    emu.lcd.draw();
+    assert_eq!(emu.lcd.frame()[0], Rgb(0x7c00));
+    assert_eq!(emu.lcd.frame()[8], Rgb(0x67bf));
+
+    // Screen is blanked by MASK_EN
+    let mut data = [0; PACKET];
+    data[0] = 0x17 << 3 | 1;
+    data[1] = 0x02;
+    send(&mut emu, &data);
+    emu.lcd.draw();
+    let wd = usize::from(LCD.wd);
+    assert_eq!(emu.lcd.frame()[40 * wd + 48], Rgb(0));
+    assert_eq!(emu.lcd.frame()[40 * wd + 47], Rgb(0x67bf));
+}
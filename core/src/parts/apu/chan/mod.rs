@@ -0,0 +1,185 @@
+//! Sound channels.
+
+use std::io::{self, Read, Write};
+
+use rugby_arch::snap::Snapshot;
+use rugby_arch::Byte;
+
+pub mod noise;
+pub mod pulse;
+pub mod wave;
+
+pub use self::noise::Noise;
+pub use self::pulse::Pulse;
+pub use self::wave::Wave;
+
+/// Length timer.
+///
+/// Shuts off its channel after a programmable duration, clocked at 256 Hz by
+/// the frame sequencer. See more details [here][length].
+///
+/// [length]: https://gbdev.io/pandocs/Audio.html#length-timer
+#[derive(Debug)]
+pub struct Length {
+    /// Maximum length.
+    max: u16,
+    /// Timer enable.
+    ena: bool,
+    /// Timer counter.
+    ctr: u16,
+}
+
+impl Length {
+    /// Constructs a new `Length`.
+    #[must_use]
+    pub fn new(max: u16) -> Self {
+        Self {
+            max,
+            ena: bool::default(),
+            ctr: u16::default(),
+        }
+    }
+
+    /// Loads the length counter from the value in `NRx1`.
+    pub fn load(&mut self, len: Byte) {
+        self.ctr = self.max - u16::from(len);
+    }
+
+    /// Handles a write to `NRx4`.
+    ///
+    /// Returns whether the channel should be disabled as a result of the
+    /// write.
+    ///
+    /// # Note
+    ///
+    /// When the next frame sequencer step will not clock the length timer,
+    /// enabling it results in an extra clock. Additionally, triggering with a
+    /// counter of zero reloads the counter to its maximum, less the extra
+    /// clock.
+    #[must_use]
+    pub fn write(&mut self, nrx4: Byte, half: bool) -> bool {
+        let trigger = nrx4 & 0x80 != 0;
+        let prev = self.ena;
+        self.ena = nrx4 & 0x40 != 0;
+        // Extra length clocking
+        let mut off = false;
+        if half && !prev && self.ena && self.ctr != 0 {
+            self.ctr -= 1;
+            off = self.ctr == 0;
+        }
+        // Trigger reload
+        if trigger && self.ctr == 0 {
+            self.ctr = self.max;
+            if half && self.ena {
+                self.ctr -= 1;
+            }
+        }
+        off && !trigger
+    }
+
+    /// Resets the length timer on power-off.
+    ///
+    /// # Note
+    ///
+    /// On DMG, the length counter is unaffected by power.
+    pub fn reset(&mut self) {
+        self.ena = false;
+    }
+
+    /// Clocks the length timer.
+    ///
+    /// Returns whether the timer expired.
+    #[must_use]
+    pub fn clock(&mut self) -> bool {
+        if self.ena && self.ctr != 0 {
+            self.ctr -= 1;
+            self.ctr == 0
+        } else {
+            false
+        }
+    }
+}
+
+impl Snapshot for Length {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.ena.save(buf)?;
+        self.ctr.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.ena.restore(buf)?;
+        self.ctr.restore(buf)
+    }
+}
+
+/// Volume envelope.
+///
+/// Periodically adjusts the channel's volume, clocked at 64 Hz by the frame
+/// sequencer. See more details [here][envelope].
+///
+/// [envelope]: https://gbdev.io/pandocs/Audio_Registers.html#ff12--nr12-channel-1-volume--envelope
+#[derive(Debug, Default)]
+pub struct Envelope {
+    /// Current volume.
+    vol: Byte,
+    /// Sweep timer.
+    clk: Byte,
+}
+
+impl Envelope {
+    /// Gets the current volume.
+    #[must_use]
+    pub fn volume(&self) -> Byte {
+        self.vol
+    }
+
+    /// Reloads the envelope on channel trigger.
+    pub fn trigger(&mut self, nrx2: Byte) {
+        self.vol = nrx2 >> 4;
+        self.clk = Self::pace(nrx2);
+    }
+
+    /// Clocks the envelope.
+    pub fn clock(&mut self, nrx2: Byte) {
+        self.clk = self.clk.saturating_sub(1);
+        if self.clk != 0 {
+            return;
+        }
+        self.clk = Self::pace(nrx2);
+        // Adjust volume
+        if nrx2 & 0x07 != 0 {
+            let up = nrx2 & 0x08 != 0;
+            match (up, self.vol) {
+                (true, 0..=14) => self.vol += 1,
+                (false, 1..=15) => self.vol -= 1,
+                _ => (),
+            }
+        }
+    }
+
+    /// Gets the sweep pace, treating a pace of zero as eight.
+    fn pace(nrx2: Byte) -> Byte {
+        match nrx2 & 0x07 {
+            0 => 8,
+            pace => pace,
+        }
+    }
+}
+
+impl Snapshot for Envelope {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.vol.save(buf)?;
+        self.clk.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.vol.restore(buf)?;
+        self.clk.restore(buf)
+    }
+}
+
+/// Checks if a channel's DAC is enabled, given its `NRx2`.
+#[must_use]
+fn dac(nrx2: Byte) -> bool {
+    nrx2 & 0xf8 != 0
+}
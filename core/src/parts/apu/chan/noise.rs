@@ -0,0 +1,188 @@
+//! Noise channel.
+
+use std::io::{self, Read, Write};
+
+use log::trace;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Byte, Shared, Word};
+
+use super::super::reg::Reg;
+use super::{dac, Envelope, Length};
+
+/// Clock divider periods.
+const DIVISOR: [u32; 8] = [8, 16, 32, 48, 64, 80, 96, 112];
+
+/// Noise channel registers.
+#[derive(Debug)]
+pub struct Control {
+    /// Length timer.
+    pub nrx1: Shared<Reg>,
+    /// Volume & envelope.
+    pub nrx2: Shared<Reg>,
+    /// Frequency & randomness.
+    pub nrx3: Shared<Reg>,
+    /// Control.
+    pub nrx4: Shared<Reg>,
+}
+
+/// Noise channel.
+///
+/// Produces pseudo-random noise using a linear-feedback shift register.
+#[derive(Debug)]
+pub struct Noise {
+    /// Channel registers.
+    reg: Control,
+    /// Channel enable.
+    ena: bool,
+    /// Length timer.
+    len: Length,
+    /// Volume envelope.
+    env: Envelope,
+    /// Period divider.
+    clk: u32,
+    /// Linear-feedback shift register.
+    lfsr: Word,
+}
+
+impl Noise {
+    /// Constructs a new `Noise`.
+    #[must_use]
+    pub fn new(reg: Control) -> Self {
+        Self {
+            reg,
+            ena: bool::default(),
+            len: Length::new(64),
+            env: Envelope::default(),
+            clk: u32::default(),
+            lfsr: Word::default(),
+        }
+    }
+
+    /// Checks if the channel is enabled.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.ena
+    }
+
+    /// Gets the channel's digital output, if its DAC is enabled.
+    #[must_use]
+    pub fn output(&self) -> Option<Byte> {
+        let nrx2 = self.reg.nrx2.borrow().data();
+        dac(nrx2).then(|| {
+            let high = self.ena && self.lfsr & 0x0001 == 0;
+            if high {
+                self.env.volume()
+            } else {
+                0
+            }
+        })
+    }
+
+    /// Gets the channel's period.
+    fn period(&self) -> u32 {
+        let nrx3 = self.reg.nrx3.borrow().data();
+        DIVISOR[usize::from(nrx3 & 0x07)] << (nrx3 >> 4)
+    }
+
+    /// Handles pending register writes.
+    pub fn poll(&mut self, half: bool) {
+        // NRx1
+        if let Some(nrx1) = self.reg.nrx1.borrow_mut().poll() {
+            self.len.load(nrx1 & 0x3f);
+        }
+        // NRx2
+        let nrx2 = self.reg.nrx2.borrow_mut().poll();
+        if nrx2.is_some_and(|nrx2| !dac(nrx2)) {
+            self.ena = false;
+        }
+        // NRx3
+        self.reg.nrx3.borrow_mut().poll();
+        // NRx4
+        let nrx4 = self.reg.nrx4.borrow_mut().poll();
+        if let Some(nrx4) = nrx4 {
+            if self.len.write(nrx4, half) {
+                self.ena = false;
+            }
+            if nrx4 & 0x80 != 0 {
+                self.trigger();
+            }
+        }
+    }
+
+    /// Triggers the channel.
+    fn trigger(&mut self) {
+        trace!("noise channel triggered");
+        let nrx2 = self.reg.nrx2.borrow().data();
+        self.ena = dac(nrx2);
+        self.clk = self.period();
+        self.env.trigger(nrx2);
+        self.lfsr = 0x7fff;
+    }
+
+    /// Clocks the length timer.
+    pub fn length(&mut self) {
+        if self.len.clock() {
+            self.ena = false;
+        }
+    }
+
+    /// Clocks the volume envelope.
+    pub fn envelope(&mut self) {
+        if self.ena {
+            self.env.clock(self.reg.nrx2.borrow().data());
+        }
+    }
+
+    /// Resets the channel on power-off.
+    ///
+    /// # Note
+    ///
+    /// On DMG, the length counter is unaffected by power.
+    pub fn reset(&mut self) {
+        self.ena = false;
+        self.len.reset();
+        self.env = Envelope::default();
+        self.clk = 0;
+        self.lfsr = 0;
+    }
+
+    /// Advances the channel by a single T-cycle.
+    pub fn cycle(&mut self) {
+        self.clk = self.clk.saturating_sub(1);
+        if self.clk == 0 {
+            self.clk = self.period();
+            // Clock shifts of 14 and 15 receive no clocks
+            if self.reg.nrx3.borrow().data() >> 4 < 14 {
+                self.shift();
+            }
+        }
+    }
+
+    /// Clocks the linear-feedback shift register.
+    fn shift(&mut self) {
+        let nrx3 = self.reg.nrx3.borrow().data();
+        let bit = (self.lfsr ^ (self.lfsr >> 1)) & 0x0001;
+        self.lfsr = (self.lfsr >> 1) | bit << 14;
+        if nrx3 & 0x08 != 0 {
+            self.lfsr = self.lfsr & !0x0040 | bit << 6;
+        }
+    }
+}
+
+impl Snapshot for Noise {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.ena.save(buf)?;
+        self.len.save(buf)?;
+        self.env.save(buf)?;
+        self.clk.save(buf)?;
+        self.lfsr.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.ena.restore(buf)?;
+        self.len.restore(buf)?;
+        self.env.restore(buf)?;
+        self.clk.restore(buf)?;
+        self.lfsr.restore(buf)
+    }
+}
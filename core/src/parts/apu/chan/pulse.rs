@@ -0,0 +1,367 @@
+//! Pulse channel.
+
+use std::io::{self, Read, Write};
+
+use log::trace;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Byte, Shared, Word};
+
+use super::super::reg::Reg;
+use super::{dac, Envelope, Length};
+
+/// Duty cycle waveforms.
+#[rustfmt::skip]
+const DUTY: [[Byte; 8]; 4] = [
+    [0, 0, 0, 0, 0, 0, 0, 1], // 12.5%
+    [1, 0, 0, 0, 0, 0, 0, 1], // 25%
+    [1, 0, 0, 0, 0, 1, 1, 1], // 50%
+    [0, 1, 1, 1, 1, 1, 1, 0], // 75%
+];
+
+/// Pulse channel registers.
+#[derive(Debug)]
+pub struct Control {
+    /// Length timer & duty cycle.
+    pub nrx1: Shared<Reg>,
+    /// Volume & envelope.
+    pub nrx2: Shared<Reg>,
+    /// Period low.
+    pub nrx3: Shared<Reg>,
+    /// Period high & control.
+    pub nrx4: Shared<Reg>,
+}
+
+/// Pulse channel.
+///
+/// Produces a square wave with a selectable duty cycle. Channel 1 additionally
+/// includes a period [sweep](Sweep).
+#[derive(Debug)]
+pub struct Pulse {
+    /// Channel registers.
+    reg: Control,
+    /// Period sweep.
+    swp: Option<Sweep>,
+    /// Channel enable.
+    ena: bool,
+    /// Length timer.
+    len: Length,
+    /// Volume envelope.
+    env: Envelope,
+    /// Period divider.
+    clk: Word,
+    /// Duty step.
+    pos: usize,
+}
+
+impl Pulse {
+    /// Constructs a new `Pulse`.
+    #[must_use]
+    pub fn new(reg: Control, swp: Option<Shared<Reg>>) -> Self {
+        Self {
+            reg,
+            swp: swp.map(Sweep::new),
+            ena: bool::default(),
+            len: Length::new(64),
+            env: Envelope::default(),
+            clk: Word::default(),
+            pos: usize::default(),
+        }
+    }
+
+    /// Checks if the channel is enabled.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.ena
+    }
+
+    /// Gets the channel's digital output, if its DAC is enabled.
+    #[must_use]
+    pub fn output(&self) -> Option<Byte> {
+        let nrx1 = self.reg.nrx1.borrow().data();
+        let nrx2 = self.reg.nrx2.borrow().data();
+        dac(nrx2).then(|| {
+            let duty = usize::from(nrx1 >> 6);
+            let high = self.ena && DUTY[duty][self.pos] != 0;
+            if high {
+                self.env.volume()
+            } else {
+                0
+            }
+        })
+    }
+
+    /// Gets the channel's period.
+    fn period(&self) -> Word {
+        let lo = self.reg.nrx3.borrow().data();
+        let hi = self.reg.nrx4.borrow().data() & 0x07;
+        Word::from_le_bytes([lo, hi])
+    }
+
+    /// Sets the channel's period.
+    fn set_period(&self, period: Word) {
+        let [lo, hi] = period.to_le_bytes();
+        self.reg.nrx3.borrow_mut().set(lo);
+        let mut nrx4 = self.reg.nrx4.borrow_mut();
+        let data = nrx4.data() & !0x07 | hi & 0x07;
+        nrx4.set(data);
+    }
+
+    /// Handles pending register writes.
+    pub fn poll(&mut self, half: bool) {
+        // NRx0
+        if let Some(swp) = &mut self.swp {
+            if swp.poll() {
+                self.ena = false;
+            }
+        }
+        // NRx1
+        if let Some(nrx1) = self.reg.nrx1.borrow_mut().poll() {
+            self.len.load(nrx1 & 0x3f);
+        }
+        // NRx2
+        let nrx2 = self.reg.nrx2.borrow_mut().poll();
+        if nrx2.is_some_and(|nrx2| !dac(nrx2)) {
+            self.ena = false;
+        }
+        // NRx4
+        let nrx4 = self.reg.nrx4.borrow_mut().poll();
+        if let Some(nrx4) = nrx4 {
+            if self.len.write(nrx4, half) {
+                self.ena = false;
+            }
+            if nrx4 & 0x80 != 0 {
+                self.trigger();
+            }
+        }
+    }
+
+    /// Triggers the channel.
+    fn trigger(&mut self) {
+        trace!("pulse channel triggered");
+        self.ena = true;
+        self.clk = (2048 - self.period()) * 4;
+        self.env.trigger(self.reg.nrx2.borrow().data());
+        let period = self.period();
+        if let Some(swp) = &mut self.swp {
+            if !swp.trigger(period) {
+                self.ena = false;
+            }
+        }
+        if !dac(self.reg.nrx2.borrow().data()) {
+            self.ena = false;
+        }
+    }
+
+    /// Clocks the length timer.
+    pub fn length(&mut self) {
+        if self.len.clock() {
+            self.ena = false;
+        }
+    }
+
+    /// Clocks the period sweep.
+    pub fn sweep(&mut self) {
+        let Some(swp) = &mut self.swp else {
+            return;
+        };
+        match swp.clock() {
+            Some(Ok(period)) => self.set_period(period),
+            Some(Err(())) => self.ena = false,
+            None => (),
+        }
+    }
+
+    /// Clocks the volume envelope.
+    pub fn envelope(&mut self) {
+        if self.ena {
+            self.env.clock(self.reg.nrx2.borrow().data());
+        }
+    }
+
+    /// Resets the channel on power-off.
+    ///
+    /// # Note
+    ///
+    /// On DMG, the length counter is unaffected by power.
+    pub fn reset(&mut self) {
+        self.ena = false;
+        self.len.reset();
+        self.env = Envelope::default();
+        self.clk = 0;
+        self.pos = 0;
+        if let Some(swp) = &mut self.swp {
+            swp.reset();
+        }
+    }
+
+    /// Advances the channel by a single T-cycle.
+    pub fn cycle(&mut self) {
+        self.clk = self.clk.saturating_sub(1);
+        if self.clk == 0 {
+            self.clk = (2048 - self.period()) * 4;
+            self.pos = (self.pos + 1) % 8;
+        }
+    }
+}
+
+impl Snapshot for Pulse {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        if let Some(swp) = &self.swp {
+            swp.save(buf)?;
+        }
+        self.ena.save(buf)?;
+        self.len.save(buf)?;
+        self.env.save(buf)?;
+        self.clk.save(buf)?;
+        self.pos.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        if let Some(swp) = &mut self.swp {
+            swp.restore(buf)?;
+        }
+        self.ena.restore(buf)?;
+        self.len.restore(buf)?;
+        self.env.restore(buf)?;
+        self.clk.restore(buf)?;
+        self.pos.restore(buf)
+    }
+}
+
+/// Period sweep.
+///
+/// See more details [here][sweep].
+///
+/// [sweep]: https://gbdev.io/pandocs/Audio_Registers.html#ff10--nr10-channel-1-sweep
+#[derive(Debug)]
+pub struct Sweep {
+    /// Sweep register.
+    nrx0: Shared<Reg>,
+    /// Sweep enable.
+    ena: bool,
+    /// Shadow period.
+    reg: Word,
+    /// Sweep timer.
+    clk: Byte,
+    /// Subtraction used since trigger.
+    neg: bool,
+}
+
+impl Sweep {
+    /// Constructs a new `Sweep`.
+    fn new(nrx0: Shared<Reg>) -> Self {
+        Self {
+            nrx0,
+            ena: bool::default(),
+            reg: Word::default(),
+            clk: Byte::default(),
+            neg: bool::default(),
+        }
+    }
+
+    /// Gets the sweep pace.
+    fn pace(&self) -> Byte {
+        (self.nrx0.borrow().data() >> 4) & 0x07
+    }
+
+    /// Checks if the sweep direction is subtraction.
+    fn negate(&self) -> bool {
+        self.nrx0.borrow().data() & 0x08 != 0
+    }
+
+    /// Gets the sweep step.
+    fn step(&self) -> Byte {
+        self.nrx0.borrow().data() & 0x07
+    }
+
+    /// Reloads the sweep timer.
+    fn reload(&mut self) {
+        self.clk = match self.pace() {
+            0 => 8,
+            pace => pace,
+        };
+    }
+
+    /// Handles a write to `NRx0`.
+    ///
+    /// Returns whether the channel should be disabled, which occurs when the
+    /// direction is changed from subtraction after it has been used.
+    fn poll(&mut self) -> bool {
+        let nrx0 = self.nrx0.borrow_mut().poll();
+        nrx0.is_some() && self.neg && !self.negate()
+    }
+
+    /// Calculates the next period, returning `None` on overflow.
+    fn calc(&mut self) -> Option<Word> {
+        let delta = self.reg >> self.step();
+        let next = if self.negate() {
+            self.neg = true;
+            self.reg - delta
+        } else {
+            self.reg + delta
+        };
+        (next <= 0x7ff).then_some(next)
+    }
+
+    /// Reloads the sweep on channel trigger.
+    ///
+    /// Returns whether the channel remains enabled.
+    fn trigger(&mut self, period: Word) -> bool {
+        self.reg = period;
+        self.neg = false;
+        self.reload();
+        self.ena = self.pace() != 0 || self.step() != 0;
+        self.step() == 0 || self.calc().is_some()
+    }
+
+    /// Clocks the sweep.
+    ///
+    /// Returns the updated period, or an error when the channel should be
+    /// disabled due to overflow.
+    fn clock(&mut self) -> Option<Result<Word, ()>> {
+        self.clk = self.clk.saturating_sub(1);
+        if self.clk != 0 {
+            return None;
+        }
+        self.reload();
+        if !self.ena || self.pace() == 0 {
+            return None;
+        }
+        let Some(next) = self.calc() else {
+            return Some(Err(()));
+        };
+        if self.step() == 0 {
+            return None;
+        }
+        self.reg = next;
+        // Perform overflow check again using the new period
+        if self.calc().is_none() {
+            return Some(Err(()));
+        }
+        Some(Ok(next))
+    }
+
+    /// Resets the sweep on power-off.
+    fn reset(&mut self) {
+        self.ena = false;
+        self.reg = 0;
+        self.clk = 0;
+        self.neg = false;
+    }
+}
+
+impl Snapshot for Sweep {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.ena.save(buf)?;
+        self.reg.save(buf)?;
+        self.clk.save(buf)?;
+        self.neg.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.ena.restore(buf)?;
+        self.reg.restore(buf)?;
+        self.clk.restore(buf)?;
+        self.neg.restore(buf)
+    }
+}
@@ -0,0 +1,232 @@
+//! Wave channel.
+
+use std::io::{self, Read, Write};
+
+use log::trace;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Byte, Shared, Word};
+
+use super::super::reg::Reg;
+use super::super::Wave as Ram;
+use super::Length;
+
+/// Wave channel registers.
+#[derive(Debug)]
+pub struct Control {
+    /// DAC enable.
+    pub nrx0: Shared<Reg>,
+    /// Length timer.
+    pub nrx1: Shared<Reg>,
+    /// Output level.
+    pub nrx2: Shared<Reg>,
+    /// Period low.
+    pub nrx3: Shared<Reg>,
+    /// Period high & control.
+    pub nrx4: Shared<Reg>,
+}
+
+/// Wave channel.
+///
+/// Plays back arbitrary 4-bit samples stored in [wave RAM](Ram).
+#[derive(Debug)]
+pub struct Wave {
+    /// Channel registers.
+    reg: Control,
+    /// Wave RAM.
+    ram: Shared<Ram>,
+    /// Channel enable.
+    ena: bool,
+    /// Length timer.
+    len: Length,
+    /// Period divider.
+    clk: Word,
+    /// Sample index.
+    idx: usize,
+    /// Sample buffer.
+    buf: Byte,
+    /// Wave RAM access window.
+    hit: Byte,
+}
+
+impl Wave {
+    /// Duration (in T-cycles) after a sample is fetched during which wave RAM
+    /// remains accessible to the CPU.
+    const HIT: Byte = 2;
+
+    /// Additional delay (in T-cycles) before the first sample is fetched
+    /// after the channel is triggered.
+    const DELAY: Word = 6;
+
+    /// Constructs a new `Wave`.
+    #[must_use]
+    pub fn new(reg: Control, ram: Shared<Ram>) -> Self {
+        Self {
+            reg,
+            ram,
+            ena: bool::default(),
+            len: Length::new(256),
+            clk: Word::default(),
+            idx: usize::default(),
+            buf: Byte::default(),
+            hit: Byte::default(),
+        }
+    }
+
+    /// Checks if the channel is enabled.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.ena
+    }
+
+    /// Checks if the channel's DAC is enabled.
+    fn dac(&self) -> bool {
+        self.reg.nrx0.borrow().data() & 0x80 != 0
+    }
+
+    /// Gets the channel's digital output, if its DAC is enabled.
+    #[must_use]
+    pub fn output(&self) -> Option<Byte> {
+        let nrx2 = self.reg.nrx2.borrow().data();
+        self.dac().then(|| {
+            if !self.ena {
+                return 0;
+            }
+            let sample = if self.idx.is_multiple_of(2) {
+                self.buf >> 4
+            } else {
+                self.buf & 0x0f
+            };
+            match (nrx2 >> 5) & 0x03 {
+                0b00 => 0,
+                0b01 => sample,
+                0b10 => sample >> 1,
+                0b11 => sample >> 2,
+                _ => unreachable!(),
+            }
+        })
+    }
+
+    /// Gets the channel's period.
+    fn period(&self) -> Word {
+        let lo = self.reg.nrx3.borrow().data();
+        let hi = self.reg.nrx4.borrow().data() & 0x07;
+        Word::from_le_bytes([lo, hi])
+    }
+
+    /// Handles pending register writes.
+    pub fn poll(&mut self, half: bool) {
+        // NRx0
+        let nrx0 = self.reg.nrx0.borrow_mut().poll();
+        if nrx0.is_some() && !self.dac() {
+            self.ena = false;
+        }
+        // NRx1
+        if let Some(nrx1) = self.reg.nrx1.borrow_mut().poll() {
+            self.len.load(nrx1);
+        }
+        // NRx2
+        self.reg.nrx2.borrow_mut().poll();
+        // NRx4
+        let nrx4 = self.reg.nrx4.borrow_mut().poll();
+        if let Some(nrx4) = nrx4 {
+            if self.len.write(nrx4, half) {
+                self.ena = false;
+            }
+            if nrx4 & 0x80 != 0 {
+                self.trigger();
+            }
+        }
+        self.sync();
+    }
+
+    /// Triggers the channel.
+    fn trigger(&mut self) {
+        trace!("wave channel triggered");
+        // Retriggering just as a sample is being fetched corrupts wave RAM on
+        // DMG. When fetching from the first four bytes only the first byte is
+        // corrupted, otherwise the first four bytes are overwritten with the
+        // aligned block being fetched.
+        if self.ena && self.clk == 2 {
+            let idx = ((self.idx + 1) % 32) / 2;
+            let ram = &mut self.ram.borrow_mut().ram;
+            if idx < 4 {
+                ram[0] = ram[idx];
+            } else {
+                let base = idx & !0x03;
+                ram.copy_within(base..base + 4, 0);
+            }
+        }
+        self.ena = self.dac();
+        self.clk = (2048 - self.period()) * 2 + Self::DELAY;
+        self.idx = 0;
+        self.hit = 0;
+    }
+
+    /// Clocks the length timer.
+    pub fn length(&mut self) {
+        if self.len.clock() {
+            self.ena = false;
+            self.sync();
+        }
+    }
+
+    /// Resets the channel on power-off.
+    ///
+    /// # Note
+    ///
+    /// On DMG, the length counter is unaffected by power.
+    pub fn reset(&mut self) {
+        self.ena = false;
+        self.len.reset();
+        self.clk = 0;
+        self.idx = 0;
+        self.buf = 0;
+        self.hit = 0;
+        self.sync();
+    }
+
+    /// Advances the channel by a single T-cycle.
+    pub fn cycle(&mut self) {
+        self.hit = self.hit.saturating_sub(1);
+        if self.ena {
+            self.clk = self.clk.saturating_sub(1);
+            if self.clk == 0 {
+                self.clk = (2048 - self.period()) * 2;
+                // Fetch the next sample
+                self.idx = (self.idx + 1) % 32;
+                self.buf = self.ram.borrow().ram[self.idx / 2];
+                self.hit = Self::HIT;
+            }
+        }
+        self.sync();
+    }
+
+    /// Synchronizes wave RAM's access state with the channel.
+    fn sync(&self) {
+        let ram = &mut *self.ram.borrow_mut();
+        ram.ena = self.ena;
+        ram.idx = self.idx / 2;
+        ram.hit = self.hit != 0;
+    }
+}
+
+/// Wave RAM is saved separately, as it is owned by the APU's memory.
+impl Snapshot for Wave {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.ena.save(buf)?;
+        self.len.save(buf)?;
+        self.clk.save(buf)?;
+        self.idx.save(buf)?;
+        self.buf.save(buf)?;
+        self.hit.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.ena.restore(buf)?;
+        self.len.restore(buf)?;
+        self.clk.restore(buf)?;
+        self.idx.restore(buf)?;
+        self.buf.restore(buf)?;
+        self.hit.restore(buf)
+    }
+}
@@ -1,18 +1,95 @@
 //! Audio model.
 
-use rugby_arch::mem::Ram;
+use std::io::{self, Read, Write};
+
+use log::debug;
+use rugby_arch::mem::{Memory, Result};
 use rugby_arch::mio::{Bus, Mmio};
 use rugby_arch::reg::{Port, Register};
-use rugby_arch::{Block, Byte, Shared};
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use self::reg::{Nr52, Reg};
+use self::sample::Resampler;
+use super::timer::reg::Div;
+use crate::api::part::audio::{Audio as Api, Sample};
 
-use crate::api::part::audio::Audio as Api;
+mod chan;
+mod sample;
+
+pub mod reg;
 
 /// Wave RAM.
 ///
 /// 16 byte RAM used to store audio samples. See more details [here][wave].
 ///
+/// # Note
+///
+/// While the wave channel is enabled, any access to wave RAM is redirected to
+/// the byte currently being played. On DMG, such accesses only succeed within
+/// a brief window after the channel fetches a sample; at any other time, reads
+/// return `0xff` and writes are ignored.
+///
 /// [wave]: https://gbdev.io/pandocs/Audio_Registers.html#ff30ff3f--wave-pattern-ram
-pub type Wave = Ram<[Byte; 0x0010]>;
+#[derive(Debug, Default)]
+pub struct Wave {
+    /// Sample data.
+    ram: [Byte; 0x0010],
+    /// Channel enable.
+    ena: bool,
+    /// Playing byte index.
+    idx: usize,
+    /// Access window open.
+    hit: bool,
+}
+
+impl Wave {
+    /// Gets the underlying sample data.
+    #[must_use]
+    pub fn inner(&self) -> &[Byte; 0x0010] {
+        &self.ram
+    }
+
+    /// Resolves the accessed byte, if currently accessible.
+    fn index(&self, addr: Word) -> Option<usize> {
+        if self.ena {
+            self.hit.then_some(self.idx)
+        } else {
+            Some(usize::from(addr))
+        }
+    }
+}
+
+impl Memory for Wave {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        self.ram.read(addr)?;
+        Ok(self.index(addr).map_or(0xff, |idx| self.ram[idx]))
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        self.ram.read(addr)?;
+        if let Some(idx) = self.index(addr) {
+            self.ram[idx] = data;
+        }
+        Ok(())
+    }
+}
+
+impl Snapshot for Wave {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.ram.save(buf)?;
+        self.ena.save(buf)?;
+        self.idx.save(buf)?;
+        self.hit.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.ram.restore(buf)?;
+        self.ena.restore(buf)?;
+        self.idx.restore(buf)?;
+        self.hit.restore(buf)
+    }
+}
 
 /// Audio register select.
 ///
@@ -66,23 +143,242 @@ pub enum Select {
 }
 
 /// Audio processing unit.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Apu {
     /// Audio registers.
     pub reg: Control,
     /// Audio memory.
     pub mem: Bank,
+    /// Audio internals.
+    etc: Internal,
+    /// Sound channels.
+    chx: Channels,
+    /// Sample output.
+    smp: Option<Resampler>,
+    /// Divider register.
+    div: Shared<Div>,
+}
+
+/// Audio internals.
+#[derive(Debug, Default)]
+struct Internal {
+    /// Power status.
+    pwr: bool,
+    /// Previous DIV-APU bit.
+    div: bool,
+    /// Frame sequencer step.
+    seq: u8,
+}
+
+impl Internal {
+    fn reset(&mut self) {
+        std::mem::take(self);
+    }
+}
+
+impl Snapshot for Internal {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.pwr.save(buf)?;
+        self.div.save(buf)?;
+        self.seq.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.pwr.restore(buf)?;
+        self.div.restore(buf)?;
+        self.seq.restore(buf)
+    }
+}
+
+/// Sound channels.
+#[derive(Debug)]
+struct Channels {
+    /// Channel 1: Pulse with period sweep.
+    ch1: chan::Pulse,
+    /// Channel 2: Pulse.
+    ch2: chan::Pulse,
+    /// Channel 3: Wave output.
+    ch3: chan::Wave,
+    /// Channel 4: Noise.
+    ch4: chan::Noise,
+}
+
+impl Snapshot for Channels {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.ch1.save(buf)?;
+        self.ch2.save(buf)?;
+        self.ch3.save(buf)?;
+        self.ch4.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.ch1.restore(buf)?;
+        self.ch2.restore(buf)?;
+        self.ch3.restore(buf)?;
+        self.ch4.restore(buf)
+    }
 }
 
 impl Apu {
     /// Constructs a new `Apu`.
+    ///
+    /// The frame sequencer is clocked by the timer's [divider](Div), which
+    /// must be supplied.
     #[must_use]
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(div: Shared<Div>) -> Self {
+        let reg = Control::new();
+        let mem = Bank::default();
+        let chx = Channels {
+            ch1: chan::Pulse::new(
+                chan::pulse::Control {
+                    nrx1: reg.nr11.clone(),
+                    nrx2: reg.nr12.clone(),
+                    nrx3: reg.nr13.clone(),
+                    nrx4: reg.nr14.clone(),
+                },
+                Some(reg.nr10.clone()),
+            ),
+            ch2: chan::Pulse::new(
+                chan::pulse::Control {
+                    nrx1: reg.nr21.clone(),
+                    nrx2: reg.nr22.clone(),
+                    nrx3: reg.nr23.clone(),
+                    nrx4: reg.nr24.clone(),
+                },
+                None,
+            ),
+            ch3: chan::Wave::new(
+                chan::wave::Control {
+                    nrx0: reg.nr30.clone(),
+                    nrx1: reg.nr31.clone(),
+                    nrx2: reg.nr32.clone(),
+                    nrx3: reg.nr33.clone(),
+                    nrx4: reg.nr34.clone(),
+                },
+                mem.wave.clone(),
+            ),
+            ch4: chan::Noise::new(chan::noise::Control {
+                nrx1: reg.nr41.clone(),
+                nrx2: reg.nr42.clone(),
+                nrx3: reg.nr43.clone(),
+                nrx4: reg.nr44.clone(),
+            }),
+        };
+        Self {
+            reg,
+            mem,
+            etc: Internal::default(),
+            chx,
+            smp: None,
+            div,
+        }
+    }
+
+    /// Checks if the APU is powered on.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.reg.nr52.borrow().enabled()
+    }
+
+    /// Samples the current stereo output.
+    ///
+    /// Each channel's digital output is converted by its DAC, then panned
+    /// according to NR51 and scaled by the master volume in NR50. Returns the
+    /// left and right amplitudes, each normalized to `-1.0..=1.0`.
+    #[must_use]
+    pub fn output(&self) -> (f32, f32) {
+        let Channels { ch1, ch2, ch3, ch4 } = &self.chx;
+        let nr50 = self.reg.nr50.borrow().data();
+        let nr51 = self.reg.nr51.borrow().data();
+        // Convert each channel's output
+        let dac = [ch1.output(), ch2.output(), ch3.output(), ch4.output()]
+            .map(|out| out.map_or(0., |out| 1. - f32::from(out) / 7.5));
+        // Mix channels according to panning
+        let mix = |pan: Byte| -> f32 {
+            dac.iter()
+                .enumerate()
+                .filter(|&(idx, _)| pan & (1 << idx) != 0)
+                .map(|(_, amp)| amp)
+                .sum::<f32>()
+                / 4.
+        };
+        let lt = mix(nr51 >> 4);
+        let rt = mix(nr51 & 0x0f);
+        // Scale by master volume
+        let lv = f32::from(((nr50 >> 4) & 0x07) + 1) / 8.;
+        let rv = f32::from((nr50 & 0x07) + 1) / 8.;
+        (lt * lv, rt * rv)
+    }
+
+    /// Powers on the APU.
+    fn power_on(&mut self) {
+        debug!("audio powered on");
+        // The frame sequencer is reset such that the next step is 0.
+        self.etc.seq = 0;
+    }
+
+    /// Powers off the APU.
+    ///
+    /// All registers, excluding wave RAM and the length timers, are cleared.
+    fn power_off(&mut self) {
+        debug!("audio powered off");
+        self.reg.clear();
+        let Channels { ch1, ch2, ch3, ch4 } = &mut self.chx;
+        ch1.reset();
+        ch2.reset();
+        ch3.reset();
+        ch4.reset();
+    }
+
+    /// Advances the frame sequencer.
+    ///
+    /// | Step | Length | Sweep | Envelope |
+    /// |:----:|:------:|:-----:|:--------:|
+    /// |  0   |   ✓    |       |          |
+    /// |  1   |        |       |          |
+    /// |  2   |   ✓    |   ✓   |          |
+    /// |  3   |        |       |          |
+    /// |  4   |   ✓    |       |          |
+    /// |  5   |        |       |          |
+    /// |  6   |   ✓    |   ✓   |          |
+    /// |  7   |        |       |    ✓     |
+    fn step(&mut self) {
+        let Channels { ch1, ch2, ch3, ch4 } = &mut self.chx;
+        let step = self.etc.seq;
+        self.etc.seq = (step + 1) % 8;
+        // Length: 256 Hz
+        if step.is_multiple_of(2) {
+            ch1.length();
+            ch2.length();
+            ch3.length();
+            ch4.length();
+        }
+        // Sweep: 128 Hz
+        if step % 4 == 2 {
+            ch1.sweep();
+        }
+        // Envelope: 64 Hz
+        if step == 7 {
+            ch1.envelope();
+            ch2.envelope();
+            ch4.envelope();
+        }
     }
 }
 
-impl Api for Apu {}
+impl Api for Apu {
+    fn rate(&self) -> Option<u32> {
+        self.smp.as_ref().map(Resampler::rate)
+    }
+
+    fn set_rate(&mut self, rate: Option<u32>) {
+        self.smp = rate.map(Resampler::new);
+    }
+
+    fn drain(&mut self) -> Vec<Sample> {
+        self.smp.as_mut().map(Resampler::drain).unwrap_or_default()
+    }
+}
 
 impl Block for Apu {
     fn ready(&self) -> bool {
@@ -90,11 +386,69 @@ impl Block for Apu {
     }
 
     fn cycle(&mut self) {
-        todo!()
+        // Handle power transitions
+        let pwr = self.enabled();
+        if pwr != self.etc.pwr {
+            if pwr {
+                self.power_on();
+            } else {
+                self.power_off();
+            }
+            self.etc.pwr = pwr;
+        }
+
+        // Handle register writes
+        //
+        // Performed even when powered off, as the length timers remain
+        // writable.
+        let half = self.etc.seq % 2 == 1;
+        let Channels { ch1, ch2, ch3, ch4 } = &mut self.chx;
+        ch1.poll(half);
+        ch2.poll(half);
+        ch3.poll(half);
+        ch4.poll(half);
+
+        // Clock the frame sequencer
+        //
+        // Uses the falling edge of DIV's bit 4 (bit 12 internally), resulting
+        // in a frequency of 512 Hz.
+        let div = self.div.borrow().div() & 0x1000 != 0;
+        let tick = self.etc.div && !div;
+        self.etc.div = div;
+        if pwr {
+            if tick {
+                self.step();
+            }
+            // Advance each channel
+            let Channels { ch1, ch2, ch3, ch4 } = &mut self.chx;
+            ch1.cycle();
+            ch2.cycle();
+            ch3.cycle();
+            ch4.cycle();
+        }
+
+        // Update channel status
+        let Channels { ch1, ch2, ch3, ch4 } = &self.chx;
+        let chs = [ch1.enabled(), ch2.enabled(), ch3.enabled(), ch4.enabled()]
+            .into_iter()
+            .enumerate()
+            .fold(0, |chs, (idx, ena)| chs | Byte::from(ena) << idx);
+        self.reg.nr52.borrow_mut().chs = chs;
+
+        // Sample audio output
+        if self.smp.is_some() {
+            let out = self.output();
+            if let Some(smp) = &mut self.smp {
+                smp.push(out);
+            }
+        }
     }
 
     fn reset(&mut self) {
         self.reg.reset();
+        self.etc.reset();
+        self.power_off();
+        self.set_rate(self.rate());
     }
 }
 
@@ -105,6 +459,23 @@ impl Mmio for Apu {
     }
 }
 
+/// Sample output is not saved, as it is configured by the frontend.
+impl Snapshot for Apu {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.reg.save(buf)?;
+        self.mem.save(buf)?;
+        self.etc.save(buf)?;
+        self.chx.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.reg.restore(buf)?;
+        self.mem.restore(buf)?;
+        self.etc.restore(buf)?;
+        self.chx.restore(buf)
+    }
+}
+
 impl Port<Byte> for Apu {
     type Select = Select;
 
@@ -163,6 +534,8 @@ impl Port<Byte> for Apu {
 
 /// Audio registers.
 ///
+/// See more details [here][regs].
+///
 /// | Address | Size | Name | Description                   |
 /// |:-------:|------|------|-------------------------------|
 /// | `$FF26` | Byte | NR52 | Audio master control          |
@@ -188,80 +561,129 @@ impl Port<Byte> for Apu {
 /// | `$FF23` | Byte | NR44 | CH4 control                   |
 ///
 /// [regs]: https://gbdev.io/pandocs/Audio_Registers.html
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Control {
     /// Audio master control.
-    pub nr52: Shared<Byte>,
+    pub nr52: Shared<Nr52>,
     /// Sound panning.
-    pub nr51: Shared<Byte>,
+    pub nr51: Shared<Reg>,
     /// Master volume & VIN panning.
-    pub nr50: Shared<Byte>,
+    pub nr50: Shared<Reg>,
     /// CH1 period sweep.
-    pub nr10: Shared<Byte>,
+    pub nr10: Shared<Reg>,
     /// CH1 length timer & duty cycle.
-    pub nr11: Shared<Byte>,
+    pub nr11: Shared<Reg>,
     /// CH1 volume & envelope.
-    pub nr12: Shared<Byte>,
+    pub nr12: Shared<Reg>,
     /// CH1 period low.
-    pub nr13: Shared<Byte>,
+    pub nr13: Shared<Reg>,
     /// CH1 period high & control.
-    pub nr14: Shared<Byte>,
+    pub nr14: Shared<Reg>,
     /// CH2 length timer & duty cycle.
-    pub nr21: Shared<Byte>,
+    pub nr21: Shared<Reg>,
     /// CH2 volume & envelope.
-    pub nr22: Shared<Byte>,
+    pub nr22: Shared<Reg>,
     /// CH2 period low.
-    pub nr23: Shared<Byte>,
+    pub nr23: Shared<Reg>,
     /// CH2 period high & control.
-    pub nr24: Shared<Byte>,
+    pub nr24: Shared<Reg>,
     /// CH3 DAC enable.
-    pub nr30: Shared<Byte>,
+    pub nr30: Shared<Reg>,
     /// CH3 length timer.
-    pub nr31: Shared<Byte>,
+    pub nr31: Shared<Reg>,
     /// CH3 output level.
-    pub nr32: Shared<Byte>,
+    pub nr32: Shared<Reg>,
     /// CH3 period low.
-    pub nr33: Shared<Byte>,
+    pub nr33: Shared<Reg>,
     /// CH3 period high & control.
-    pub nr34: Shared<Byte>,
+    pub nr34: Shared<Reg>,
     /// CH4 length timer.
-    pub nr41: Shared<Byte>,
+    pub nr41: Shared<Reg>,
     /// CH4 volume & envelope.
-    pub nr42: Shared<Byte>,
+    pub nr42: Shared<Reg>,
     /// CH4 frequency & randomness.
-    pub nr43: Shared<Byte>,
+    pub nr43: Shared<Reg>,
     /// CH4 control.
-    pub nr44: Shared<Byte>,
+    pub nr44: Shared<Reg>,
 }
 
-impl Block for Control {
-    fn reset(&mut self) {
+impl Control {
+    /// Constructs a new `Control`.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn new() -> Self {
+        let nr52 = Shared::<Nr52>::default();
+        let reg = |mask, keep| Shared::new(Reg::new(&nr52, mask, keep));
+        Self {
+            // Global Control Registers
+            nr51: reg(0x00, 0x00),
+            nr50: reg(0x00, 0x00),
+            // Sound Channel 1 — Pulse with wavelength sweep
+            nr10: reg(0x80, 0x00),
+            nr11: reg(0x3f, 0x3f),
+            nr12: reg(0x00, 0x00),
+            nr13: reg(0xff, 0x00),
+            nr14: reg(0xbf, 0x00),
+            // Sound Channel 2 — Pulse
+            nr21: reg(0x3f, 0x3f),
+            nr22: reg(0x00, 0x00),
+            nr23: reg(0xff, 0x00),
+            nr24: reg(0xbf, 0x00),
+            // Sound Channel 3 — Wave output
+            nr30: reg(0x7f, 0x00),
+            nr31: reg(0xff, 0xff),
+            nr32: reg(0x9f, 0x00),
+            nr33: reg(0xff, 0x00),
+            nr34: reg(0xbf, 0x00),
+            // Sound Channel 4 — Noise
+            nr41: reg(0xff, 0x3f),
+            nr42: reg(0x00, 0x00),
+            nr43: reg(0x00, 0x00),
+            nr44: reg(0xbf, 0x00),
+            nr52,
+        }
+    }
+
+    /// Clears all sound registers, excluding NR52.
+    fn clear(&self) {
         // Global Control Registers
-        self.nr52.take();
-        self.nr51.take();
-        self.nr50.take();
+        self.nr51.borrow_mut().clear();
+        self.nr50.borrow_mut().clear();
         // Sound Channel 1 — Pulse with wavelength sweep
-        self.nr10.take();
-        self.nr11.take();
-        self.nr12.take();
-        self.nr13.take();
-        self.nr14.take();
+        self.nr10.borrow_mut().clear();
+        self.nr11.borrow_mut().clear();
+        self.nr12.borrow_mut().clear();
+        self.nr13.borrow_mut().clear();
+        self.nr14.borrow_mut().clear();
         // Sound Channel 2 — Pulse
-        self.nr21.take();
-        self.nr22.take();
-        self.nr23.take();
-        self.nr24.take();
+        self.nr21.borrow_mut().clear();
+        self.nr22.borrow_mut().clear();
+        self.nr23.borrow_mut().clear();
+        self.nr24.borrow_mut().clear();
         // Sound Channel 3 — Wave output
-        self.nr30.take();
-        self.nr31.take();
-        self.nr32.take();
-        self.nr33.take();
-        self.nr34.take();
+        self.nr30.borrow_mut().clear();
+        self.nr31.borrow_mut().clear();
+        self.nr32.borrow_mut().clear();
+        self.nr33.borrow_mut().clear();
+        self.nr34.borrow_mut().clear();
         // Sound Channel 4 — Noise
-        self.nr41.take();
-        self.nr42.take();
-        self.nr43.take();
-        self.nr44.take();
+        self.nr41.borrow_mut().clear();
+        self.nr42.borrow_mut().clear();
+        self.nr43.borrow_mut().clear();
+        self.nr44.borrow_mut().clear();
+    }
+}
+
+impl Default for Control {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        self.nr52.take();
+        self.clear();
     }
 }
 
@@ -296,6 +718,56 @@ impl Mmio for Control {
     }
 }
 
+impl Snapshot for Control {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.nr52.save(buf)?;
+        self.nr51.save(buf)?;
+        self.nr50.save(buf)?;
+        self.nr10.save(buf)?;
+        self.nr11.save(buf)?;
+        self.nr12.save(buf)?;
+        self.nr13.save(buf)?;
+        self.nr14.save(buf)?;
+        self.nr21.save(buf)?;
+        self.nr22.save(buf)?;
+        self.nr23.save(buf)?;
+        self.nr24.save(buf)?;
+        self.nr30.save(buf)?;
+        self.nr31.save(buf)?;
+        self.nr32.save(buf)?;
+        self.nr33.save(buf)?;
+        self.nr34.save(buf)?;
+        self.nr41.save(buf)?;
+        self.nr42.save(buf)?;
+        self.nr43.save(buf)?;
+        self.nr44.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.nr52.restore(buf)?;
+        self.nr51.restore(buf)?;
+        self.nr50.restore(buf)?;
+        self.nr10.restore(buf)?;
+        self.nr11.restore(buf)?;
+        self.nr12.restore(buf)?;
+        self.nr13.restore(buf)?;
+        self.nr14.restore(buf)?;
+        self.nr21.restore(buf)?;
+        self.nr22.restore(buf)?;
+        self.nr23.restore(buf)?;
+        self.nr24.restore(buf)?;
+        self.nr30.restore(buf)?;
+        self.nr31.restore(buf)?;
+        self.nr32.restore(buf)?;
+        self.nr33.restore(buf)?;
+        self.nr34.restore(buf)?;
+        self.nr41.restore(buf)?;
+        self.nr42.restore(buf)?;
+        self.nr43.restore(buf)?;
+        self.nr44.restore(buf)
+    }
+}
+
 /// Audio memory.
 ///
 /// |     Address     | Size | Name | Description |
@@ -311,6 +783,125 @@ impl Block for Bank {}
 
 impl Mmio for Bank {
     fn attach(&self, bus: &mut Bus) {
-        bus.map(0xff30..=0xff40, self.wave.clone().into());
+        bus.map(0xff30..=0xff3f, self.wave.clone().into());
+    }
+}
+
+impl Snapshot for Bank {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.wave.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.wave.restore(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parts::pic::Pic;
+    use crate::parts::timer::Timer;
+
+    fn setup() -> Apu {
+        let mut apu = Apu::new(Shared::default());
+        apu.reg.nr52.store(0x80);
+        apu.cycle();
+        apu
+    }
+
+    #[test]
+    fn register_masks_work() {
+        let mut apu = setup();
+        #[rustfmt::skip]
+        let regs = [
+            (Select::Nr10, 0x80), (Select::Nr11, 0x3f), (Select::Nr12, 0x00),
+            (Select::Nr13, 0xff), (Select::Nr14, 0xbf), (Select::Nr21, 0x3f),
+            (Select::Nr22, 0x00), (Select::Nr23, 0xff), (Select::Nr24, 0xbf),
+            (Select::Nr30, 0x7f), (Select::Nr31, 0xff), (Select::Nr32, 0x9f),
+            (Select::Nr33, 0xff), (Select::Nr34, 0xbf), (Select::Nr41, 0xff),
+            (Select::Nr42, 0x00), (Select::Nr43, 0x00), (Select::Nr44, 0xbf),
+            (Select::Nr50, 0x00), (Select::Nr51, 0x00), (Select::Nr52, 0x70),
+        ];
+        for (reg, mask) in regs {
+            if !matches!(reg, Select::Nr52) {
+                apu.store(reg, 0x00);
+            }
+            assert_eq!(apu.load(reg) & mask, mask, "{reg:?}");
+        }
+    }
+
+    #[test]
+    fn power_off_works() {
+        let mut apu = setup();
+        apu.store(Select::Nr50, 0x77);
+        apu.store(Select::Nr12, 0xf0);
+        apu.store(Select::Nr14, 0x80);
+        apu.cycle();
+        assert_eq!(apu.load(Select::Nr52), 0xf1);
+        // Power off
+        apu.store(Select::Nr52, 0x00);
+        apu.cycle();
+        assert_eq!(apu.load(Select::Nr52), 0x70);
+        assert_eq!(apu.load(Select::Nr50), 0x00);
+        assert_eq!(apu.load(Select::Nr12), 0x00);
+        // Writes are ignored
+        apu.store(Select::Nr50, 0x77);
+        assert_eq!(apu.load(Select::Nr50), 0x00);
+    }
+
+    #[test]
+    fn length_timer_works() {
+        let mut tma = Timer::new(Pic::new().line);
+        let mut apu = Apu::new(tma.reg.div.clone());
+        apu.reg.nr52.store(0x80);
+        apu.cycle();
+        // Trigger CH2 with a length of 1
+        apu.store(Select::Nr21, 0x3f);
+        apu.store(Select::Nr22, 0xf0);
+        apu.store(Select::Nr24, 0xc0);
+        apu.cycle();
+        assert_eq!(apu.load(Select::Nr52) & 0x02, 0x02);
+        // Clock the frame sequencer
+        for _ in 0..0x2000 {
+            tma.cycle();
+            apu.cycle();
+        }
+        assert_eq!(apu.load(Select::Nr52) & 0x02, 0x00);
+    }
+
+    #[test]
+    fn sample_rate_works() {
+        let mut apu = setup();
+        // Sampling is disabled by default
+        apu.cycle();
+        assert!(apu.drain().is_empty());
+        // Sample a single second of output
+        apu.set_rate(Some(48_000));
+        for _ in 0..crate::dmg::FREQ {
+            apu.cycle();
+        }
+        let out = apu.drain();
+        assert_eq!(out.len(), 48_000);
+        assert!(out
+            .iter()
+            .all(|smp| smp.lt.abs() <= 1. && smp.rt.abs() <= 1.));
+        // Samples are drained
+        assert!(apu.drain().is_empty());
+    }
+
+    #[test]
+    fn sample_rate_clamps() {
+        let mut apu = setup();
+        // Rates are clamped to those supported
+        apu.set_rate(Some(0));
+        assert_eq!(apu.rate(), Some(1));
+        apu.set_rate(Some(u32::MAX));
+        assert_eq!(apu.rate(), Some(crate::dmg::FREQ / 4));
+        // Sampling still works at the limit
+        for _ in 0..0x100 {
+            apu.cycle();
+        }
+        assert_eq!(apu.drain().len(), 0x100 / 4);
     }
 }
@@ -0,0 +1,169 @@
+//! Audio register models.
+
+use std::io::{self, Read, Write};
+
+use rugby_arch::mem::Memory;
+use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Byte, Shared, Word};
+
+/// Audio master control.
+///
+/// | Bit | Name              | Use |
+/// |-----|-------------------|-----|
+/// |  7  | Audio on/off      | R/W |
+/// |  3  | CH4 on?           | R   |
+/// |  2  | CH3 on?           | R   |
+/// |  1  | CH2 on?           | R   |
+/// |  0  | CH1 on?           | R   |
+#[derive(Debug, Default)]
+pub struct Nr52 {
+    /// Audio enable.
+    pub(super) ena: bool,
+    /// Channel status.
+    pub(super) chs: Byte,
+}
+
+impl Nr52 {
+    /// Checks if the APU is powered on.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.ena
+    }
+}
+
+impl Memory for Nr52 {
+    fn read(&self, _: Word) -> rugby_arch::mem::Result<Byte> {
+        Ok(self.load())
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> rugby_arch::mem::Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Nr52 {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        0x70 | Byte::from(self.ena) << 7 | self.chs & 0x0f
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.ena = value & 0x80 != 0;
+    }
+}
+
+impl Snapshot for Nr52 {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.ena.save(buf)?;
+        self.chs.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.ena.restore(buf)?;
+        self.chs.restore(buf)
+    }
+}
+
+/// Audio channel register.
+///
+/// Models the read-back and power gating behaviour shared by each of the sound
+/// registers. While the APU is powered off, writes are ignored, except to bits
+/// which remain writable (the length timers on DMG).
+#[derive(Debug)]
+pub struct Reg {
+    /// Register data.
+    data: Byte,
+    /// Unreadable bits.
+    mask: Byte,
+    /// Bits writable while off.
+    keep: Byte,
+    /// Pending write.
+    poll: bool,
+    /// Master control.
+    pwr: Shared<Nr52>,
+}
+
+impl Reg {
+    /// Constructs a new `Reg`.
+    #[must_use]
+    pub(super) fn new(pwr: &Shared<Nr52>, mask: Byte, keep: Byte) -> Self {
+        Self {
+            data: Byte::default(),
+            mask,
+            keep,
+            poll: bool::default(),
+            pwr: pwr.clone(),
+        }
+    }
+
+    /// Gets the underlying register data.
+    #[must_use]
+    pub(super) fn data(&self) -> Byte {
+        self.data
+    }
+
+    /// Sets the underlying register data.
+    ///
+    /// Unlike a store, this has no side effects.
+    pub(super) fn set(&mut self, data: Byte) {
+        self.data = data;
+    }
+
+    /// Polls for a pending write, returning the written value.
+    pub(super) fn poll(&mut self) -> Option<Byte> {
+        std::mem::take(&mut self.poll).then_some(self.data)
+    }
+
+    /// Clears the register.
+    pub(super) fn clear(&mut self) {
+        self.data = 0;
+        self.poll = false;
+    }
+}
+
+impl Memory for Reg {
+    fn read(&self, _: Word) -> rugby_arch::mem::Result<Byte> {
+        Ok(self.load())
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> rugby_arch::mem::Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Reg {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.data | self.mask
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        if self.pwr.borrow().enabled() {
+            self.data = value;
+        } else if self.keep != 0 {
+            self.data = self.data & !self.keep | value & self.keep;
+        } else {
+            return;
+        }
+        self.poll = true;
+    }
+}
+
+/// Only the register's contents are saved, as its masks are fixed and its
+/// master control is saved separately.
+impl Snapshot for Reg {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.data.save(buf)?;
+        self.poll.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.data.restore(buf)?;
+        self.poll.restore(buf)
+    }
+}
@@ -0,0 +1,166 @@
+//! Audio resampling.
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+use crate::api::part::audio::Sample;
+use crate::dmg::FREQ;
+
+/// Oversampling factor.
+///
+/// Samples are first box-filtered down to this multiple of the output rate,
+/// then decimated by the low-pass filter.
+const OVER: u32 = 4;
+
+/// Low-pass filter length.
+const TAPS: u32 = 48;
+
+/// High-pass filter charge factor.
+///
+/// Models the capacitor on the audio output, which slowly removes any DC
+/// offset from the signal. See more details [here][hpf].
+///
+/// [hpf]: https://gbdev.io/pandocs/Audio_details.html#mixer
+const CHARGE: f64 = 0.999_958;
+
+/// Band-limited resampler.
+///
+/// Converts the APU's output, sampled every T-cycle, into PCM samples at an
+/// arbitrary output rate. Resampling is performed in two stages:
+///
+/// 1. Each T-cycle's output is integrated over an intermediate period of
+///    [`OVER`] times the output rate. Cycles straddling a period boundary are
+///    split proportionally between both periods.
+/// 2. The intermediate samples are decimated by a windowed-sinc low-pass
+///    filter, with its cutoff just below the output's Nyquist frequency.
+///
+/// Finally, a high-pass filter removes any DC offset.
+#[derive(Debug)]
+pub struct Resampler {
+    /// Output sample rate.
+    rate: u32,
+    /// Integrator phase.
+    phase: u32,
+    /// Integrator sum.
+    sum: (f64, f64),
+    /// Intermediate samples.
+    hist: VecDeque<(f64, f64)>,
+    /// Decimation counter.
+    skip: u32,
+    /// Low-pass filter kernel.
+    kern: Box<[f64]>,
+    /// High-pass filter factor.
+    hpf: f64,
+    /// High-pass filter state.
+    cap: (f64, f64),
+    /// Output samples.
+    out: Vec<Sample>,
+}
+
+impl Resampler {
+    /// Constructs a new `Resampler`.
+    ///
+    /// The rate is clamped such that the intermediate rate is nonzero and
+    /// does not exceed the clock frequency.
+    #[must_use]
+    pub fn new(rate: u32) -> Self {
+        let rate = rate.clamp(1, FREQ / OVER);
+        Self {
+            rate,
+            phase: u32::default(),
+            sum: <(f64, f64)>::default(),
+            hist: (0..TAPS).map(|_| (0., 0.)).collect(),
+            skip: u32::default(),
+            kern: Self::kernel(),
+            hpf: CHARGE.powf(f64::from(FREQ) / f64::from(rate)),
+            cap: <(f64, f64)>::default(),
+            out: Vec::new(),
+        }
+    }
+
+    /// Computes the low-pass filter kernel.
+    ///
+    /// Uses a Blackman-windowed sinc, normalized to unity gain.
+    fn kernel() -> Box<[f64]> {
+        // Cutoff relative to the intermediate rate
+        let fc = 0.45 / f64::from(OVER);
+        let mid = f64::from(TAPS - 1) / 2.;
+        let kern: Vec<f64> = (0..TAPS)
+            .map(|idx| {
+                let x = f64::from(idx) - mid;
+                let sinc = if x == 0. {
+                    2. * fc
+                } else {
+                    (2. * PI * fc * x).sin() / (PI * x)
+                };
+                let t = f64::from(idx) / f64::from(TAPS - 1);
+                let win = 0.42 - 0.5 * (2. * PI * t).cos() + 0.08 * (4. * PI * t).cos();
+                sinc * win
+            })
+            .collect();
+        let gain: f64 = kern.iter().sum();
+        kern.into_iter().map(|tap| tap / gain).collect()
+    }
+
+    /// Gets the output sample rate.
+    #[must_use]
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Pushes a single T-cycle's output into the resampler.
+    pub fn push(&mut self, (lt, rt): (f32, f32)) {
+        let (lt, rt) = (f64::from(lt), f64::from(rt));
+        let step = self.rate * OVER;
+        let next = self.phase + step;
+        if next < FREQ {
+            // Accumulate the entire cycle
+            let wt = f64::from(step);
+            self.sum.0 += lt * wt;
+            self.sum.1 += rt * wt;
+            self.phase = next;
+        } else {
+            // Split the cycle at the period boundary
+            let head = f64::from(FREQ - self.phase);
+            let tail = next - FREQ;
+            let norm = f64::from(FREQ);
+            self.emit((
+                (self.sum.0 + lt * head) / norm,
+                (self.sum.1 + rt * head) / norm,
+            ));
+            self.sum = (lt * f64::from(tail), rt * f64::from(tail));
+            self.phase = tail;
+        }
+    }
+
+    /// Emits an intermediate sample.
+    fn emit(&mut self, smp: (f64, f64)) {
+        self.hist.pop_front();
+        self.hist.push_back(smp);
+        // Decimate to the output rate
+        self.skip = (self.skip + 1) % OVER;
+        if self.skip != 0 {
+            return;
+        }
+        let (lt, rt) = self
+            .hist
+            .iter()
+            .zip(self.kern.iter())
+            .fold((0., 0.), |(lt, rt), (smp, tap)| {
+                (lt + smp.0 * tap, rt + smp.1 * tap)
+            });
+        // Remove DC offset
+        let out = (lt - self.cap.0, rt - self.cap.1);
+        self.cap = (lt - out.0 * self.hpf, rt - out.1 * self.hpf);
+        #[allow(clippy::cast_possible_truncation)]
+        self.out.push(Sample {
+            lt: out.0 as f32,
+            rt: out.1 as f32,
+        });
+    }
+
+    /// Drains all buffered output samples.
+    pub fn drain(&mut self) -> Vec<Sample> {
+        std::mem::take(&mut self.out)
+    }
+}
@@ -1,29 +1,34 @@
 //! Boot ROM.
 
 use std::fmt::Debug;
+use std::io::{self, Read, Write};
 
 use log::{debug, trace};
 use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::mio::{Bus, Mmio};
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared, Word};
 
 /// Boot ROM.
-pub type Boot = rugby_arch::mem::Rom<[Byte; 0x100]>;
+///
+/// Sized 256 B on the DMG, and 2304 B on the CGB. In either case the
+/// cartridge header (at `$0100..=$01FF`) remains visible while mapped.
+pub type Boot<const N: usize = 0x100> = rugby_arch::mem::Rom<[Byte; N]>;
 
 /// Boot mapper chip.
 #[derive(Clone, Debug)]
-pub struct Chip {
+pub struct Chip<const N: usize = 0x100> {
     /// Boot disable.
     pub reg: Shared<Control>,
     /// Boot bank.
-    pub mem: Shared<Bank>,
+    pub mem: Shared<Bank<N>>,
 }
 
-impl Chip {
+impl<const N: usize> Chip<N> {
     /// Constructs a new `Rom`.
     #[must_use]
-    pub fn new(rom: Boot) -> Self {
+    pub fn new(rom: Boot<N>) -> Self {
         trace!(
             "BOOT:\n{rom}",
             rom = hexd::Printer::<Byte>::new(0, rom.inner())
@@ -36,15 +41,17 @@ impl Chip {
     }
 }
 
-impl Block for Chip {
+impl<const N: usize> Block for Chip<N> {
     fn reset(&mut self) {
         self.reg.reset();
     }
 }
 
-impl Mmio for Chip {
+impl<const N: usize> Mmio for Chip<N> {
     fn attach(&self, bus: &mut Bus) {
-        bus.map(0x0000..=0x00ff, self.mem.clone().into());
+        #[allow(clippy::cast_possible_truncation)]
+        let last = (N - 1) as Word;
+        bus.map(0x0000..=last, self.mem.clone().into());
         bus.map(0xff50..=0xff50, self.reg.clone().into());
     }
 }
@@ -67,6 +74,16 @@ impl Block for Control {
     }
 }
 
+impl Snapshot for Control {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.0.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.0.restore(buf)
+    }
+}
+
 impl Memory for Control {
     fn read(&self, _: Word) -> Result<Byte> {
         Ok(self.load())
@@ -101,29 +118,32 @@ impl Register for Control {
 }
 /// Boot memory bank.
 #[derive(Debug)]
-pub struct Bank {
+pub struct Bank<const N: usize = 0x100> {
     /// Boot ROM.
-    pub boot: Boot,
+    pub boot: Boot<N>,
     /// Boot disable.
     reg: Shared<Control>,
 }
 
-impl Bank {
+impl<const N: usize> Bank<N> {
     /// Constructs a new `Rom`.
-    fn new(reg: Shared<Control>, boot: Boot) -> Self {
+    fn new(reg: Shared<Control>, boot: Boot<N>) -> Self {
         Self { boot, reg }
     }
 }
 
-impl Block for Bank {
+impl<const N: usize> Block for Bank<N> {
     fn ready(&self) -> bool {
         self.reg.load() & 0x01 == 0
     }
 }
 
-impl Memory for Bank {
+impl<const N: usize> Memory for Bank<N> {
     fn read(&self, addr: Word) -> Result<Byte> {
-        if self.ready() {
+        if (0x0100..=0x01ff).contains(&addr) {
+            // Cartridge header is never overlaid
+            Err(Error::Range)
+        } else if self.ready() {
             self.boot.read(addr)
         } else {
             Err(Error::Busy)
@@ -131,7 +151,10 @@ impl Memory for Bank {
     }
 
     fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
-        if self.ready() {
+        if (0x0100..=0x01ff).contains(&addr) {
+            // Cartridge header is never overlaid
+            Err(Error::Range)
+        } else if self.ready() {
             self.boot.write(addr, data)
         } else {
             Err(Error::Busy)
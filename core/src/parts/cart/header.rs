@@ -78,6 +78,12 @@ pub struct Header {
     ///
     /// [title]: https://gbdev.io/pandocs/The_Cartridge_Header.html#0134-0143--title
     pub title: Option<String>,
+    /// `[$0134..=$0143]`: Title bytes.
+    ///
+    /// The raw contents of the title area, which may also contain the
+    /// manufacturer code and CGB flag. Used by the CGB boot ROM to identify
+    /// games for colorization.
+    pub ident: [Byte; 0x10],
     /// `[$0143]`: DMG flag.
     ///
     /// Whether this cartridge is compatible with the DMG model.
@@ -94,6 +100,15 @@ pub struct Header {
     ///
     /// [cgb]: https://gbdev.io/pandocs/The_Cartridge_Header.html#0143--cgb-flag
     pub cgb: bool,
+    /// `[$0144..=$0145]`, `[$014B]`: Licensee code.
+    ///
+    /// Whether the game was published by Nintendo, as indicated by either the
+    /// old or new licensee code.
+    ///
+    /// See more details [here][lic].
+    ///
+    /// [lic]: https://gbdev.io/pandocs/The_Cartridge_Header.html#014b--old-licensee-code
+    pub nintendo: bool,
     /// `[$0146]`: SGB flag.
     ///
     /// Whether this cartridge is compatible with the SGB model.
@@ -179,10 +194,14 @@ impl Header {
             logo: make::logo(head),
             // Parse title
             title: make::title(head)?,
+            // Copy title bytes
+            ident: make::ident(head),
             // Parse DMG flag
             dmg: make::dmg(head),
             // Parse CGB flag
             cgb: make::cgb(head)?,
+            // Parse licensee code
+            nintendo: make::nintendo(head),
             // Parse SGB flag
             sgb: make::sgb(head),
             // Parse cartridge type
@@ -239,10 +258,14 @@ impl Header {
             logo: make::logo(head),
             // Parse title
             title: make::title(head)?,
+            // Copy title bytes
+            ident: make::ident(head),
             // Parse DMG flag
             dmg: make::dmg(head),
             // Parse CGB flag
             cgb: make::cgb(head)?,
+            // Parse licensee code
+            nintendo: make::nintendo(head),
             // Parse SGB flag
             sgb: make::sgb(head),
             // Parse cartridge type
@@ -301,6 +324,8 @@ impl Header {
                 error!("{err} (default: {default:?})");
                 default
             }),
+            // Copy title bytes
+            ident: make::ident(head),
             // Parse DMG flag
             dmg: make::dmg(head),
             // Parse CGB flag
@@ -309,6 +334,8 @@ impl Header {
                 error!("{err} (default: {default:?})");
                 default
             }),
+            // Parse licensee code
+            nintendo: make::nintendo(head),
             // Parse SGB flag
             sgb: make::sgb(head),
             // Parse cartridge type
@@ -371,6 +398,11 @@ mod make {
         Ok(if title.is_empty() { None } else { Some(title) })
     }
 
+    /// Parse the `ident` field from the header.
+    pub fn ident(head: &[Byte; 0x50]) -> [Byte; 0x10] {
+        head[0x34..=0x43].try_into().unwrap()
+    }
+
     /// Parse the `dmg` field from the header.
     pub fn dmg(head: &[Byte; 0x50]) -> bool {
         (head[0x43] & 0xc0) != 0xc0
@@ -385,6 +417,15 @@ mod make {
         }
     }
 
+    /// Parse the `nintendo` field from the header.
+    pub fn nintendo(head: &[Byte; 0x50]) -> bool {
+        match head[0x4b] {
+            0x01 => true,
+            0x33 => head[0x44..=0x45] == *b"01",
+            _ => false,
+        }
+    }
+
     /// Parse the `sgb` field from the header.
     pub fn sgb(head: &[Byte; 0x50]) -> bool {
         match head[0x46] {
@@ -511,6 +552,9 @@ pub enum Info {
     HuC1,
     HuC3,
     Camera,
+    Sachen1,
+    Sachen2,
+    WisdomTree,
 }
 
 impl Info {
@@ -524,6 +568,7 @@ impl Info {
             | Info::Mbc3 { pwr, .. }
             | Info::Mbc5 { pwr, .. }
             | Info::Mmm01 { pwr, .. } => *pwr,
+            Info::Mbc7 | Info::HuC1 | Info::HuC3 | Info::Camera => true,
             _ => false,
         }
     }
@@ -537,6 +582,17 @@ impl Info {
             | Info::Mbc3 { ram, .. }
             | Info::Mbc5 { ram, .. }
             | Info::Mmm01 { ram, .. } => *ram,
+            Info::Mbc2 { .. } | Info::Mbc7 | Info::HuC1 | Info::HuC3 | Info::Camera => true,
+            _ => false,
+        }
+    }
+
+    /// Check if the cartridge has a real-time clock.
+    #[must_use]
+    pub fn has_rtc(&self) -> bool {
+        match self {
+            Info::Mbc3 { rtc, .. } => *rtc,
+            Info::HuC3 => true,
             _ => false,
         }
     }
@@ -558,6 +614,9 @@ impl Display for Info {
             Self::HuC1   { .. } => "HuC1",
             Self::HuC3   { .. } => "HuC3",
             Self::Camera { .. } => "Camera",
+            Self::Sachen1       => "Sachen MMC1",
+            Self::Sachen2       => "Sachen MMC2",
+            Self::WisdomTree    => "Wisdom Tree",
         }
         .fmt(f)
     }
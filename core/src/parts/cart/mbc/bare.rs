@@ -1,6 +1,7 @@
 use std::io;
 
 use rugby_arch::mio::Device;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Shared};
 
 use super::{Data, Mbc};
@@ -47,3 +48,13 @@ impl Mbc for Bare {
         buf.write(self.ram.borrow().inner())
     }
 }
+
+impl Snapshot for Bare {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ram.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ram.restore(buf)
+    }
+}
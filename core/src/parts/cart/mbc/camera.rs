@@ -0,0 +1,574 @@
+use std::fmt::Debug;
+use std::io;
+
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::Device;
+use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::{Data, Mbc};
+use crate::api::part::camera::{self, Source, HEIGHT, WIDTH};
+
+/// [Pocket Camera][camera] cartridge type.
+///
+/// Includes an image [sensor](Sensor), whose captures are processed and
+/// stored into RAM bank 0.
+///
+/// [camera]: https://gbdev.io/pandocs/Gameboy_Camera.html
+#[derive(Debug)]
+pub struct Camera {
+    ctl: Control,
+    rom: Shared<Rom>,
+    ram: Shared<Ram>,
+}
+
+impl Camera {
+    /// Constructs a new `Camera`.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let ctl = Control::default();
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::new(ctl.clone(), ram)),
+            ctl,
+        }
+    }
+
+    /// Gets the cartridge's image sensor.
+    #[must_use]
+    pub fn sensor(&self) -> &Sensor {
+        &self.ctl.sen
+    }
+}
+
+impl Block for Camera {
+    fn cycle(&mut self) {
+        // Wait for capture to complete
+        if !self.ctl.cap.borrow_mut().tick() {
+            return;
+        }
+        // Process captured image
+        let img = self.ctl.sen.capture();
+        let out = self.ctl.cap.borrow().process(&img);
+        // Store into RAM
+        let mem = &mut self.ram.borrow_mut().mem;
+        if let Some(dst) = mem.get_mut(Capture::IMAGE..Capture::IMAGE + out.len()) {
+            dst.copy_from_slice(&out);
+        }
+        debug!("completed capture");
+    }
+
+    fn reset(&mut self) {
+        self.ctl.reset();
+    }
+}
+
+impl Mbc for Camera {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        buf.read(&mut self.ram.borrow_mut().mem)
+    }
+
+    fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        buf.write(&self.ram.borrow().mem)
+    }
+}
+
+/// The image sensor is not saved, as its source is provided by the frontend.
+impl Snapshot for Camera {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.ena.save(buf)?;
+        self.ctl.rom.save(buf)?;
+        self.ctl.ram.save(buf)?;
+        self.ctl.cap.save(buf)?;
+        self.ram.borrow().mem.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.ena.restore(buf)?;
+        self.ctl.rom.restore(buf)?;
+        self.ctl.ram.restore(buf)?;
+        self.ctl.cap.restore(buf)?;
+        self.ram.borrow_mut().mem.restore(buf)
+    }
+}
+
+snapshot!(Enable, RomBank, RamBank);
+
+impl Snapshot for Capture {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.reg.save(buf)?;
+        self.busy.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.reg.restore(buf)?;
+        self.busy.restore(buf)
+    }
+}
+
+/// Pocket Camera registers.
+///
+/// |     Address     | Size | Name | Description      |
+/// |:---------------:|------|------|------------------|
+/// | `$0000..=$1FFF` | 1bit | ENA  | RAM Enable.      |
+/// | `$2000..=$3FFF` | 6bit | ROM  | ROM Bank Number. |
+/// | `$4000..=$5FFF` | 5bit | RAM  | RAM Bank Number. |
+///
+/// Selecting RAM bank `$10` maps the [capture](Capture) registers to
+/// `$A000..=$BFFF`.
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+struct Control {
+    /// RAM Enable.
+    ena: Shared<Enable>,
+    /// ROM Bank Number.
+    rom: Shared<RomBank>,
+    /// RAM Bank Number.
+    ram: Shared<RamBank>,
+    /// Capture registers.
+    cap: Shared<Capture>,
+    /// Image sensor.
+    sen: Sensor,
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        self.ena.take();
+        self.rom.take();
+        self.ram.take();
+        self.cap.take();
+    }
+}
+
+/// RAM Enable.
+#[derive(Debug, Default)]
+struct Enable(bool);
+
+impl Memory for Enable {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Enable {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        Byte::from(self.0)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = value & 0x0f == 0x0a;
+        debug!("RAM Enable: {}", self.0);
+    }
+}
+
+/// ROM Bank Number.
+#[derive(Debug, Default)]
+struct RomBank(Byte);
+
+impl Memory for RomBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RomBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x3f
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x3f & value;
+        debug!("ROM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// RAM Bank Number.
+#[derive(Debug, Default)]
+struct RamBank(Byte);
+
+impl RamBank {
+    /// Checks if the capture registers are selected.
+    fn regs(&self) -> bool {
+        self.0 & 0x10 != 0
+    }
+}
+
+impl Memory for RamBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RamBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x0f
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x1f & value;
+        debug!("RAM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// Capture registers.
+///
+/// Mirrored every 128 bytes. Only `$A000` can be read; all other registers
+/// read as `$00`.
+///
+/// |     Address     | Name | Description                        |
+/// |:---------------:|------|------------------------------------|
+/// | `$A000`         | CTL  | Capture start (bit 0), edge modes. |
+/// | `$A001`         | GAIN | Output gain, N (bit 7).            |
+/// | `$A002..=$A003` | EXP  | Exposure time (big-endian).        |
+/// | `$A004`         | EDGE | Edge enhancement, invert (bit 3).  |
+/// | `$A005`         | VREF | Output reference voltage.          |
+/// | `$A006..=$A035` | MTX  | Dithering and contrast matrix.     |
+///
+/// # Note
+///
+/// Of the analog controls, only exposure (affecting capture duration) and
+/// inversion are modelled; gain and edge enhancement are ignored.
+#[derive(Debug)]
+struct Capture {
+    /// Register values.
+    reg: [Byte; 0x36],
+    /// Remaining busy cycles.
+    busy: u32,
+}
+
+impl Default for Capture {
+    fn default() -> Self {
+        Self {
+            reg: [0; 0x36],
+            busy: 0,
+        }
+    }
+}
+
+impl Capture {
+    /// RAM offset of the processed image.
+    const IMAGE: usize = 0x0100;
+
+    /// Base address of the matrix registers.
+    const MATRIX: usize = 0x06;
+
+    /// Calculates the capture duration in (T-)cycles.
+    ///
+    /// Depends on the configured exposure time, as well as the N bit.
+    fn duration(&self) -> u32 {
+        let exp = u32::from(Word::from_be_bytes([self.reg[0x2], self.reg[0x3]]));
+        let nbit = self.reg[0x1] & 0x80 != 0;
+        4 * (32_446 + if nbit { 0 } else { 512 } + 16 * exp)
+    }
+
+    /// Ticks an in-progress capture, returning when it has completed.
+    fn tick(&mut self) -> bool {
+        if self.busy == 0 {
+            return false;
+        }
+        self.busy -= 1;
+        if self.busy > 0 {
+            return false;
+        }
+        self.reg[0x0] &= !0x01;
+        true
+    }
+
+    /// Processes a captured image into tile data.
+    ///
+    /// Each pixel is compared against its three thresholds from the 4×4
+    /// matrix to produce a 2-bit color, then stored as 16×14 tiles.
+    fn process(&self, img: &[u8]) -> Box<[Byte]> {
+        let inv = self.reg[0x4] & 0x08 != 0;
+        let mut out = vec![0; WIDTH * HEIGHT / 4].into_boxed_slice();
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                // Sample pixel
+                let mut pix = img.get(y * WIDTH + x).copied().unwrap_or_default();
+                if inv {
+                    pix = !pix;
+                }
+                // Apply matrix
+                let idx = Self::MATRIX + 3 * (4 * (y % 4) + x % 4);
+                let thr = &self.reg[idx..idx + 3];
+                let col = thr.iter().take_while(|&&thr| pix >= thr).count();
+                let col = 3 - u8::try_from(col).unwrap();
+                // Store as tile data
+                let addr = 16 * (WIDTH / 8 * (y / 8) + x / 8) + 2 * (y % 8);
+                let bit = 7 - x % 8;
+                out[addr] |= (col & 0b01) << bit;
+                out[addr + 1] |= (col >> 1) << bit;
+            }
+        }
+        out
+    }
+}
+
+impl Memory for Capture {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        Ok(match addr & 0x7f {
+            0x00 => self.reg[0x0] & 0x07,
+            _ => 0x00,
+        })
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        let idx = usize::from(addr & 0x7f);
+        match idx {
+            // Capture control
+            0x00 => {
+                self.reg[0x0] = data & 0x07;
+                if data & 0x01 == 0 {
+                    // Stop capture
+                    self.busy = 0;
+                } else if self.busy == 0 {
+                    // Start capture
+                    self.busy = self.duration();
+                    debug!("started capture: {} cycles", self.busy);
+                }
+            }
+            // Capture parameters
+            0x01..=0x35 => self.reg[idx] = data,
+            // Unmapped
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+/// Pocket Camera ROM.
+#[derive(Debug)]
+struct Rom {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = usize::from(self.ctl.rom.load());
+        let addr = usize::from(addr);
+        (bank << 14 | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        let index = match addr {
+            0x0000..=0x3fff => usize::from(addr),
+            0x4000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        trace!("Camera::write({addr:#06x}, {data:#04x})");
+        match addr {
+            // RAM Enable
+            0x0000..=0x1fff => {
+                // ctl.ena <- data[3:0] == 0xA
+                self.ctl.ena.store(data);
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // ctl.rom[5:0] <- data[5:0]
+                self.ctl.rom.store(data);
+            }
+            // RAM Bank Number
+            0x4000..=0x5fff => {
+                // ctl.ram[4:0] <- data[4:0]
+                self.ctl.ram.store(data);
+            }
+            // Unmapped
+            0x6000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// Pocket Camera RAM.
+///
+/// # Note
+///
+/// RAM can be read even while disabled, but reads as `$00` while a capture is
+/// in progress.
+#[derive(Debug)]
+struct Ram {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Ram {
+    /// Constructs a new `Ram`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = usize::from(self.ctl.ram.load());
+        let addr = usize::from(addr);
+        (bank << 13 | addr & 0x1fff) % self.mem.len().max(0x2000)
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        // Perform register read
+        if self.ctl.ram.borrow().regs() {
+            return self.ctl.cap.read(addr);
+        }
+        // Hide RAM while busy
+        if self.ctl.cap.borrow().busy > 0 {
+            return Ok(0x00);
+        }
+        // Perform adjusted read
+        let index = self.adjust(addr);
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        // Perform register write
+        if self.ctl.ram.borrow().regs() {
+            return self.ctl.cap.write(addr, data);
+        }
+        // Error when disabled
+        if self.ctl.ena.load() == 0 {
+            return Err(Error::Busy);
+        }
+        // Perform adjusted write
+        let index = self.adjust(addr);
+        *self.mem.get_mut(index).ok_or(Error::Range)? = data;
+        Ok(())
+    }
+}
+
+/// Image sensor.
+///
+/// Captures images from a connected [source](Source), as supplied through its
+/// [`Camera`](camera::Camera) interface. Without a source, captured images
+/// are black.
+#[derive(Clone, Default)]
+pub struct Sensor(Shared<Option<Box<dyn Source>>>);
+
+impl Sensor {
+    /// Disconnects the image source, if any.
+    pub fn take_source(&mut self) -> Option<Box<dyn Source>> {
+        self.0.borrow_mut().take()
+    }
+
+    /// Captures an image from the source.
+    fn capture(&self) -> Box<[u8]> {
+        self.0
+            .borrow_mut()
+            .as_mut()
+            .map_or_else(|| vec![0; WIDTH * HEIGHT].into(), |src| src.capture())
+    }
+}
+
+impl Debug for Sensor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sensor")
+            .field("src", &self.0.borrow().is_some())
+            .finish()
+    }
+}
+
+impl camera::Camera for Sensor {
+    fn set_source(&mut self, src: Box<dyn Source>) {
+        *self.0.borrow_mut() = Some(src);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::part::camera::Camera as _;
+
+    /// Horizontal gradient source.
+    struct Gradient;
+
+    impl Source for Gradient {
+        fn capture(&mut self) -> Box<[u8]> {
+            (0..WIDTH * HEIGHT)
+                .map(|idx| u8::try_from(2 * (idx % WIDTH)).unwrap())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn capture_works() {
+        let mut mbc = Camera::new(vec![0; 0x8000].into(), vec![0; 0x20000].into());
+        mbc.sensor().clone().set_source(Box::new(Gradient));
+        let mut rom = mbc.rom();
+        let mut ram = mbc.ram();
+        // Configure capture
+        rom.write(0x4000, 0x10).unwrap();
+        ram.write(0x0001, 0x80).unwrap(); // N bit
+        ram.write(0x0002, 0x00).unwrap();
+        ram.write(0x0003, 0x10).unwrap(); // exposure
+        for idx in 0..16 {
+            let base = 0x0006 + 3 * idx;
+            ram.write(base, 0x40).unwrap();
+            ram.write(base + 1, 0x80).unwrap();
+            ram.write(base + 2, 0xc0).unwrap();
+        }
+        // Start capture
+        ram.write(0x0000, 0x01).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0x01));
+        assert_eq!(ram.read(0x0001), Ok(0x00));
+        let time = 4 * (32_446 + 16 * 0x10);
+        for _ in 0..time {
+            mbc.cycle();
+        }
+        assert_eq!(ram.read(0x0080), Ok(0x00));
+        // Read processed image
+        rom.write(0x4000, 0x00).unwrap();
+        let row = |tile: Word| {
+            let addr = 0x0100 + 16 * tile;
+            (ram.read(addr).unwrap(), ram.read(addr + 1).unwrap())
+        };
+        assert_eq!(row(0), (0xff, 0xff)); // black
+        assert_eq!(row(4), (0x00, 0xff)); // dark gray
+        assert_eq!(row(8), (0xff, 0x00)); // light gray
+        assert_eq!(row(15), (0x00, 0x00)); // white
+    }
+}
@@ -0,0 +1,345 @@
+use std::io;
+
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::Device;
+use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::{Data, Mbc};
+
+/// [HuC1][huc1] cartridge type.
+///
+/// Hudson's MBC1-like mapper, which adds an infrared transceiver. As no other
+/// device can be linked, the receiver never detects any light.
+///
+/// [huc1]: https://gbdev.io/pandocs/HuC1.html
+#[derive(Debug)]
+pub struct HuC1 {
+    ctl: Control,
+    rom: Shared<Rom>,
+    ram: Shared<Ram>,
+}
+
+impl HuC1 {
+    /// Constructs a new `HuC1`.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let ctl = Control::default();
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::new(ctl.clone(), ram)),
+            ctl,
+        }
+    }
+}
+
+impl Block for HuC1 {
+    fn reset(&mut self) {
+        self.ctl.reset();
+    }
+}
+
+impl Mbc for HuC1 {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        buf.read(&mut self.ram.borrow_mut().mem)
+    }
+
+    fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        buf.write(&self.ram.borrow().mem)
+    }
+}
+
+impl Snapshot for HuC1 {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.sel.save(buf)?;
+        self.ctl.rom.save(buf)?;
+        self.ctl.ram.save(buf)?;
+        self.ctl.led.save(buf)?;
+        self.ram.borrow().mem.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.sel.restore(buf)?;
+        self.ctl.rom.restore(buf)?;
+        self.ctl.ram.restore(buf)?;
+        self.ctl.led.restore(buf)?;
+        self.ram.borrow_mut().mem.restore(buf)
+    }
+}
+
+snapshot!(Select, RomBank, RamBank, Led);
+
+/// HuC1 registers.
+///
+/// |     Address     | Size | Name | Description      |
+/// |:---------------:|------|------|------------------|
+/// | `$0000..=$1FFF` | 1bit | SEL  | RAM/IR Select.   |
+/// | `$2000..=$3FFF` | 6bit | ROM  | ROM Bank Number. |
+/// | `$4000..=$5FFF` | 2bit | RAM  | RAM Bank Number. |
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+struct Control {
+    /// RAM/IR Select.
+    sel: Shared<Select>,
+    /// ROM Bank Number.
+    rom: Shared<RomBank>,
+    /// RAM Bank Number.
+    ram: Shared<RamBank>,
+    /// IR Transmitter.
+    led: Shared<Led>,
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        self.sel.take();
+        self.rom.take();
+        self.ram.take();
+        self.led.take();
+    }
+}
+
+/// RAM/IR Select.
+///
+/// Unlike other cartridges, RAM need not be enabled before use.
+#[derive(Debug, Default)]
+struct Select(bool);
+
+impl Memory for Select {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Select {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        Byte::from(self.0)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = value & 0x0f == 0x0e;
+        debug!("IR Select: {}", self.0);
+    }
+}
+
+/// ROM Bank Number.
+#[derive(Debug, Default)]
+struct RomBank(Byte);
+
+impl Memory for RomBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RomBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x3f
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x3f & value;
+        debug!("ROM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// RAM Bank Number.
+#[derive(Debug, Default)]
+struct RamBank(Byte);
+
+impl Memory for RamBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RamBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x03
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x03 & value;
+        debug!("RAM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// IR Transmitter.
+#[derive(Debug, Default)]
+struct Led(bool);
+
+impl Register for Led {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        Byte::from(self.0)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = value & 0x01 != 0;
+        trace!("IR LED: {}", self.0);
+    }
+}
+
+/// HuC1 ROM.
+#[derive(Debug)]
+struct Rom {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = match usize::from(self.ctl.rom.load()) {
+            0 => 1,
+            x => x,
+        };
+        let addr = usize::from(addr);
+        (bank << 14 | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        let index = match addr {
+            0x0000..=0x3fff => usize::from(addr),
+            0x4000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        trace!("HuC1::write({addr:#06x}, {data:#04x})");
+        match addr {
+            // RAM/IR Select
+            0x0000..=0x1fff => {
+                // ctl.sel <- data[3:0] == 0xE
+                self.ctl.sel.store(data);
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // ctl.rom[5:0] <- data[5:0]
+                self.ctl.rom.store(data);
+            }
+            // RAM Bank Number
+            0x4000..=0x5fff => {
+                // ctl.ram[1:0] <- data[1:0]
+                self.ctl.ram.store(data);
+            }
+            // Unmapped
+            0x6000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// HuC1 RAM.
+#[derive(Debug)]
+struct Ram {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Ram {
+    /// Value read from the IR receiver when no light is detected.
+    const DARK: Byte = 0xc0;
+
+    /// Constructs a new `Ram`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = usize::from(self.ctl.ram.load());
+        let addr = usize::from(addr);
+        (bank << 13 | addr & 0x1fff) % self.mem.len().max(0x2000)
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        // Perform IR read
+        if self.ctl.sel.load() != 0 {
+            return Ok(Self::DARK);
+        }
+        // Perform adjusted read
+        let index = self.adjust(addr);
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        // Perform IR write
+        if self.ctl.sel.load() != 0 {
+            self.ctl.led.store(data);
+            return Ok(());
+        }
+        // Perform adjusted write
+        let index = self.adjust(addr);
+        *self.mem.get_mut(index).ok_or(Error::Range)? = data;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ir_select_works() {
+        let mbc = HuC1::new(vec![0; 0x8000].into(), vec![0; 0x8000].into());
+        let mut rom = mbc.rom();
+        let mut ram = mbc.ram();
+        // RAM is usable without being enabled
+        rom.write(0x4000, 0x02).unwrap();
+        ram.write(0x0000, 0x5a).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0x5a));
+        // IR reads as no light
+        rom.write(0x0000, 0x0e).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0xc0));
+        ram.write(0x0000, 0x01).unwrap();
+        // RAM is unaffected by IR writes
+        rom.write(0x0000, 0x00).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0x5a));
+        rom.write(0x4000, 0x00).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0x00));
+    }
+}
@@ -0,0 +1,833 @@
+use std::io;
+
+use log::{debug, trace, warn};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::Device;
+use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::rtc::{self, Source, Timer};
+use super::{Data, Mbc};
+use crate::api::part::speaker::{Event, Speaker};
+
+/// [HuC3][huc3] cartridge type.
+///
+/// Hudson's mapper with an infrared transceiver, a [real-time clock](Rtc), and
+/// a [piezo speaker](Piezo) driven by the clock's tone generator.
+///
+/// [huc3]: https://gbdev.io/pandocs/HuC3.html
+#[derive(Debug)]
+pub struct HuC3 {
+    ctl: Control,
+    rom: Shared<Rom>,
+    ram: Shared<Ram>,
+    spk: Piezo,
+}
+
+impl HuC3 {
+    /// Constructs a new `HuC3`.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let ctl = Control::default();
+        let spk = ctl.rtc.borrow().spk.clone();
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::new(ctl.clone(), ram)),
+            ctl,
+            spk,
+        }
+    }
+
+    /// Gets the cartridge's real-time clock.
+    #[must_use]
+    pub fn rtc(&self) -> &Shared<Rtc> {
+        &self.ctl.rtc
+    }
+
+    /// Gets the cartridge's piezo speaker.
+    #[must_use]
+    pub fn speaker(&self) -> &Piezo {
+        &self.spk
+    }
+}
+
+impl Block for HuC3 {
+    fn cycle(&mut self) {
+        self.ctl.rtc.borrow_mut().cycle();
+    }
+
+    fn reset(&mut self) {
+        self.ctl.reset();
+    }
+}
+
+impl Mbc for HuC3 {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        let mut nbytes = buf.read(&mut self.ram.borrow_mut().mem)?;
+        // Load clock data
+        nbytes += self.ctl.rtc.borrow_mut().flash(buf)?;
+        Ok(nbytes)
+    }
+
+    fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        let mut nbytes = buf.write(&self.ram.borrow().mem)?;
+        // Save clock data
+        nbytes += self.ctl.rtc.borrow_mut().dump(buf)?;
+        Ok(nbytes)
+    }
+}
+
+impl Snapshot for HuC3 {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.sel.save(buf)?;
+        self.ctl.rom.save(buf)?;
+        self.ctl.ram.save(buf)?;
+        self.ctl.rtc.save(buf)?;
+        self.ram.borrow().mem.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.sel.restore(buf)?;
+        self.ctl.rom.restore(buf)?;
+        self.ctl.ram.restore(buf)?;
+        self.ctl.rtc.restore(buf)?;
+        self.ram.borrow_mut().mem.restore(buf)
+    }
+}
+
+snapshot!(Select, RomBank, RamBank);
+
+/// HuC3 registers.
+///
+/// |     Address     | Size | Name | Description      |
+/// |:---------------:|------|------|------------------|
+/// | `$0000..=$1FFF` | 4bit | SEL  | Mode Select.     |
+/// | `$2000..=$3FFF` | 7bit | ROM  | ROM Bank Number. |
+/// | `$4000..=$5FFF` | 2bit | RAM  | RAM Bank Number. |
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+struct Control {
+    /// Mode Select.
+    sel: Shared<Select>,
+    /// ROM Bank Number.
+    rom: Shared<RomBank>,
+    /// RAM Bank Number.
+    ram: Shared<RamBank>,
+    /// Real-time clock.
+    rtc: Shared<Rtc>,
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        self.sel.take();
+        self.rom.take();
+        self.ram.take();
+    }
+}
+
+/// Mode Select.
+///
+/// Selects the device mapped to `$A000..=$BFFF`.
+///
+/// | Value | Mode                    |
+/// |:-----:|-------------------------|
+/// | `$0`  | RAM (read-only).        |
+/// | `$A`  | RAM (read/write).       |
+/// | `$B`  | RTC command (write).    |
+/// | `$C`  | RTC response (read).    |
+/// | `$D`  | RTC semaphore.          |
+/// | `$E`  | IR transceiver.         |
+#[derive(Debug, Default)]
+struct Select(Byte);
+
+impl Memory for Select {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Select {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x0f
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x0f & value;
+        debug!("Mode Select: {:#03x}", self.0);
+    }
+}
+
+/// ROM Bank Number.
+#[derive(Debug, Default)]
+struct RomBank(Byte);
+
+impl Memory for RomBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RomBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x7f
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x7f & value;
+        debug!("ROM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// RAM Bank Number.
+#[derive(Debug, Default)]
+struct RamBank(Byte);
+
+impl Memory for RamBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RamBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x03
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x03 & value;
+        debug!("RAM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// HuC3 ROM.
+#[derive(Debug)]
+struct Rom {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = match usize::from(self.ctl.rom.load()) {
+            0 => 1,
+            x => x,
+        };
+        let addr = usize::from(addr);
+        (bank << 14 | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        let index = match addr {
+            0x0000..=0x3fff => usize::from(addr),
+            0x4000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        trace!("HuC3::write({addr:#06x}, {data:#04x})");
+        match addr {
+            // Mode Select
+            0x0000..=0x1fff => {
+                // ctl.sel[3:0] <- data[3:0]
+                self.ctl.sel.store(data);
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // ctl.rom[6:0] <- data[6:0]
+                self.ctl.rom.store(data);
+            }
+            // RAM Bank Number
+            0x4000..=0x5fff => {
+                // ctl.ram[1:0] <- data[1:0]
+                self.ctl.ram.store(data);
+            }
+            // Unmapped
+            0x6000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// HuC3 RAM.
+#[derive(Debug)]
+struct Ram {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Ram {
+    /// Value read from the IR receiver when no light is detected.
+    const DARK: Byte = 0xc0;
+
+    /// Constructs a new `Ram`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = usize::from(self.ctl.ram.load());
+        let addr = usize::from(addr);
+        (bank << 13 | addr & 0x1fff) % self.mem.len().max(0x2000)
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        match self.ctl.sel.load() {
+            // RAM
+            0x0 | 0xa => {
+                // Perform adjusted read
+                let index = self.adjust(addr);
+                self.mem.get(index).ok_or(Error::Range).copied()
+            }
+            // RTC response
+            0xc => Ok(self.ctl.rtc.borrow().response()),
+            // RTC semaphore
+            0xd => Ok(self.ctl.rtc.borrow().semaphore()),
+            // IR
+            0xe => Ok(Self::DARK),
+            // Disabled
+            _ => Err(Error::Busy),
+        }
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        match self.ctl.sel.load() {
+            // RAM (read/write)
+            0xa => {
+                // Perform adjusted write
+                let index = self.adjust(addr);
+                *self.mem.get_mut(index).ok_or(Error::Range)? = data;
+            }
+            // RTC command
+            0xb => self.ctl.rtc.borrow_mut().command(data),
+            // RTC semaphore
+            0xd if data & 0x01 == 0 => self.ctl.rtc.borrow_mut().execute(),
+            // RAM (read-only), IR
+            0x0 | 0xd | 0xe => (),
+            // Disabled
+            _ => return Err(Error::Busy),
+        }
+        Ok(())
+    }
+}
+
+/// Clock counters.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Clock {
+    /// Seconds.
+    sec: u16,
+    /// Minutes of the day.
+    min: u16,
+    /// Day counter.
+    day: u16,
+}
+
+impl Clock {
+    /// Minutes per day.
+    const DAY: u16 = 1440;
+
+    /// Advances the clock by a single second.
+    fn tick(&mut self) {
+        self.sec += 1;
+        if self.sec < 60 {
+            return;
+        }
+        self.sec = 0;
+        self.min += 1;
+        if self.min < Self::DAY {
+            return;
+        }
+        self.min = 0;
+        self.day = (self.day + 1) & 0x0fff;
+    }
+
+    /// Advances the clock by the provided number of seconds.
+    fn advance(&mut self, secs: u64) {
+        let time = u64::from(self.sec)
+            + u64::from(self.min) * 60
+            + u64::from(self.day) * u64::from(Self::DAY) * 60
+            + secs;
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            self.sec = (time % 60) as u16;
+            self.min = (time / 60 % u64::from(Self::DAY)) as u16;
+            self.day = (time / 60 / u64::from(Self::DAY)) as u16 & 0x0fff;
+        }
+    }
+}
+
+/// HuC3 real-time clock.
+///
+/// Counts minutes and days, even while the console is powered off. Unlike the
+/// [MBC3's clock](super::mbc3::Rtc), it is controlled by writing commands to
+/// be executed, which operate on an internal 256 × 4-bit register file. See
+/// more details [here][rtc].
+///
+/// # Commands
+///
+/// | Command | Description                                        |
+/// |:-------:|----------------------------------------------------|
+/// | `$1x`   | Read register, then increment address.             |
+/// | `$3x`   | Write `x` to register, then increment address.     |
+/// | `$4x`   | Set address bits `3:0` to `x`.                     |
+/// | `$5x`   | Set address bits `7:4` to `x`.                     |
+/// | `$60`   | Copy the clock counters to registers `$00..=$05`.  |
+/// | `$61`   | Copy registers `$00..=$05` to the clock counters.  |
+/// | `$62`   | Check status (always responds with `1`).           |
+/// | `$6E`   | Play the tone selected by register `$27`.          |
+///
+/// Counters are stored as 12-bit little-endian nibbles, with minutes of the
+/// day followed by the day counter. Registers `$58..=$5E` hold the alarm.
+///
+/// # Persistence
+///
+/// The clock's state is persisted after cartridge RAM using a 28-byte footer,
+/// which stores the counters, the alarm, and a UNIX timestamp. When loaded,
+/// the clock is advanced by the time elapsed since it was saved.
+///
+/// [rtc]: https://gbdev.io/pandocs/HuC3.html
+#[derive(Debug)]
+pub struct Rtc {
+    /// Clock counters.
+    reg: Clock,
+    /// Register file.
+    mem: [Byte; 0x100],
+    /// Register address.
+    idx: Byte,
+    /// Pending command.
+    cmd: Byte,
+    /// Command response.
+    out: Byte,
+    /// Piezo speaker.
+    spk: Piezo,
+    /// Timekeeper.
+    tmr: Timer,
+}
+
+impl Snapshot for Clock {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.sec.save(buf)?;
+        self.min.save(buf)?;
+        self.day.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.sec.restore(buf)?;
+        self.min.restore(buf)?;
+        self.day.restore(buf)
+    }
+}
+
+impl Default for Rtc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rtc {
+    /// Size of the persisted footer.
+    pub const SAVE: usize = 28;
+
+    /// Base address of the alarm registers.
+    const ALARM: usize = 0x58;
+
+    /// Address of the tone register.
+    const TONE: usize = 0x27;
+
+    /// Constructs a new `Rtc`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            reg: Clock::default(),
+            mem: [0; 0x100],
+            idx: Byte::default(),
+            cmd: Byte::default(),
+            out: Byte::default(),
+            spk: Piezo::default(),
+            tmr: Timer::default(),
+        }
+    }
+
+    /// Gets the clock's time source.
+    #[must_use]
+    pub fn source(&self) -> Source {
+        self.tmr.source()
+    }
+
+    /// Sets the clock's time source.
+    pub fn set_source(&mut self, src: Source) {
+        self.sync();
+        self.tmr.set_source(src);
+    }
+
+    /// Advances the clock by a single cycle.
+    pub fn cycle(&mut self) {
+        if self.tmr.cycle() {
+            self.reg.tick();
+        }
+    }
+
+    /// Synchronizes the clock with the wall-clock time.
+    fn sync(&mut self) {
+        let secs = self.tmr.sync();
+        self.reg.advance(secs);
+    }
+
+    /// Reads a 12-bit value from the register file.
+    fn get(&self, addr: usize) -> u16 {
+        self.mem[addr..addr + 3]
+            .iter()
+            .rev()
+            .fold(0, |acc, &nib| acc << 4 | u16::from(nib & 0x0f))
+    }
+
+    /// Writes a 12-bit value to the register file.
+    fn set(&mut self, addr: usize, value: u16) {
+        for (idx, nib) in self.mem[addr..addr + 3].iter_mut().enumerate() {
+            #[allow(clippy::cast_possible_truncation)]
+            let value = (value >> (4 * idx)) as Byte;
+            *nib = value & 0x0f;
+        }
+    }
+
+    /// Writes a command to be executed.
+    fn command(&mut self, data: Byte) {
+        self.cmd = data & 0x7f;
+    }
+
+    /// Reads the response to the last command.
+    fn response(&self) -> Byte {
+        0x80 | self.cmd & 0x70 | self.out
+    }
+
+    /// Reads the semaphore.
+    ///
+    /// As commands are executed immediately, the clock is always ready.
+    #[allow(clippy::unused_self)]
+    fn semaphore(&self) -> Byte {
+        0xff
+    }
+
+    /// Executes the pending command.
+    fn execute(&mut self) {
+        let arg = self.cmd & 0x0f;
+        let idx = usize::from(self.idx);
+        match self.cmd >> 4 {
+            // Read register
+            0x1 => {
+                self.out = self.mem[idx];
+                self.idx = self.idx.wrapping_add(1);
+            }
+            // Write register
+            0x3 => {
+                self.mem[idx] = arg;
+                self.idx = self.idx.wrapping_add(1);
+            }
+            // Set address (low)
+            0x4 => self.idx = self.idx & 0xf0 | arg,
+            // Set address (high)
+            0x5 => self.idx = self.idx & 0x0f | arg << 4,
+            // Extended
+            0x6 => match arg {
+                // Read time
+                0x0 => {
+                    self.sync();
+                    self.set(0x00, self.reg.min);
+                    self.set(0x03, self.reg.day);
+                }
+                // Write time
+                0x1 => {
+                    self.sync();
+                    self.reg = Clock {
+                        sec: 0,
+                        min: self.get(0x00) % Clock::DAY,
+                        day: self.get(0x03),
+                    };
+                    self.tmr.sub = 0;
+                    debug!("set clock: {:?}", self.reg);
+                }
+                // Check status
+                0x2 => self.out = 0x1,
+                // Play tone
+                0xe => self
+                    .spk
+                    .set(Some(self.mem[Self::TONE]).filter(|&tone| tone != 0)),
+                _ => warn!("unknown clock command: {:#04x}", self.cmd),
+            },
+            _ => warn!("unknown clock command: {:#04x}", self.cmd),
+        }
+    }
+
+    /// Loads the clock's state from a save file footer.
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        // Read footer
+        let mut data = [0; Self::SAVE];
+        let nbytes = rtc::read(buf, &mut data)?;
+        if nbytes != Self::SAVE {
+            if nbytes != 0 {
+                warn!("ignored invalid clock data ({nbytes} bytes)");
+            }
+            return Ok(nbytes);
+        }
+        // Parse timestamp
+        let Some(secs) = self.tmr.flash(&data[20..]) else {
+            return Ok(nbytes);
+        };
+        // Parse counters
+        #[allow(clippy::cast_possible_truncation)]
+        let word = |idx: usize| {
+            let bytes = data[4 * idx..4 * idx + 4].try_into().unwrap();
+            u32::from_le_bytes(bytes) as u16 & 0x0fff
+        };
+        self.reg = Clock {
+            sec: 0,
+            min: word(0) % Clock::DAY,
+            day: word(1),
+        };
+        self.set(Self::ALARM, word(2));
+        self.set(Self::ALARM + 3, word(3));
+        #[allow(clippy::cast_possible_truncation)]
+        let ena = word(4) as Byte;
+        self.mem[Self::ALARM + 6] = ena & 0x0f;
+        // Advance by time elapsed since saved
+        self.reg.advance(secs);
+        debug!("loaded clock data: {:?} (+{secs}s)", self.reg);
+        Ok(nbytes)
+    }
+
+    /// Saves the clock's state as a save file footer.
+    fn dump(&mut self, buf: &mut impl io::Write) -> io::Result<usize> {
+        self.sync();
+        let mut data = [0; Self::SAVE];
+        let words = [
+            self.reg.min,
+            self.reg.day,
+            self.get(Self::ALARM),
+            self.get(Self::ALARM + 3),
+            u16::from(self.mem[Self::ALARM + 6]),
+        ];
+        for (idx, word) in words.into_iter().enumerate() {
+            data[4 * idx..4 * idx + 4].copy_from_slice(&u32::from(word).to_le_bytes());
+        }
+        data[20..].copy_from_slice(&Timer::dump());
+        buf.write_all(&data)?;
+        Ok(data.len())
+    }
+}
+
+impl Snapshot for Rtc {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.reg.save(buf)?;
+        self.mem.save(buf)?;
+        self.idx.save(buf)?;
+        self.cmd.save(buf)?;
+        self.out.save(buf)?;
+        self.spk.0.borrow().tone.save(buf)?;
+        self.tmr.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.reg.restore(buf)?;
+        self.mem.restore(buf)?;
+        self.idx.restore(buf)?;
+        self.cmd.restore(buf)?;
+        self.out.restore(buf)?;
+        self.spk.0.borrow_mut().tone.restore(buf)?;
+        self.tmr.restore(buf)
+    }
+}
+
+/// Piezo speaker.
+///
+/// Records tones played by the clock's tone generator, which are reported
+/// through its [`Speaker`] interface.
+#[derive(Clone, Debug, Default)]
+pub struct Piezo(Shared<Sound>);
+
+impl Piezo {
+    /// Sets the tone being played.
+    fn set(&self, tone: Option<Byte>) {
+        let snd = &mut *self.0.borrow_mut();
+        if snd.tone.is_none() && tone.is_none() {
+            return;
+        }
+        snd.tone = tone;
+        // Discard the oldest events once full
+        if snd.evs.len() >= Sound::LIMIT {
+            snd.evs.remove(0);
+        }
+        snd.evs.push(tone.map_or(Event::Stop, Event::Play));
+        debug!("speaker tone: {tone:?}");
+    }
+}
+
+impl Speaker for Piezo {
+    fn tone(&self) -> Option<u8> {
+        self.0.borrow().tone
+    }
+
+    fn events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.0.borrow_mut().evs)
+    }
+}
+
+/// Piezo speaker state.
+#[derive(Debug, Default)]
+struct Sound {
+    /// Playing tone.
+    tone: Option<Byte>,
+    /// Pending events.
+    evs: Vec<Event>,
+}
+
+impl Sound {
+    /// Maximum number of pending events.
+    const LIMIT: usize = 0x40;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> HuC3 {
+        HuC3::new(vec![0; 0x8000].into(), vec![0; 0x2000].into())
+    }
+
+    /// Executes a clock command.
+    fn exec(mbc: &HuC3, cmd: Byte) -> Byte {
+        let mut rom = mbc.rom();
+        let mut ram = mbc.ram();
+        rom.write(0x0000, 0x0b).unwrap();
+        ram.write(0x0000, cmd).unwrap();
+        rom.write(0x0000, 0x0d).unwrap();
+        ram.write(0x0000, 0xfe).unwrap();
+        rom.write(0x0000, 0x0c).unwrap();
+        ram.read(0x0000).unwrap()
+    }
+
+    #[test]
+    fn mode_select_works() {
+        let mbc = setup();
+        let mut rom = mbc.rom();
+        let mut ram = mbc.ram();
+        // Read-only RAM
+        rom.write(0x0000, 0x00).unwrap();
+        ram.write(0x0000, 0x5a).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0x00));
+        // Read/write RAM
+        rom.write(0x0000, 0x0a).unwrap();
+        ram.write(0x0000, 0x5a).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0x5a));
+        // IR reads as no light
+        rom.write(0x0000, 0x0e).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0xc0));
+    }
+
+    #[test]
+    fn rtc_commands_work() {
+        let mbc = setup();
+        // Write 12:34 on day 5 to the registers
+        for cmd in [0x40, 0x50, 0x32, 0x3f, 0x32, 0x35, 0x30, 0x30] {
+            exec(&mbc, cmd);
+        }
+        exec(&mbc, 0x61);
+        assert_eq!(
+            mbc.rtc().borrow().reg,
+            Clock {
+                sec: 0,
+                min: 754,
+                day: 5,
+            }
+        );
+        // Advance by a day and a minute
+        mbc.rtc().borrow_mut().reg.advance(86_460);
+        exec(&mbc, 0x60);
+        exec(&mbc, 0x40);
+        let time: Vec<_> = (0..6).map(|_| exec(&mbc, 0x10) & 0x0f).collect();
+        assert_eq!(time, [0x3, 0xf, 0x2, 0x6, 0x0, 0x0]);
+        // Responses include the command
+        assert_eq!(exec(&mbc, 0x62), 0xe1);
+    }
+
+    #[test]
+    fn rtc_tone_works() {
+        let mbc = setup();
+        let mut spk = mbc.speaker().clone();
+        for cmd in [0x47, 0x52, 0x33, 0x6e] {
+            exec(&mbc, cmd);
+        }
+        assert_eq!(spk.tone(), Some(0x3));
+        // Tones are reported each time they are played
+        exec(&mbc, 0x6e);
+        for cmd in [0x47, 0x52, 0x30, 0x6e] {
+            exec(&mbc, cmd);
+        }
+        assert_eq!(spk.tone(), None);
+        assert_eq!(
+            spk.events(),
+            [Event::Play(0x3), Event::Play(0x3), Event::Stop]
+        );
+        assert_eq!(spk.events(), []);
+    }
+
+    #[test]
+    fn rtc_save_works() {
+        let mbc = setup();
+        mbc.rtc().borrow_mut().reg = Clock {
+            sec: 0,
+            min: 600,
+            day: 42,
+        };
+        // Save footer after RAM
+        let mut buf = Vec::new();
+        assert_eq!(mbc.dump(&mut buf).unwrap(), 0x2000 + Rtc::SAVE);
+        // Load into a new cartridge
+        let mut new = setup();
+        assert_eq!(new.flash(&mut buf.as_slice()).unwrap(), buf.len());
+        let rtc = new.rtc().borrow();
+        assert_eq!((rtc.reg.min, rtc.reg.day), (600, 42));
+    }
+}
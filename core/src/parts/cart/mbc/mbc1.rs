@@ -1,16 +1,22 @@
 use std::io;
 
-use log::{debug, error, trace};
+use log::{debug, trace};
 use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::mio::Device;
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared, Word};
 
 use super::{Data, Mbc};
+use crate::parts::cart::header::LOGO;
 
 /// [MBC1][mbc1] cartridge type.
 ///
-/// [mbc1]: https://gbdev.io/pandocs/MBC1.html
+/// Supports the alternate wiring used by MBC1 [multicarts][mbc1m] (MBC1M), in
+/// which only 4 bits of the ROM bank number are connected.
+///
+/// [mbc1]:  https://gbdev.io/pandocs/MBC1.html
+/// [mbc1m]: https://gbdev.io/pandocs/MBC1.html#mbc1m-1-mib-multi-game-compilation-carts
 #[derive(Debug)]
 pub struct Mbc1 {
     ctl: Control,
@@ -29,6 +35,30 @@ impl Mbc1 {
             ctl,
         }
     }
+
+    /// Constructs a new `Mbc1` using multicart (MBC1M) wiring.
+    #[must_use]
+    pub fn multicart(rom: Data, ram: Data) -> Self {
+        let ctl = Control {
+            mux: true,
+            ..Default::default()
+        };
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::new(ctl.clone(), ram)),
+            ctl,
+        }
+    }
+
+    /// Checks if the ROM appears to be an MBC1 multicart.
+    ///
+    /// As multicarts are indistinguishable by their header, they are instead
+    /// detected heuristically by the presence of a second game's Nintendo logo
+    /// at the start of bank `$10` of a 1 MiB ROM.
+    #[must_use]
+    pub fn detect(rom: &[Byte]) -> bool {
+        rom.len() == 0x10_0000 && rom[0x4_0104..0x4_0134] == LOGO
+    }
 }
 
 impl Block for Mbc1 {
@@ -55,6 +85,26 @@ impl Mbc for Mbc1 {
     }
 }
 
+impl Snapshot for Mbc1 {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.ena.save(buf)?;
+        self.ctl.rom.save(buf)?;
+        self.ctl.ram.save(buf)?;
+        self.ctl.sel.save(buf)?;
+        self.ram.borrow().mem.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.ena.restore(buf)?;
+        self.ctl.rom.restore(buf)?;
+        self.ctl.ram.restore(buf)?;
+        self.ctl.sel.restore(buf)?;
+        self.ram.borrow_mut().mem.restore(buf)
+    }
+}
+
+snapshot!(Enable, RomBank, RamBank, Select);
+
 /// MBC1 registers.
 ///
 /// |     Address     | Size | Name | Description          |
@@ -63,6 +113,9 @@ impl Mbc for Mbc1 {
 /// | `$2000..=$3FFF` | 5bit | ROM  | ROM Bank Number.     |
 /// | `$4000..=$5FFF` | 2bit | RAM  | RAM Bank Number.     |
 /// | `$6000..=$7FFF` | 1bit | SEL  | Banking Mode Select. |
+///
+/// When banking mode 1 is selected, the RAM bank number also applies to
+/// `$0000..=$3FFF` and RAM.
 #[rustfmt::skip]
 #[derive(Clone, Debug, Default)]
 struct Control {
@@ -74,6 +127,19 @@ struct Control {
     ram: Shared<RamBank>,
     /// Banking Mode Select.
     sel: Shared<Select>,
+    /// Multicart wiring.
+    mux: bool,
+}
+
+impl Control {
+    /// Gets the number of ROM bank number bits in use.
+    fn width(&self) -> usize {
+        if self.mux {
+            4
+        } else {
+            5
+        }
+    }
 }
 
 impl Block for Control {
@@ -192,7 +258,7 @@ impl Register for Select {
     }
 
     fn store(&mut self, value: Self::Value) {
-        self.0 = value != 0;
+        self.0 = value & 0x01 != 0;
         debug!("Banking Mode Select: {}", self.0);
     }
 }
@@ -212,13 +278,20 @@ impl Rom {
 
     /// Adjusts addresses by internal bank number.
     fn adjust(&self, addr: Word) -> usize {
-        let bank = {
-            let lo = match usize::from(self.ctl.rom.load()) {
-                0 => 1,
-                x => x,
-            };
-            let hi = usize::from(self.ctl.ram.load());
-            hi << 5 | lo
+        let width = self.ctl.width();
+        let hi = usize::from(self.ctl.ram.load()) << width;
+        let bank = match addr {
+            // Bank 0 (upper bits apply in mode 1)
+            0x0000..=0x3fff if self.ctl.sel.load() != 0 => hi,
+            0x0000..=0x3fff => 0,
+            // Bank N (zero check applies before masking)
+            _ => {
+                let lo = match usize::from(self.ctl.rom.load()) {
+                    0 => 1,
+                    x => x,
+                };
+                hi | lo & ((1 << width) - 1)
+            }
         };
         let addr = usize::from(addr);
         (bank << 14 | addr & 0x3fff) % self.mem.len().max(0x8000)
@@ -228,8 +301,7 @@ impl Rom {
 impl Memory for Rom {
     fn read(&self, addr: Word) -> Result<Byte> {
         let index = match addr {
-            0x0000..=0x3fff => usize::from(addr),
-            0x4000..=0x7fff => self.adjust(addr),
+            0x0000..=0x7fff => self.adjust(addr),
             _ => return Err(Error::Range),
         };
         self.mem.get(index).ok_or(Error::Range).copied()
@@ -250,13 +322,12 @@ impl Memory for Rom {
             }
             // RAM Bank Number
             0x4000..=0x5fff => {
-                // ctl.rom[1:0] <- data[1:0]
+                // ctl.ram[1:0] <- data[1:0]
                 self.ctl.ram.store(data);
             }
             // Banking Mode Select
             0x6000..=0x7fff => {
-                // ctl.sel <- data[3:0] == 0xA
-                error!("unimplemented: Mbc1::write({addr:#06x}, {data:#04x})");
+                // ctl.sel <- data[0]
                 self.ctl.sel.store(data);
             }
             _ => return Err(Error::Range),
@@ -280,7 +351,10 @@ impl Ram {
 
     /// Adjusts addresses by internal bank number.
     fn adjust(&self, addr: Word) -> usize {
-        let bank = usize::from(self.ctl.ram.load());
+        let bank = match self.ctl.sel.load() {
+            0 => 0,
+            _ => usize::from(self.ctl.ram.load()),
+        };
         let addr = usize::from(addr);
         (bank << 13 | addr & 0x1fff) % self.mem.len().max(0x2000)
     }
@@ -308,3 +382,64 @@ impl Memory for Ram {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a ROM with each bank tagged by its number.
+    fn rom(banks: usize) -> Data {
+        let mut rom = vec![0; banks << 14];
+        for (idx, bank) in rom.chunks_mut(0x4000).enumerate() {
+            bank[0] = u8::try_from(idx).unwrap();
+        }
+        rom.into()
+    }
+
+    #[test]
+    fn mode_select_works() {
+        let mbc = Mbc1::new(rom(128), vec![0; 0x8000].into());
+        let mut rom = mbc.rom();
+        let mut ram = mbc.ram();
+        rom.write(0x0000, 0x0a).unwrap();
+        rom.write(0x2000, 0x00).unwrap();
+        rom.write(0x4000, 0x02).unwrap();
+        // Mode 0
+        assert_eq!(rom.read(0x0000), Ok(0x00));
+        assert_eq!(rom.read(0x4000), Ok(0x41));
+        ram.write(0x0000, 0x5a).unwrap();
+        // Mode 1
+        rom.write(0x6000, 0x01).unwrap();
+        assert_eq!(rom.read(0x0000), Ok(0x40));
+        assert_eq!(rom.read(0x4000), Ok(0x41));
+        assert_eq!(ram.read(0x0000), Ok(0x00));
+        rom.write(0x4000, 0x00).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0x5a));
+    }
+
+    #[test]
+    fn multicart_works() {
+        let mbc = Mbc1::multicart(rom(64), Data::default());
+        let mut rom = mbc.rom();
+        // Upper bits select the game
+        rom.write(0x4000, 0x01).unwrap();
+        rom.write(0x2000, 0x12).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x12));
+        rom.write(0x6000, 0x01).unwrap();
+        assert_eq!(rom.read(0x0000), Ok(0x10));
+        // Zero check uses all 5 bits
+        rom.write(0x2000, 0x10).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x10));
+        rom.write(0x2000, 0x00).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x11));
+    }
+
+    #[test]
+    fn multicart_detected() {
+        let mut rom = vec![0; 0x10_0000];
+        assert!(!Mbc1::detect(&rom));
+        rom[0x4_0104..0x4_0134].copy_from_slice(&LOGO);
+        assert!(Mbc1::detect(&rom));
+        assert!(!Mbc1::detect(&rom[..0x8_0000]));
+    }
+}
@@ -0,0 +1,311 @@
+use std::io;
+
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::Device;
+use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::{Data, Mbc};
+
+/// [MBC2][mbc2] cartridge type.
+///
+/// [mbc2]: https://gbdev.io/pandocs/MBC2.html
+#[derive(Debug)]
+pub struct Mbc2 {
+    ctl: Control,
+    rom: Shared<Rom>,
+    ram: Shared<Ram>,
+}
+
+impl Mbc2 {
+    /// Constructs a new `Mbc2`.
+    ///
+    /// # Note
+    ///
+    /// As MBC2 includes built-in RAM, no external RAM is used.
+    #[must_use]
+    pub fn new(rom: Data) -> Self {
+        let ctl = Control::default();
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::new(ctl.clone())),
+            ctl,
+        }
+    }
+}
+
+impl Block for Mbc2 {
+    fn reset(&mut self) {
+        self.ctl.reset();
+    }
+}
+
+impl Mbc for Mbc2 {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        buf.read(&mut *self.ram.borrow_mut().mem)
+    }
+
+    fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        buf.write(&*self.ram.borrow().mem)
+    }
+}
+
+impl Snapshot for Mbc2 {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.ena.save(buf)?;
+        self.ctl.rom.save(buf)?;
+        self.ram.borrow().mem.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.ena.restore(buf)?;
+        self.ctl.rom.restore(buf)?;
+        self.ram.borrow_mut().mem.restore(buf)
+    }
+}
+
+snapshot!(Enable, RomBank);
+
+/// MBC2 registers.
+///
+/// Both registers share the same address range, and are selected by bit 8 of
+/// the address.
+///
+/// |     Address     | Size | Name | Description                 |
+/// |:---------------:|------|------|-----------------------------|
+/// | `$0000..=$3FFF` | 1bit | ENA  | RAM Enable (`A8 == 0`).     |
+/// | `$0000..=$3FFF` | 4bit | ROM  | ROM Bank Number (`A8 == 1`) |
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+struct Control {
+    /// RAM Enable.
+    ena: Shared<Enable>,
+    /// ROM Bank Number.
+    rom: Shared<RomBank>,
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        self.ena.take();
+        self.rom.take();
+    }
+}
+
+/// RAM Enable.
+#[derive(Debug, Default)]
+struct Enable(bool);
+
+impl Memory for Enable {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Enable {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        Byte::from(self.0)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = value & 0x0f == 0x0a;
+        debug!("RAM Enable: {}", self.0);
+    }
+}
+
+/// ROM Bank Number.
+#[derive(Debug, Default)]
+struct RomBank(Byte);
+
+impl Memory for RomBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RomBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x0f
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x0f & value;
+        debug!("ROM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// MBC2 ROM.
+#[derive(Debug)]
+struct Rom {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = match usize::from(self.ctl.rom.load()) {
+            0 => 1,
+            x => x,
+        };
+        let addr = usize::from(addr);
+        (bank << 14 | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        let index = match addr {
+            0x0000..=0x3fff => usize::from(addr),
+            0x4000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        trace!("Mbc2::write({addr:#06x}, {data:#04x})");
+        match addr {
+            // RAM Enable
+            0x0000..=0x3fff if addr & 0x0100 == 0 => {
+                // ctl.ena <- data[3:0] == 0xA
+                self.ctl.ena.store(data);
+            }
+            // ROM Bank Number
+            0x0000..=0x3fff => {
+                // ctl.rom[3:0] <- data[3:0]
+                self.ctl.rom.store(data);
+            }
+            // Unmapped
+            0x4000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// MBC2 RAM.
+///
+/// Built-in 512 × 4-bit RAM, mirrored throughout `$A000..=$BFFF`.
+#[derive(Debug)]
+struct Ram {
+    ctl: Control,
+    mem: Box<[Byte; 0x200]>,
+}
+
+impl Ram {
+    /// Constructs a new `Ram`.
+    fn new(ctl: Control) -> Self {
+        Self {
+            ctl,
+            mem: Box::new([0; 0x200]),
+        }
+    }
+
+    /// Adjusts addresses by mirroring.
+    fn adjust(addr: Word) -> usize {
+        usize::from(addr) & 0x01ff
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        // Error when disabled
+        if self.ctl.ena.load() == 0 {
+            return Err(Error::Busy);
+        }
+        // Perform adjusted read
+        //
+        // Only the lower nibble is stored; the upper nibble reads as set.
+        let index = Self::adjust(addr);
+        Ok(0xf0 | self.mem[index])
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        // Error when disabled
+        if self.ctl.ena.load() == 0 {
+            return Err(Error::Busy);
+        }
+        // Perform adjusted write
+        let index = Self::adjust(addr);
+        self.mem[index] = data & 0x0f;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Mbc2 {
+        // Tag each ROM bank with its number
+        let rom = (0..0x40000)
+            .map(|addr: usize| Byte::try_from(addr >> 14).unwrap())
+            .collect::<Box<_>>();
+        Mbc2::new(rom)
+    }
+
+    #[test]
+    fn rom_banking_works() {
+        let mbc = setup();
+        let mut rom = mbc.rom();
+        // Bank 0 maps to bank 1
+        assert_eq!(rom.read(0x4000), Ok(0x01));
+        // Bank select requires address bit 8
+        rom.write(0x2000, 0x05).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x01));
+        rom.write(0x2100, 0x05).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x05));
+        // Only the lower 4 bits are used
+        rom.write(0x0100, 0xff).unwrap();
+        assert_eq!(rom.read(0x7fff), Ok(0x0f));
+        assert_eq!(rom.read(0x0000), Ok(0x00));
+    }
+
+    #[test]
+    fn ram_nibbles_work() {
+        let mbc = setup();
+        let mut rom = mbc.rom();
+        let mut ram = mbc.ram();
+        // Disabled by default
+        assert_eq!(ram.read(0x0000), Err(Error::Busy));
+        // Enable select requires address bit 8 clear
+        rom.write(0x0100, 0x0a).unwrap();
+        assert_eq!(ram.read(0x0000), Err(Error::Busy));
+        rom.write(0x0000, 0x0a).unwrap();
+        // Upper nibble reads as set
+        ram.write(0x0000, 0x5a).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0xfa));
+        // Mirrored throughout the range
+        assert_eq!(ram.read(0x0200), Ok(0xfa));
+        assert_eq!(ram.read(0x1e00), Ok(0xfa));
+    }
+}
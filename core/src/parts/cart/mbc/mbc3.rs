@@ -1,11 +1,13 @@
 use std::io;
 
-use log::{debug, error, trace};
+use log::{debug, trace, warn};
 use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::mio::Device;
-use rugby_arch::reg::Register;
+use rugby_arch::reg::{Port, Register};
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared, Word};
 
+use super::rtc::{self, Source, Timer};
 use super::{Data, Mbc};
 
 /// [MBC3][mbc3] cartridge type.
@@ -16,25 +18,48 @@ pub struct Mbc3 {
     ctl: Control,
     rom: Shared<Rom>,
     ram: Shared<Ram>,
-    #[allow(unused)]
-    rtc: Rtc,
 }
 
 impl Mbc3 {
     /// Constructs a new `Mbc3`.
     #[must_use]
     pub fn new(rom: Data, ram: Data) -> Self {
-        let ctl = Control::default();
+        Self::make(Control::default(), rom, ram)
+    }
+
+    /// Constructs a new `Mbc3` with a [real-time clock](Rtc).
+    #[must_use]
+    pub fn with_rtc(rom: Data, ram: Data) -> Self {
+        let ctl = Control {
+            rtc: Some(Shared::default()),
+            ..Default::default()
+        };
+        Self::make(ctl, rom, ram)
+    }
+
+    /// Constructs a new `Mbc3` from its control registers.
+    fn make(ctl: Control, rom: Data, ram: Data) -> Self {
         Self {
             rom: Shared::new(Rom::new(ctl.clone(), rom)),
             ram: Shared::new(Ram::new(ctl.clone(), ram)),
             ctl,
-            rtc: Rtc,
         }
     }
+
+    /// Gets the cartridge's real-time clock, if present.
+    #[must_use]
+    pub fn rtc(&self) -> Option<&Shared<Rtc>> {
+        self.ctl.rtc.as_ref()
+    }
 }
 
 impl Block for Mbc3 {
+    fn cycle(&mut self) {
+        if let Some(rtc) = &self.ctl.rtc {
+            rtc.borrow_mut().cycle();
+        }
+    }
+
     fn reset(&mut self) {
         self.ctl.reset();
     }
@@ -50,21 +75,57 @@ impl Mbc for Mbc3 {
     }
 
     fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
-        buf.read(&mut self.ram.borrow_mut().mem)
+        let mut nbytes = buf.read(&mut self.ram.borrow_mut().mem)?;
+        // Load clock data
+        if let Some(rtc) = &self.ctl.rtc {
+            nbytes += rtc.borrow_mut().flash(buf)?;
+        }
+        Ok(nbytes)
     }
 
     fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
-        buf.write(&self.ram.borrow().mem)
+        let mut nbytes = buf.write(&self.ram.borrow().mem)?;
+        // Save clock data
+        if let Some(rtc) = &self.ctl.rtc {
+            nbytes += rtc.borrow_mut().dump(buf)?;
+        }
+        Ok(nbytes)
     }
 }
 
+impl Snapshot for Mbc3 {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.ena.save(buf)?;
+        self.ctl.rom.save(buf)?;
+        self.ctl.ram.save(buf)?;
+        self.ctl.lcd.save(buf)?;
+        if let Some(rtc) = &self.ctl.rtc {
+            rtc.save(buf)?;
+        }
+        self.ram.borrow().mem.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.ena.restore(buf)?;
+        self.ctl.rom.restore(buf)?;
+        self.ctl.ram.restore(buf)?;
+        self.ctl.lcd.restore(buf)?;
+        if let Some(rtc) = &mut self.ctl.rtc {
+            rtc.restore(buf)?;
+        }
+        self.ram.borrow_mut().mem.restore(buf)
+    }
+}
+
+snapshot!(Enable, RomBank, RamBank, Latch);
+
 /// MBC3 registers.
 ///
 /// |     Address     | Size | Name | Description         |
 /// |:---------------:|------|------|---------------------|
 /// | `$0000..=$1FFF` | 1bit | ENA  | RAM + Timer Enable. |
 /// | `$2000..=$3FFF` | 7bit | ROM  | ROM Bank Number.    |
-/// | `$4000..=$5FFF` | 2bit | RAM  | RAM Bank Number.    |
+/// | `$4000..=$5FFF` | 4bit | RAM  | RAM Bank Number.    |
 /// | `$6000..=$7FFF` | 1bit | LCD  | Latch Clock Data.   |
 #[rustfmt::skip]
 #[derive(Clone, Debug, Default)]
@@ -77,6 +138,8 @@ struct Control {
     ram: Shared<RamBank>,
     /// Latch Clock Data.
     lcd: Shared<Latch>,
+    /// Real-time clock.
+    rtc: Option<Shared<Rtc>>,
 }
 
 impl Block for Control {
@@ -145,6 +208,9 @@ impl Register for RomBank {
 }
 
 /// RAM Bank Number.
+///
+/// Values in `$08..=$0C` select a [clock register](Rtc) in place of a RAM
+/// bank.
 #[derive(Debug, Default)]
 struct RamBank(Byte);
 
@@ -163,11 +229,11 @@ impl Register for RamBank {
     type Value = Byte;
 
     fn load(&self) -> Self::Value {
-        self.0 & 0x03
+        self.0 & 0x0f
     }
 
     fn store(&mut self, value: Self::Value) {
-        self.0 = 0x03 & value;
+        self.0 = 0x0f & value;
         debug!("RAM Bank Number: {:#04x}", self.0);
     }
 }
@@ -195,10 +261,7 @@ impl Register for Latch {
     }
 
     fn store(&mut self, value: Self::Value) {
-        let value = value & 0x01 != 0;
-        if !self.0 && value {
-            debug!("latched clock data");
-        }
+        self.0 = value & 0x01 != 0;
     }
 }
 
@@ -217,7 +280,10 @@ impl Rom {
 
     /// Adjusts addresses by internal bank number.
     fn adjust(&self, addr: Word) -> usize {
-        let bank = usize::from(self.ctl.ram.load());
+        let bank = match usize::from(self.ctl.rom.load()) {
+            0 => 1,
+            x => x,
+        };
         let addr = usize::from(addr);
         (bank << 14 | addr & 0x3fff) % self.mem.len().max(0x8000)
     }
@@ -243,19 +309,25 @@ impl Memory for Rom {
             }
             // ROM Bank Number
             0x2000..=0x3fff => {
-                // ctl.rom[4:0] <- data[4:0]
+                // ctl.rom[6:0] <- data[6:0]
                 self.ctl.rom.store(data);
             }
             // RAM Bank Number
             0x4000..=0x5fff => {
-                // ctl.rom[1:0] <- data[1:0]
+                // ctl.ram[3:0] <- data[3:0]
                 self.ctl.ram.store(data);
             }
-            // Banking Mode Select
+            // Latch Clock Data
             0x6000..=0x7fff => {
-                // ctl.sel <- data[3:0] == 0xA
-                error!("unimplemented: Mbc3::write({addr:#06x}, {data:#04x})");
+                // ctl.lcd <- data[0]
+                let prev = self.ctl.lcd.load();
                 self.ctl.lcd.store(data);
+                // Latch on rising edge
+                if prev == 0 && self.ctl.lcd.load() != 0 {
+                    if let Some(rtc) = &self.ctl.rtc {
+                        rtc.borrow_mut().latch();
+                    }
+                }
             }
             _ => return Err(Error::Range),
         }
@@ -278,10 +350,25 @@ impl Ram {
 
     /// Adjusts addresses by internal bank number.
     fn adjust(&self, addr: Word) -> usize {
-        let bank = usize::from(self.ctl.ram.load());
+        let bank = usize::from(self.ctl.ram.load() & 0x03);
         let addr = usize::from(addr);
         (bank << 13 | addr & 0x1fff) % self.mem.len().max(0x2000)
     }
+
+    /// Selects the clock register, if mapped.
+    fn clock(&self) -> Option<Result<(&Shared<Rtc>, Select)>> {
+        let sel = match self.ctl.ram.load() {
+            0x00..=0x07 => return None,
+            0x08 => Select::S,
+            0x09 => Select::M,
+            0x0a => Select::H,
+            0x0b => Select::DL,
+            0x0c => Select::DH,
+            _ => return Some(Err(Error::Range)),
+        };
+        let rtc = self.ctl.rtc.as_ref().ok_or(Error::Range);
+        Some(rtc.map(|rtc| (rtc, sel)))
+    }
 }
 
 impl Memory for Ram {
@@ -290,6 +377,11 @@ impl Memory for Ram {
         if self.ctl.ena.load() == 0 {
             return Err(Error::Busy);
         }
+        // Perform clock read
+        if let Some(clock) = self.clock() {
+            let (rtc, sel) = clock?;
+            return Ok(rtc.borrow().load(sel));
+        }
         // Perform adjusted read
         let index = self.adjust(addr);
         self.mem.get(index).ok_or(Error::Range).copied()
@@ -300,6 +392,12 @@ impl Memory for Ram {
         if self.ctl.ena.load() == 0 {
             return Err(Error::Busy);
         }
+        // Perform clock write
+        if let Some(clock) = self.clock() {
+            let (rtc, sel) = clock?;
+            rtc.borrow_mut().store(sel, data);
+            return Ok(());
+        }
         // Perform adjusted write
         let index = self.adjust(addr);
         *self.mem.get_mut(index).ok_or(Error::Range)? = data;
@@ -307,6 +405,438 @@ impl Memory for Ram {
     }
 }
 
+/// Clock register select.
+///
+/// See more details [here][regs].
+///
+/// [regs]: https://gbdev.io/pandocs/MBC3.html#the-clock-counter-registers
+#[derive(Clone, Copy, Debug)]
+pub enum Select {
+    /// `[$08]`: Seconds
+    S,
+    /// `[$09]`: Minutes
+    M,
+    /// `[$0A]`: Hours
+    H,
+    /// `[$0B]`: Day counter (lower 8 bits)
+    DL,
+    /// `[$0C]`: Day counter (upper bit), halt, and day carry
+    DH,
+}
+
+/// Clock counter registers.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Clock {
+    /// Seconds.
+    sec: Byte,
+    /// Minutes.
+    min: Byte,
+    /// Hours.
+    hrs: Byte,
+    /// Day counter (lower 8 bits).
+    dl: Byte,
+    /// Day counter (upper bit), halt, and day carry.
+    dh: Byte,
+}
+
+impl Clock {
+    /// Seconds per day.
+    const DAY: u64 = 86_400;
+
+    /// Gets the day counter.
+    fn days(self) -> u64 {
+        u64::from(self.dh & 0x01) << 8 | u64::from(self.dl)
+    }
+
+    /// Checks if the clock is halted.
+    fn halted(self) -> bool {
+        self.dh & 0x40 != 0
+    }
+
+    /// Checks if every counter is within its usual range.
+    fn valid(self) -> bool {
+        self.sec < 60 && self.min < 60 && self.hrs < 24
+    }
+
+    /// Advances the clock by a single second.
+    ///
+    /// # Note
+    ///
+    /// Counters may be written with out-of-range values, in which case they
+    /// continue to count until they overflow their bit width, without carrying
+    /// into the next counter.
+    fn tick(&mut self) {
+        self.sec = (self.sec + 1) & 0x3f;
+        if self.sec != 60 {
+            return;
+        }
+        self.sec = 0;
+        self.min = (self.min + 1) & 0x3f;
+        if self.min != 60 {
+            return;
+        }
+        self.min = 0;
+        self.hrs = (self.hrs + 1) & 0x1f;
+        if self.hrs != 24 {
+            return;
+        }
+        self.hrs = 0;
+        self.dl = self.dl.wrapping_add(1);
+        if self.dl == 0 {
+            if self.dh & 0x01 != 0 {
+                // Day counter overflow
+                self.dh = self.dh & !0x01 | 0x80;
+            } else {
+                self.dh |= 0x01;
+            }
+        }
+    }
+
+    /// Advances the clock by the provided number of seconds.
+    fn advance(&mut self, mut secs: u64) {
+        // Step individually until all counters are in range
+        while secs > 0 && !self.valid() {
+            self.tick();
+            secs -= 1;
+        }
+        if secs == 0 {
+            return;
+        }
+        // Compute the remaining time directly
+        let time = u64::from(self.sec)
+            + u64::from(self.min) * 60
+            + u64::from(self.hrs) * 3600
+            + self.days() * Self::DAY
+            + secs;
+        let days = time / Self::DAY;
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            self.sec = (time % 60) as Byte;
+            self.min = (time / 60 % 60) as Byte;
+            self.hrs = (time / 3600 % 24) as Byte;
+            self.dl = days as Byte;
+            self.dh = self.dh & !0x01 | (days >> 8) as Byte & 0x01;
+        }
+        if days > 0x1ff {
+            // Day counter overflow
+            self.dh |= 0x80;
+        }
+    }
+}
+
 /// MBC3 real-time clock.
+///
+/// Counts time in seconds, minutes, hours, and days, even while the console is
+/// powered off. The counters must be [latched](Rtc::latch) before they can be
+/// read. See more details [here][rtc].
+///
+/// # Persistence
+///
+/// The clock's state is persisted after cartridge RAM using the 48-byte
+/// footer popularized by VBA and BGB, which stores the counters, the latched
+/// counters, and a UNIX timestamp. When loaded, the clock is advanced by the
+/// time elapsed since it was saved.
+///
+/// [rtc]: https://gbdev.io/pandocs/MBC3.html#the-clock-counter-registers
 #[derive(Debug)]
-pub struct Rtc;
+pub struct Rtc {
+    /// Clock counters.
+    reg: Clock,
+    /// Latched counters.
+    lat: Clock,
+    /// Timekeeper.
+    tmr: Timer,
+}
+
+impl Snapshot for Clock {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.sec.save(buf)?;
+        self.min.save(buf)?;
+        self.hrs.save(buf)?;
+        self.dl.save(buf)?;
+        self.dh.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.sec.restore(buf)?;
+        self.min.restore(buf)?;
+        self.hrs.restore(buf)?;
+        self.dl.restore(buf)?;
+        self.dh.restore(buf)
+    }
+}
+
+impl Default for Rtc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rtc {
+    /// Size of the persisted footer.
+    pub const SAVE: usize = 48;
+
+    /// Constructs a new `Rtc`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            reg: Clock::default(),
+            lat: Clock::default(),
+            tmr: Timer::default(),
+        }
+    }
+
+    /// Gets the clock's time source.
+    #[must_use]
+    pub fn source(&self) -> Source {
+        self.tmr.source()
+    }
+
+    /// Sets the clock's time source.
+    pub fn set_source(&mut self, src: Source) {
+        self.sync();
+        self.tmr.set_source(src);
+    }
+
+    /// Latches the clock counters.
+    pub fn latch(&mut self) {
+        self.sync();
+        self.lat = self.reg;
+        debug!("latched clock data: {:?}", self.lat);
+    }
+
+    /// Advances the clock by a single cycle.
+    pub fn cycle(&mut self) {
+        if !self.reg.halted() && self.tmr.cycle() {
+            self.reg.tick();
+        }
+    }
+
+    /// Synchronizes the clock with the wall-clock time.
+    fn sync(&mut self) {
+        let secs = self.tmr.sync();
+        if !self.reg.halted() {
+            self.reg.advance(secs);
+        }
+    }
+
+    /// Loads the clock's state from a save file footer.
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        // Read footer
+        let mut data = [0; Self::SAVE];
+        let nbytes = rtc::read(buf, &mut data)?;
+        // Some emulators use a 32-bit timestamp
+        if nbytes != Self::SAVE && nbytes != Self::SAVE - 4 {
+            if nbytes != 0 {
+                warn!("ignored invalid clock data ({nbytes} bytes)");
+            }
+            return Ok(nbytes);
+        }
+        // Parse timestamp
+        let Some(secs) = self.tmr.flash(&data[40..nbytes]) else {
+            return Ok(nbytes);
+        };
+        // Parse counters
+        let word = |idx: usize| data[4 * idx];
+        let clock = |base: usize| Clock {
+            sec: word(base) & 0x3f,
+            min: word(base + 1) & 0x3f,
+            hrs: word(base + 2) & 0x1f,
+            dl: word(base + 3),
+            dh: word(base + 4) & 0xc1,
+        };
+        self.reg = clock(0);
+        self.lat = clock(5);
+        // Advance by time elapsed since saved
+        if !self.reg.halted() {
+            self.reg.advance(secs);
+        }
+        debug!("loaded clock data: {:?} (+{secs}s)", self.reg);
+        Ok(nbytes)
+    }
+
+    /// Saves the clock's state as a save file footer.
+    fn dump(&mut self, buf: &mut impl io::Write) -> io::Result<usize> {
+        self.sync();
+        let mut data = [0; Self::SAVE];
+        let counters = [self.reg, self.lat]
+            .into_iter()
+            .flat_map(|clk| [clk.sec, clk.min, clk.hrs, clk.dl, clk.dh]);
+        for (idx, byte) in counters.enumerate() {
+            data[4 * idx..4 * idx + 4].copy_from_slice(&u32::from(byte).to_le_bytes());
+        }
+        data[40..].copy_from_slice(&Timer::dump());
+        buf.write_all(&data)?;
+        Ok(data.len())
+    }
+}
+
+impl Snapshot for Rtc {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.reg.save(buf)?;
+        self.lat.save(buf)?;
+        self.tmr.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.reg.restore(buf)?;
+        self.lat.restore(buf)?;
+        self.tmr.restore(buf)
+    }
+}
+
+impl Port<Byte> for Rtc {
+    type Select = Select;
+
+    /// Loads from a latched clock register.
+    fn load(&self, reg: Self::Select) -> Byte {
+        let lat = &self.lat;
+        match reg {
+            Select::S => lat.sec & 0x3f,
+            Select::M => lat.min & 0x3f,
+            Select::H => lat.hrs & 0x1f,
+            Select::DL => lat.dl,
+            Select::DH => lat.dh & 0xc1,
+        }
+    }
+
+    /// Stores to a clock register.
+    fn store(&mut self, reg: Self::Select, value: Byte) {
+        self.sync();
+        let clk = &mut self.reg;
+        match reg {
+            Select::S => {
+                clk.sec = value & 0x3f;
+                // Writing seconds resets the sub-second counter
+                self.tmr.sub = 0;
+            }
+            Select::M => clk.min = value & 0x3f,
+            Select::H => clk.hrs = value & 0x1f,
+            Select::DL => clk.dl = value,
+            Select::DH => clk.dh = value & 0xc1,
+        }
+        debug!("RTC {reg:?}: {value:#04x}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::dmg::FREQ;
+
+    fn setup() -> Mbc3 {
+        let mbc = Mbc3::with_rtc(vec![0; 0x8000].into(), vec![0; 0x2000].into());
+        // Enable RAM + timer
+        mbc.rom().write(0x0000, 0x0a).unwrap();
+        mbc
+    }
+
+    #[test]
+    fn rtc_latch_works() {
+        let mbc = setup();
+        let mut rom = mbc.rom();
+        let mut ram = mbc.ram();
+        // Select seconds
+        rom.write(0x4000, 0x08).unwrap();
+        rom.write(0x6000, 0x01).unwrap();
+        ram.write(0x0000, 0x2a).unwrap();
+        // Unchanged until latched
+        assert_eq!(ram.read(0x0000), Ok(0x00));
+        rom.write(0x6000, 0x01).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0x00));
+        // Latch requires a write of 0 then 1
+        rom.write(0x6000, 0x00).unwrap();
+        rom.write(0x6000, 0x01).unwrap();
+        assert_eq!(ram.read(0x0000), Ok(0x2a));
+    }
+
+    #[test]
+    fn rtc_tick_works() {
+        let mut clk = Clock {
+            sec: 59,
+            min: 59,
+            hrs: 23,
+            dl: 0xff,
+            dh: 0x01,
+        };
+        // Day counter overflow sets carry
+        clk.tick();
+        assert_eq!(
+            clk,
+            Clock {
+                dh: 0x80,
+                ..Default::default()
+            }
+        );
+        // Out-of-range values don't carry
+        clk.sec = 63;
+        clk.tick();
+        assert_eq!((clk.sec, clk.min), (0, 0));
+    }
+
+    #[test]
+    fn rtc_advance_works() {
+        for (init, secs) in [
+            (Clock::default(), 1_000_000),
+            (
+                Clock {
+                    sec: 61,
+                    min: 59,
+                    hrs: 23,
+                    ..Default::default()
+                },
+                100,
+            ),
+            (
+                Clock {
+                    dl: 0xfe,
+                    dh: 0x41,
+                    ..Default::default()
+                },
+                200_000,
+            ),
+        ] {
+            let mut fast = init;
+            fast.advance(secs);
+            let mut slow = init;
+            (0..secs).for_each(|_| slow.tick());
+            assert_eq!(fast, slow, "{init:?} + {secs}s");
+        }
+    }
+
+    #[test]
+    fn rtc_wall_works() {
+        let mbc = setup();
+        let mut rtc = mbc.rtc().unwrap().borrow_mut();
+        rtc.set_source(Source::Wall);
+        // Pretend the clock was last synchronized 90s ago
+        rtc.tmr.now -= Duration::from_secs(90);
+        // Emulated cycles are ignored
+        (0..FREQ).for_each(|_| rtc.cycle());
+        rtc.latch();
+        assert_eq!((rtc.load(Select::M), rtc.load(Select::S)), (1, 30));
+    }
+
+    #[test]
+    fn rtc_save_works() {
+        let mbc = setup();
+        let rtc = mbc.rtc().unwrap();
+        // Halt the clock at a known time
+        rtc.borrow_mut().store(Select::DH, 0x41);
+        rtc.borrow_mut().store(Select::H, 0x12);
+        rtc.borrow_mut().latch();
+        // Save footer after RAM
+        let mut buf = Vec::new();
+        assert_eq!(mbc.dump(&mut buf).unwrap(), 0x2000 + Rtc::SAVE);
+        assert_eq!(buf[0x2000 + 4 * 2], 0x12);
+        assert_eq!(buf[0x2000 + 4 * 9], 0x41);
+        // Load into a new cartridge
+        let mut new = setup();
+        assert_eq!(new.flash(&mut buf.as_slice()).unwrap(), buf.len());
+        let rtc = new.rtc().unwrap().borrow();
+        assert_eq!(rtc.load(Select::H), 0x12);
+        assert_eq!(rtc.load(Select::DH), 0x41);
+    }
+}
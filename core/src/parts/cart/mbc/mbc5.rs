@@ -4,12 +4,17 @@ use log::{debug, trace};
 use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::mio::Device;
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared, Word};
 
 use super::{Data, Mbc};
+use crate::api::part::rumble::{Event, Rumble};
 
 /// [MBC5][mbc5] cartridge type.
 ///
+/// May include a rumble [motor](Motor), controlled by bit 3 of the RAM bank
+/// number.
+///
 /// [mbc5]: https://gbdev.io/pandocs/MBC5.html
 #[derive(Debug)]
 pub struct Mbc5 {
@@ -29,6 +34,26 @@ impl Mbc5 {
             ctl,
         }
     }
+
+    /// Constructs a new `Mbc5` with a rumble motor.
+    #[must_use]
+    pub fn with_rumble(rom: Data, ram: Data) -> Self {
+        let ctl = Control {
+            vib: Some(Motor::default()),
+            ..Default::default()
+        };
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::new(ctl.clone(), ram)),
+            ctl,
+        }
+    }
+
+    /// Gets the cartridge's rumble motor, if any.
+    #[must_use]
+    pub fn motor(&self) -> Option<&Motor> {
+        self.ctl.vib.as_ref()
+    }
 }
 
 impl Block for Mbc5 {
@@ -55,6 +80,27 @@ impl Mbc for Mbc5 {
     }
 }
 
+/// The rumble motor is not saved, as its state is reported to the frontend.
+impl Snapshot for Mbc5 {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.ena.save(buf)?;
+        self.ctl.rom.0.save(buf)?;
+        self.ctl.rom.1.save(buf)?;
+        self.ctl.ram.save(buf)?;
+        self.ram.borrow().mem.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.ena.restore(buf)?;
+        self.ctl.rom.0.restore(buf)?;
+        self.ctl.rom.1.restore(buf)?;
+        self.ctl.ram.restore(buf)?;
+        self.ram.borrow_mut().mem.restore(buf)
+    }
+}
+
+snapshot!(Enable, RomBankLo, RomBankHi, RamBank);
+
 /// MBC5 registers.
 ///
 /// |     Address     | Size | Name | Description           |
@@ -63,6 +109,9 @@ impl Mbc for Mbc5 {
 /// | `$2000..=$2FFF` | 8bit | LO   | ROM Bank Number (LO). |
 /// | `$3000..=$3FFF` | 1bit | HI   | ROM Bank Number (HI). |
 /// | `$4000..=$7FFF` | 4bit | RAM  | RAM Bank Number.      |
+///
+/// On cartridges with a rumble motor, bit 3 of the RAM bank number instead
+/// controls the motor.
 #[rustfmt::skip]
 #[derive(Clone, Debug, Default)]
 struct Control {
@@ -72,6 +121,8 @@ struct Control {
     rom: (Shared<RomBankLo>, Shared<RomBankHi>),
     /// RAM Bank Number.
     ram: Shared<RamBank>,
+    /// Rumble motor.
+    vib: Option<Motor>,
 }
 
 impl Block for Control {
@@ -80,6 +131,9 @@ impl Block for Control {
         self.rom.0.take();
         self.rom.1.take();
         self.ram.take();
+        if let Some(vib) = &self.vib {
+            vib.set(false);
+        }
     }
 }
 
@@ -112,9 +166,19 @@ impl Register for Enable {
 }
 
 /// ROM Bank Number (bits 8:0).
-#[derive(Debug, Default)]
+///
+/// Initially selects bank 1, as on hardware, such that the switchable region
+/// does not mirror bank 0 until written. Unlike the MBC1, bank 0 can still be
+/// selected explicitly afterwards.
+#[derive(Debug)]
 struct RomBankLo(Byte);
 
+impl Default for RomBankLo {
+    fn default() -> Self {
+        Self(0x01)
+    }
+}
+
 impl Memory for RomBankLo {
     fn read(&self, _: Word) -> Result<Byte> {
         Err(Error::Misuse)
@@ -250,8 +314,12 @@ impl Memory for Rom {
             }
             // RAM Bank Number
             0x4000..=0x5fff => {
-                // ctl.ram[2:0] <- data[1:0]
+                // ctl.ram[3:0] <- data[3:0]
                 self.ctl.ram.store(data);
+                // ctl.vib <- data[3]
+                if let Some(vib) = &self.ctl.vib {
+                    vib.set(data & 0x08 != 0);
+                }
             }
             _ => return Err(Error::Range),
         }
@@ -274,7 +342,11 @@ impl Ram {
 
     /// Adjusts addresses by internal bank number.
     fn adjust(&self, addr: Word) -> usize {
-        let bank = usize::from(self.ctl.ram.load());
+        let bank = match self.ctl.vib {
+            // Rumble motor uses bit 3
+            Some(_) => usize::from(self.ctl.ram.load() & 0x07),
+            None => usize::from(self.ctl.ram.load()),
+        };
         let addr = usize::from(addr);
         (bank << 13 | addr & 0x1fff) % self.mem.len().max(0x2000)
     }
@@ -302,3 +374,92 @@ impl Memory for Ram {
         Ok(())
     }
 }
+
+/// Rumble motor.
+///
+/// Records motor transitions, which are reported through its [`Rumble`]
+/// interface.
+#[derive(Clone, Debug, Default)]
+pub struct Motor(Shared<Vibration>);
+
+impl Motor {
+    /// Sets whether the motor is running.
+    fn set(&self, on: bool) {
+        let vib = &mut *self.0.borrow_mut();
+        if vib.on == on {
+            return;
+        }
+        vib.on = on;
+        // Discard the oldest events once full
+        if vib.evs.len() >= Vibration::LIMIT {
+            vib.evs.drain(..2);
+        }
+        vib.evs.push(if on { Event::Start } else { Event::Stop });
+        trace!("rumble motor: {on}");
+    }
+}
+
+impl Rumble for Motor {
+    fn active(&self) -> bool {
+        self.0.borrow().on
+    }
+
+    fn events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.0.borrow_mut().evs)
+    }
+}
+
+/// Rumble motor state.
+#[derive(Debug, Default)]
+struct Vibration {
+    /// Motor running.
+    on: bool,
+    /// Pending events.
+    evs: Vec<Event>,
+}
+
+impl Vibration {
+    /// Maximum number of pending events.
+    const LIMIT: usize = 0x40;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rom_bank_works() {
+        let rom: Vec<_> = (0..4).flat_map(|bank| [bank; 0x4000]).collect();
+        let mbc = Mbc5::new(rom.into(), Data::default());
+        let mut rom = mbc.rom();
+        // Bank 1 is initially selected
+        assert_eq!(rom.read(0x4000), Ok(0x01));
+        // Any bank can be selected, including bank 0
+        rom.write(0x2000, 0x03).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x03));
+        rom.write(0x2000, 0x00).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x00));
+    }
+
+    #[test]
+    fn rumble_works() {
+        let mbc = Mbc5::with_rumble(vec![0; 0x8000].into(), vec![0; 0x20000].into());
+        let mut rom = mbc.rom();
+        let mut ram = mbc.ram();
+        let mut vib = mbc.motor().unwrap().clone();
+        // Enable RAM
+        rom.write(0x0000, 0x0a).unwrap();
+        rom.write(0x4000, 0x01).unwrap();
+        ram.write(0x0000, 0x5a).unwrap();
+        // Motor is excluded from banking
+        rom.write(0x4000, 0x09).unwrap();
+        assert!(vib.active());
+        assert_eq!(ram.read(0x0000), Ok(0x5a));
+        // Only transitions are reported
+        rom.write(0x4000, 0x08).unwrap();
+        rom.write(0x4000, 0x00).unwrap();
+        assert!(!vib.active());
+        assert_eq!(vib.events(), [Event::Start, Event::Stop]);
+        assert_eq!(vib.events(), []);
+    }
+}
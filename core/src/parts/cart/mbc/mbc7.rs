@@ -0,0 +1,796 @@
+use std::io;
+
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::Device;
+use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::{Data, Mbc};
+use crate::api::part::motion::{Motion, Tilt};
+
+/// [MBC7][mbc7] cartridge type.
+///
+/// Includes a two-axis [accelerometer](Accelerometer), as well as a serial
+/// EEPROM in place of RAM.
+///
+/// [mbc7]: https://gbdev.io/pandocs/MBC7.html
+#[derive(Debug)]
+pub struct Mbc7 {
+    ctl: Control,
+    rom: Shared<Rom>,
+    ram: Shared<Ram>,
+}
+
+impl Mbc7 {
+    /// Constructs a new `Mbc7`.
+    ///
+    /// # Note
+    ///
+    /// As MBC7 includes a built-in EEPROM, no external RAM is used.
+    #[must_use]
+    pub fn new(rom: Data) -> Self {
+        let ctl = Control::default();
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::new(ctl.clone())),
+            ctl,
+        }
+    }
+
+    /// Gets the cartridge's accelerometer.
+    #[must_use]
+    pub fn accelerometer(&self) -> &Accelerometer {
+        &self.ctl.acc
+    }
+}
+
+impl Block for Mbc7 {
+    fn reset(&mut self) {
+        self.ctl.reset();
+    }
+}
+
+impl Mbc for Mbc7 {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        buf.read(&mut *self.ctl.eep.borrow_mut().mem)
+    }
+
+    fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        buf.write(&*self.ctl.eep.borrow().mem)
+    }
+}
+
+/// The accelerometer is not saved, as its tilt is provided by the frontend.
+impl Snapshot for Mbc7 {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.en1.save(buf)?;
+        self.ctl.rom.save(buf)?;
+        self.ctl.en2.save(buf)?;
+        self.ctl.lat.save(buf)?;
+        self.ctl.eep.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.en1.restore(buf)?;
+        self.ctl.rom.restore(buf)?;
+        self.ctl.en2.restore(buf)?;
+        self.ctl.lat.restore(buf)?;
+        self.ctl.eep.restore(buf)
+    }
+}
+
+snapshot!(Enable, RomBank);
+
+impl Snapshot for Latch {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.rdy.save(buf)?;
+        self.x.save(buf)?;
+        self.y.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.rdy.restore(buf)?;
+        self.x.restore(buf)?;
+        self.y.restore(buf)
+    }
+}
+
+impl Snapshot for Eeprom {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.mem.save(buf)?;
+        self.cs.save(buf)?;
+        self.clk.save(buf)?;
+        self.di.save(buf)?;
+        self.out.save(buf)?;
+        self.wen.save(buf)?;
+        self.phase.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.mem.restore(buf)?;
+        self.cs.restore(buf)?;
+        self.clk.restore(buf)?;
+        self.di.restore(buf)?;
+        self.out.restore(buf)?;
+        self.wen.restore(buf)?;
+        self.phase.restore(buf)
+    }
+}
+
+/// MBC7 registers.
+///
+/// |     Address     | Size | Name | Description      |
+/// |:---------------:|------|------|------------------|
+/// | `$0000..=$1FFF` | 1bit | EN1  | RAM Enable 1.    |
+/// | `$2000..=$3FFF` | 7bit | ROM  | ROM Bank Number. |
+/// | `$4000..=$5FFF` | 1bit | EN2  | RAM Enable 2.    |
+///
+/// Once both enables are set, the sensor and EEPROM registers are mapped to
+/// `$A000..=$AFFF`.
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+struct Control {
+    /// RAM Enable 1.
+    en1: Shared<Enable>,
+    /// ROM Bank Number.
+    rom: Shared<RomBank>,
+    /// RAM Enable 2.
+    en2: Shared<Enable>,
+    /// Accelerometer.
+    acc: Accelerometer,
+    /// Accelerometer latch.
+    lat: Shared<Latch>,
+    /// Serial EEPROM.
+    eep: Shared<Eeprom>,
+}
+
+impl Control {
+    /// Checks if the registers are mapped.
+    fn enabled(&self) -> bool {
+        self.en1.load() != 0 && self.en2.load() != 0
+    }
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        self.en1.take();
+        self.rom.take();
+        self.en2.take();
+        self.lat.take();
+        self.eep.borrow_mut().reset();
+    }
+}
+
+/// RAM Enable.
+#[derive(Debug, Default)]
+struct Enable(bool);
+
+impl Memory for Enable {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Enable {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        Byte::from(self.0)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = value != 0;
+        debug!("RAM Enable: {}", self.0);
+    }
+}
+
+/// ROM Bank Number.
+#[derive(Debug, Default)]
+struct RomBank(Byte);
+
+impl Memory for RomBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RomBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x7f
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x7f & value;
+        debug!("ROM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// MBC7 ROM.
+#[derive(Debug)]
+struct Rom {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    ///
+    /// # Note
+    ///
+    /// Unlike most other cartridges, bank 0 may also be mapped to
+    /// `$4000..=$7FFF`.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = usize::from(self.ctl.rom.load());
+        let addr = usize::from(addr);
+        (bank << 14 | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        let index = match addr {
+            0x0000..=0x3fff => usize::from(addr),
+            0x4000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        trace!("Mbc7::write({addr:#06x}, {data:#04x})");
+        match addr {
+            // RAM Enable 1
+            0x0000..=0x1fff => {
+                // ctl.en1 <- data == 0x0A
+                self.ctl.en1.store(Byte::from(data == 0x0a));
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // ctl.rom[6:0] <- data[6:0]
+                self.ctl.rom.store(data);
+            }
+            // RAM Enable 2
+            0x4000..=0x5fff => {
+                // ctl.en2 <- data == 0x40
+                self.ctl.en2.store(Byte::from(data == 0x40));
+            }
+            // Unmapped
+            0x6000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// MBC7 RAM.
+///
+/// Maps the sensor and EEPROM registers, repeated every 256 bytes. Address
+/// bits `7:4` select the register.
+///
+/// | Register | Description                                     |
+/// |:--------:|-------------------------------------------------|
+/// | `$Ax0x`  | Erase latched sensor data (by writing `$55`).   |
+/// | `$Ax1x`  | Latch sensor data (by writing `$AA`).           |
+/// | `$Ax2x`  | Horizontal axis (low).                          |
+/// | `$Ax3x`  | Horizontal axis (high).                         |
+/// | `$Ax4x`  | Vertical axis (low).                            |
+/// | `$Ax5x`  | Vertical axis (high).                           |
+/// | `$Ax6x`  | Unknown (reads as `$00`).                       |
+/// | `$Ax8x`  | EEPROM signals.                                 |
+#[derive(Debug)]
+struct Ram {
+    ctl: Control,
+}
+
+impl Ram {
+    /// Constructs a new `Ram`.
+    fn new(ctl: Control) -> Self {
+        Self { ctl }
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        // Error when disabled
+        if !self.ctl.enabled() {
+            return Err(Error::Busy);
+        }
+        // Perform register read
+        if addr > 0x0fff {
+            return Ok(0xff);
+        }
+        let lat = self.ctl.lat.borrow();
+        let [xlo, xhi] = lat.x.to_le_bytes();
+        let [ylo, yhi] = lat.y.to_le_bytes();
+        Ok(match addr >> 4 & 0x0f {
+            0x2 => xlo,
+            0x3 => xhi,
+            0x4 => ylo,
+            0x5 => yhi,
+            0x6 => 0x00,
+            0x8 => self.ctl.eep.borrow().load(),
+            _ => 0xff,
+        })
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        // Error when disabled
+        if !self.ctl.enabled() {
+            return Err(Error::Busy);
+        }
+        // Perform register write
+        if addr > 0x0fff {
+            return Ok(());
+        }
+        match addr >> 4 & 0x0f {
+            0x0 if data == 0x55 => self.ctl.lat.borrow_mut().erase(),
+            0x1 if data == 0xaa => self.ctl.lat.borrow_mut().latch(self.ctl.acc.tilt()),
+            0x8 => self.ctl.eep.borrow_mut().store(data),
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+/// ADXL202 accelerometer.
+///
+/// Measures the tilt of the cartridge, as supplied through its [`Motion`]
+/// interface.
+#[derive(Clone, Debug, Default)]
+pub struct Accelerometer(Shared<Tilt>);
+
+impl Motion for Accelerometer {
+    fn tilt(&self) -> Tilt {
+        *self.0.borrow()
+    }
+
+    fn set_tilt(&mut self, tilt: Tilt) {
+        *self.0.borrow_mut() = tilt;
+    }
+}
+
+/// Accelerometer latch.
+#[derive(Debug)]
+struct Latch {
+    /// Latch ready.
+    rdy: bool,
+    /// Horizontal axis.
+    x: Word,
+    /// Vertical axis.
+    y: Word,
+}
+
+impl Default for Latch {
+    fn default() -> Self {
+        Self {
+            rdy: false,
+            x: Self::ERASED,
+            y: Self::ERASED,
+        }
+    }
+}
+
+impl Latch {
+    /// Value of an erased axis.
+    const ERASED: Word = 0x8000;
+
+    /// Value of an axis at rest.
+    const CENTER: f32 = 33_232.; // 0x81d0
+
+    /// Change in value per unit of standard gravity.
+    const GRAVITY: f32 = 112.; // 0x70
+
+    /// Erases the latched values.
+    fn erase(&mut self) {
+        self.rdy = true;
+        self.x = Self::ERASED;
+        self.y = Self::ERASED;
+    }
+
+    /// Latches the provided tilt.
+    ///
+    /// Values must first be [erased](Self::erase) before they can be latched.
+    fn latch(&mut self, tilt: Tilt) {
+        if !std::mem::take(&mut self.rdy) {
+            return;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_sign_loss)]
+        let axis = |g: f32| (Self::CENTER + Self::GRAVITY * g).clamp(0., 65_535.) as Word;
+        self.x = axis(tilt.x);
+        self.y = axis(tilt.y);
+        trace!("latched tilt: ({:#06x}, {:#06x})", self.x, self.y);
+    }
+}
+
+/// 93LC56 serial EEPROM.
+///
+/// Organized as 128 × 16-bit words, stored little-endian. It is accessed over
+/// a serial interface, using the following register bits:
+///
+/// | Bit | Name | Description                    |
+/// |:---:|------|--------------------------------|
+/// | 7   | CS   | Chip select.                   |
+/// | 6   | CLK  | Serial clock.                  |
+/// | 1   | DI   | Data input.                    |
+/// | 0   | DO   | Data output (read-only).       |
+///
+/// Bits are transferred on the rising edge of the clock, with each command
+/// consisting of a start bit, a 2-bit opcode, and an 8-bit address.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug)]
+struct Eeprom {
+    /// Memory contents.
+    mem: Box<[Byte; 0x100]>,
+    /// Chip select.
+    cs: bool,
+    /// Serial clock.
+    clk: bool,
+    /// Data input.
+    di: bool,
+    /// Data output.
+    out: bool,
+    /// Write enable.
+    wen: bool,
+    /// Transfer phase.
+    phase: Phase,
+}
+
+impl Default for Eeprom {
+    fn default() -> Self {
+        Self {
+            mem: Box::new([0xff; 0x100]),
+            cs: false,
+            clk: false,
+            di: false,
+            out: true,
+            wen: false,
+            phase: Phase::default(),
+        }
+    }
+}
+
+/// EEPROM transfer phase.
+#[derive(Clone, Copy, Debug, Default)]
+enum Phase {
+    /// Awaiting start bit.
+    #[default]
+    Idle,
+    /// Receiving command.
+    Command { bits: Word, len: u8 },
+    /// Receiving data.
+    Input {
+        addr: Option<usize>,
+        bits: Word,
+        len: u8,
+    },
+    /// Sending data.
+    Output { addr: usize, bits: Word, len: u8 },
+    /// Command completed.
+    Done,
+}
+
+impl Snapshot for Phase {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        match *self {
+            Phase::Idle => 0u8.save(buf),
+            Phase::Command { bits, len } => {
+                1u8.save(buf)?;
+                bits.save(buf)?;
+                len.save(buf)
+            }
+            Phase::Input { addr, bits, len } => {
+                2u8.save(buf)?;
+                addr.save(buf)?;
+                bits.save(buf)?;
+                len.save(buf)
+            }
+            Phase::Output { addr, bits, len } => {
+                3u8.save(buf)?;
+                addr.save(buf)?;
+                bits.save(buf)?;
+                len.save(buf)
+            }
+            Phase::Done => 4u8.save(buf),
+        }
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        *self = match u8::decode(buf)? {
+            0 => Phase::Idle,
+            1 => Phase::Command {
+                bits: Word::decode(buf)?,
+                len: u8::decode(buf)?,
+            },
+            2 => Phase::Input {
+                addr: Snapshot::decode(buf)?,
+                bits: Word::decode(buf)?,
+                len: u8::decode(buf)?,
+            },
+            3 => Phase::Output {
+                addr: usize::decode(buf)?,
+                bits: Word::decode(buf)?,
+                len: u8::decode(buf)?,
+            },
+            4 => Phase::Done,
+            _ => return Err(io::ErrorKind::InvalidData.into()),
+        };
+        Ok(())
+    }
+}
+
+impl Eeprom {
+    /// Resets the serial interface.
+    fn reset(&mut self) {
+        self.cs = false;
+        self.clk = false;
+        self.di = false;
+        self.out = true;
+        self.wen = false;
+        self.phase = Phase::Idle;
+    }
+
+    /// Reads a word.
+    fn get(&self, addr: usize) -> Word {
+        let addr = 2 * (addr & 0x7f);
+        Word::from_le_bytes([self.mem[addr], self.mem[addr + 1]])
+    }
+
+    /// Writes a word, if enabled.
+    fn set(&mut self, addr: usize, word: Word) {
+        if !self.wen {
+            return;
+        }
+        let addr = 2 * (addr & 0x7f);
+        self.mem[addr..=addr + 1].copy_from_slice(&word.to_le_bytes());
+    }
+
+    /// Clocks in a single bit.
+    fn clock(&mut self, bit: bool) {
+        let bit = Word::from(bit);
+        self.phase = match self.phase {
+            // Start bit
+            Phase::Idle if bit != 0 => Phase::Command { bits: 0, len: 0 },
+            Phase::Idle => Phase::Idle,
+            // Opcode and address
+            Phase::Command { bits, len } => {
+                let bits = bits << 1 | bit;
+                if len + 1 < 10 {
+                    Phase::Command { bits, len: len + 1 }
+                } else {
+                    self.exec(bits)
+                }
+            }
+            // Data input
+            Phase::Input { addr, bits, len } => {
+                let bits = bits << 1 | bit;
+                if len + 1 < 16 {
+                    Phase::Input {
+                        addr,
+                        bits,
+                        len: len + 1,
+                    }
+                } else {
+                    match addr {
+                        Some(addr) => self.set(addr, bits),
+                        None => (0..0x80).for_each(|addr| self.set(addr, bits)),
+                    }
+                    self.out = true;
+                    Phase::Done
+                }
+            }
+            // Data output
+            Phase::Output { addr, bits, len } => {
+                self.out = bits & 0x8000 != 0;
+                if len + 1 < 16 {
+                    Phase::Output {
+                        addr,
+                        bits: bits << 1,
+                        len: len + 1,
+                    }
+                } else {
+                    // Continue reading sequentially
+                    let addr = (addr + 1) & 0x7f;
+                    Phase::Output {
+                        addr,
+                        bits: self.get(addr),
+                        len: 0,
+                    }
+                }
+            }
+            Phase::Done => Phase::Done,
+        };
+    }
+
+    /// Executes a command.
+    fn exec(&mut self, cmd: Word) -> Phase {
+        let addr = usize::from(cmd & 0x7f);
+        trace!("EEPROM command: {cmd:#05x}");
+        match cmd >> 8 & 0b11 {
+            // READ
+            0b10 => {
+                // Output begins with a dummy bit
+                self.out = false;
+                Phase::Output {
+                    addr,
+                    bits: self.get(addr),
+                    len: 0,
+                }
+            }
+            // WRITE
+            0b01 => Phase::Input {
+                addr: Some(addr),
+                bits: 0,
+                len: 0,
+            },
+            // ERASE
+            0b11 => {
+                self.set(addr, 0xffff);
+                self.out = true;
+                Phase::Done
+            }
+            // Extended
+            _ => match cmd >> 6 & 0b11 {
+                // EWDS
+                0b00 => {
+                    self.wen = false;
+                    Phase::Done
+                }
+                // WRAL
+                0b01 => Phase::Input {
+                    addr: None,
+                    bits: 0,
+                    len: 0,
+                },
+                // ERAL
+                0b10 => {
+                    (0..0x80).for_each(|addr| self.set(addr, 0xffff));
+                    self.out = true;
+                    Phase::Done
+                }
+                // EWEN
+                _ => {
+                    self.wen = true;
+                    Phase::Done
+                }
+            },
+        }
+    }
+}
+
+impl Register for Eeprom {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        Byte::from(self.cs) << 7
+            | Byte::from(self.clk) << 6
+            | Byte::from(self.di) << 1
+            | Byte::from(self.out)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        let cs = value & 0x80 != 0;
+        let clk = value & 0x40 != 0;
+        let di = value & 0x02 != 0;
+        if !cs {
+            // Deselecting aborts any command
+            self.phase = Phase::Idle;
+            self.out = true;
+        } else if clk && !self.clk {
+            // Transfer on rising edge
+            self.clock(di);
+        }
+        self.cs = cs;
+        self.clk = clk;
+        self.di = di;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Mbc7 {
+        let mbc = Mbc7::new(vec![0; 0x8000].into());
+        // Enable registers
+        mbc.rom().write(0x0000, 0x0a).unwrap();
+        mbc.rom().write(0x4000, 0x40).unwrap();
+        mbc
+    }
+
+    /// Transfers bits to the EEPROM, returning the output bits.
+    fn send(ram: &mut Device, bits: &[u8]) -> Vec<u8> {
+        bits.iter()
+            .map(|&bit| {
+                let di = bit << 1;
+                ram.write(0x0080, 0x80 | di).unwrap();
+                ram.write(0x0080, 0xc0 | di).unwrap();
+                ram.read(0x0080).unwrap() & 0x01
+            })
+            .collect()
+    }
+
+    /// Converts a value into its bits, most significant first.
+    fn bits(value: u32, len: u32) -> Vec<u8> {
+        (0..len).rev().map(|idx| (value >> idx & 1) as u8).collect()
+    }
+
+    #[test]
+    fn accelerometer_works() {
+        let mbc = setup();
+        let mut ram = mbc.ram();
+        mbc.accelerometer()
+            .clone()
+            .set_tilt(Tilt { x: 1., y: -0.5 });
+        // Latch requires erasing first
+        ram.write(0x0010, 0xaa).unwrap();
+        assert_eq!(ram.read(0x0030), Ok(0x80));
+        ram.write(0x0000, 0x55).unwrap();
+        ram.write(0x0010, 0xaa).unwrap();
+        // Read latched values
+        let x = Word::from_le_bytes([ram.read(0x0020).unwrap(), ram.read(0x0030).unwrap()]);
+        let y = Word::from_le_bytes([ram.read(0x0040).unwrap(), ram.read(0x0050).unwrap()]);
+        assert_eq!((x, y), (0x81d0 + 0x70, 0x81d0 - 0x38));
+    }
+
+    #[test]
+    fn eeprom_works() {
+        let mbc = setup();
+        let mut ram = mbc.ram();
+        // Writes are ignored until enabled
+        send(
+            &mut ram,
+            &[[1, 0, 1].as_slice(), &bits(0x03, 8), &bits(0xbeef, 16)].concat(),
+        );
+        ram.write(0x0080, 0x00).unwrap();
+        // EWEN
+        send(&mut ram, &[[1, 0, 0].as_slice(), &bits(0xc0, 8)].concat());
+        ram.write(0x0080, 0x00).unwrap();
+        // WRITE
+        send(
+            &mut ram,
+            &[[1, 0, 1].as_slice(), &bits(0x04, 8), &bits(0xcafe, 16)].concat(),
+        );
+        ram.write(0x0080, 0x00).unwrap();
+        // READ
+        let out = send(
+            &mut ram,
+            &[[1, 1, 0].as_slice(), &bits(0x03, 8), &[0; 32]].concat(),
+        );
+        ram.write(0x0080, 0x00).unwrap();
+        assert_eq!(out[10], 0, "missing dummy bit");
+        assert_eq!(out[11..27], bits(0xffff, 16));
+        assert_eq!(out[27..], bits(0xcafe, 16));
+        // Persisted in little-endian
+        let mut buf = Vec::new();
+        mbc.dump(&mut buf).unwrap();
+        assert_eq!(buf[6..10], [0xff, 0xff, 0xfe, 0xca]);
+    }
+}
@@ -0,0 +1,417 @@
+use std::io;
+
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::Device;
+use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::{Data, Mbc};
+
+/// [MMM01][mmm01] cartridge type.
+///
+/// Multicart mapper used by several game compilations. On power-up the mapper
+/// is unconfigured, mapping the last 32 KiB of ROM (containing the menu) to
+/// `$0000..=$7FFF`. Once the menu has selected a game, it locks the mapper
+/// into that game's bank window, after which the game sees an MBC1-like
+/// mapper. Only a reset will unlock the mapper.
+///
+/// [mmm01]: https://gbdev.io/pandocs/MMM01.html
+#[derive(Debug)]
+pub struct Mmm01 {
+    ctl: Control,
+    rom: Shared<Rom>,
+    ram: Shared<Ram>,
+}
+
+impl Mmm01 {
+    /// Constructs a new `Mmm01`.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let ctl = Control::default();
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::new(ctl.clone(), ram)),
+            ctl,
+        }
+    }
+}
+
+impl Block for Mmm01 {
+    fn reset(&mut self) {
+        self.ctl.reset();
+    }
+}
+
+impl Mbc for Mmm01 {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        buf.read(&mut self.ram.borrow_mut().mem)
+    }
+
+    fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        buf.write(&self.ram.borrow().mem)
+    }
+}
+
+impl Snapshot for Mmm01 {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.ena.save(buf)?;
+        self.ctl.map.save(buf)?;
+        self.ram.borrow().mem.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.ena.restore(buf)?;
+        self.ctl.map.restore(buf)?;
+        self.ram.borrow_mut().mem.restore(buf)
+    }
+}
+
+snapshot!(Enable);
+
+impl Snapshot for Mapping {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.map.save(buf)?;
+        self.rom.save(buf)?;
+        self.rmsk.save(buf)?;
+        self.ram.save(buf)?;
+        self.amsk.save(buf)?;
+        self.sel.save(buf)?;
+        self.wp.save(buf)?;
+        self.mux.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.map.restore(buf)?;
+        self.rom.restore(buf)?;
+        self.rmsk.restore(buf)?;
+        self.ram.restore(buf)?;
+        self.amsk.restore(buf)?;
+        self.sel.restore(buf)?;
+        self.wp.restore(buf)?;
+        self.mux.restore(buf)
+    }
+}
+
+/// MMM01 registers.
+///
+/// Fields marked with `*` are only writable while the mapper is unlocked.
+///
+/// |     Address     | Bits | Name  | Description              |
+/// |:---------------:|------|-------|--------------------------|
+/// | `$0000..=$1FFF` | 3:0  | ENA   | RAM Enable.              |
+/// |                 | 5:4  | AMSK* | RAM Bank Mask.           |
+/// |                 | 6    | MAP*  | Mapping Lock.            |
+/// | `$2000..=$3FFF` | 4:0  | ROM   | ROM Bank Number (low).   |
+/// |                 | 6:5  | ROM*  | ROM Bank Number (mid).   |
+/// | `$4000..=$5FFF` | 1:0  | RAM   | RAM Bank Number (low).   |
+/// |                 | 3:2  | RAM*  | RAM Bank Number (high).  |
+/// |                 | 5:4  | ROM*  | ROM Bank Number (high).  |
+/// |                 | 6    | WP*   | Mode Select Protect.     |
+/// | `$6000..=$7FFF` | 0    | SEL   | Banking Mode Select.     |
+/// |                 | 5:2  | RMSK* | ROM Bank Mask.           |
+/// |                 | 6    | MUX*  | Multiplex Enable.        |
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+struct Control {
+    /// RAM Enable.
+    ena: Shared<Enable>,
+    /// Bank mapping.
+    map: Shared<Mapping>,
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        self.ena.take();
+        self.map.take();
+    }
+}
+
+/// RAM Enable.
+#[derive(Debug, Default)]
+struct Enable(bool);
+
+impl Memory for Enable {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Enable {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        Byte::from(self.0)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = value & 0x0f == 0x0a;
+        debug!("RAM Enable: {}", self.0);
+    }
+}
+
+/// Bank mapping.
+///
+/// Masked bank number bits retain their value when written, which restricts a
+/// locked game to its own bank window.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default)]
+struct Mapping {
+    /// Mapping Lock.
+    map: bool,
+    /// ROM Bank Number.
+    rom: Word,
+    /// ROM Bank Mask.
+    rmsk: Byte,
+    /// RAM Bank Number.
+    ram: Byte,
+    /// RAM Bank Mask.
+    amsk: Byte,
+    /// Banking Mode Select.
+    sel: bool,
+    /// Mode Select Protect.
+    wp: bool,
+    /// Multiplex Enable.
+    mux: bool,
+}
+
+impl Mapping {
+    /// Gets the ROM banks mapped to `$0000..=$3FFF` and `$4000..=$7FFF`.
+    ///
+    /// While unlocked, these are the last two banks of ROM.
+    fn rom(&self) -> (usize, usize) {
+        if !self.map {
+            return (0x1fe, 0x1ff);
+        }
+        let rom = usize::from(self.rom);
+        let lo = rom & 0x1f;
+        let hi = rom & 0x180;
+        // When multiplexed, the middle bits are taken from the RAM bank
+        let mid = if self.mux {
+            usize::from(self.ram & 0x03) << 5
+        } else {
+            rom & 0x60
+        };
+        let msk = usize::from(self.rmsk) << 1;
+        let rom0 = hi | if self.mux && self.sel { 0 } else { mid } | lo & msk;
+        let rom1 = hi | mid | lo;
+        // Bank 0 of the window maps to bank 1
+        (rom0, if rom1 == rom0 { rom1 + 1 } else { rom1 })
+    }
+
+    /// Gets the RAM bank mapped to `$A000..=$BFFF`.
+    fn ram(&self) -> usize {
+        let ram = usize::from(self.ram);
+        if self.map && self.mux {
+            // When multiplexed, the lower bits are taken from the ROM bank
+            ram & 0x0c | usize::from(self.rom >> 5) & 0x03
+        } else {
+            ram
+        }
+    }
+}
+
+/// MMM01 ROM.
+#[derive(Debug)]
+struct Rom {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let (rom0, rom1) = self.ctl.map.borrow().rom();
+        let bank = match addr {
+            0x0000..=0x3fff => rom0,
+            _ => rom1,
+        };
+        let addr = usize::from(addr);
+        (bank << 14 | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        let index = match addr {
+            0x0000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        trace!("Mmm01::write({addr:#06x}, {data:#04x})");
+        let map = &mut *self.ctl.map.borrow_mut();
+        match addr {
+            // RAM Enable
+            0x0000..=0x1fff => {
+                // ctl.ena <- data[3:0] == 0xA
+                self.ctl.ena.store(data);
+                if !map.map {
+                    // ctl.amsk[1:0] <- data[5:4]
+                    map.amsk = data >> 4 & 0x03;
+                    // ctl.map <- data[6]
+                    map.map = data & 0x40 != 0;
+                    if map.map {
+                        debug!("Mapping Lock: {:#05x}", map.rom);
+                    }
+                }
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // ctl.rom[4:0] <- data[4:0] (unmasked)
+                let msk = Word::from(map.rmsk) << 1;
+                map.rom = map.rom & !(0x1f & !msk) | Word::from(data) & 0x1f & !msk;
+                if !map.map {
+                    // ctl.rom[6:5] <- data[6:5]
+                    map.rom = map.rom & !0x60 | Word::from(data) & 0x60;
+                }
+                debug!("ROM Bank Number: {:#05x}", map.rom);
+            }
+            // RAM Bank Number
+            0x4000..=0x5fff => {
+                // ctl.ram[1:0] <- data[1:0] (unmasked)
+                map.ram = map.ram & !(0x03 & !map.amsk) | data & 0x03 & !map.amsk;
+                if !map.map {
+                    // ctl.ram[3:2] <- data[3:2]
+                    map.ram = map.ram & !0x0c | data & 0x0c;
+                    // ctl.rom[8:7] <- data[5:4]
+                    map.rom = map.rom & !0x180 | Word::from(data & 0x30) << 3;
+                    // ctl.wp <- data[6]
+                    map.wp = data & 0x40 != 0;
+                }
+                debug!("RAM Bank Number: {:#04x}", map.ram);
+            }
+            // Banking Mode Select
+            0x6000..=0x7fff => {
+                if !map.wp {
+                    // ctl.sel <- data[0]
+                    map.sel = data & 0x01 != 0;
+                }
+                if !map.map {
+                    // ctl.rmsk[3:0] <- data[5:2]
+                    map.rmsk = data >> 2 & 0x0f;
+                    // ctl.mux <- data[6]
+                    map.mux = data & 0x40 != 0;
+                }
+            }
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// MMM01 RAM.
+#[derive(Debug)]
+struct Ram {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Ram {
+    /// Constructs a new `Ram`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = self.ctl.map.borrow().ram();
+        let addr = usize::from(addr);
+        (bank << 13 | addr & 0x1fff) % self.mem.len().max(0x2000)
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        // Error when disabled
+        if self.ctl.ena.load() == 0 {
+            return Err(Error::Busy);
+        }
+        // Perform adjusted read
+        let index = self.adjust(addr);
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        // Error when disabled
+        if self.ctl.ena.load() == 0 {
+            return Err(Error::Busy);
+        }
+        // Perform adjusted write
+        let index = self.adjust(addr);
+        *self.mem.get_mut(index).ok_or(Error::Range)? = data;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Mmm01 {
+        // Tag each ROM bank with its number
+        let rom = (0..0x80000)
+            .map(|addr: usize| Byte::try_from(addr >> 14).unwrap())
+            .collect::<Box<_>>();
+        Mmm01::new(rom, Box::default())
+    }
+
+    #[test]
+    fn unlocked_maps_last_banks() {
+        let mbc = setup();
+        let mut rom = mbc.rom();
+        assert_eq!(rom.read(0x0000), Ok(0x1e));
+        assert_eq!(rom.read(0x4000), Ok(0x1f));
+        // Bank selection has no effect until locked
+        rom.write(0x2000, 0x04).unwrap();
+        assert_eq!(rom.read(0x0000), Ok(0x1e));
+        assert_eq!(rom.read(0x4000), Ok(0x1f));
+    }
+
+    #[test]
+    fn lock_restricts_window() {
+        let mut mbc = setup();
+        let mut rom = mbc.rom();
+        // Select a 4-bank window at bank 8
+        rom.write(0x2000, 0x08).unwrap();
+        rom.write(0x6000, 0x0e << 2).unwrap();
+        rom.write(0x0000, 0x40).unwrap();
+        assert_eq!(rom.read(0x0000), Ok(0x08));
+        assert_eq!(rom.read(0x4000), Ok(0x09));
+        // Only unmasked bits are writable
+        rom.write(0x2000, 0x03).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x0b));
+        rom.write(0x2000, 0x1c).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x09));
+        // Further configuration is ignored
+        rom.write(0x0000, 0x00).unwrap();
+        rom.write(0x6000, 0x00).unwrap();
+        rom.write(0x2000, 0x12).unwrap();
+        assert_eq!(rom.read(0x0000), Ok(0x08));
+        assert_eq!(rom.read(0x4000), Ok(0x0a));
+        // Reset unlocks the mapper
+        mbc.reset();
+        assert_eq!(rom.read(0x0000), Ok(0x1e));
+    }
+}
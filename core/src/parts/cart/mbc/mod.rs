@@ -9,20 +9,54 @@ use std::io;
 
 use log::trace;
 use rugby_arch::mio::{Bus, Device, Mmio};
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte};
 
 use super::header::Header;
 use super::{Error, Info, Result};
 
+/// Implements [`Snapshot`] for single-field registers.
+macro_rules! snapshot {
+    ($($reg:ident),+ $(,)?) => {$(
+        impl Snapshot for $reg {
+            fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+                self.0.save(buf)
+            }
+
+            fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+                self.0.restore(buf)
+            }
+        }
+    )+};
+}
+
 mod bare;
+mod huc1;
 mod mbc1;
-mod mbc3;
-mod mbc5;
+mod mbc2;
+mod mmm01;
+mod sachen;
+mod wisdom;
+
+pub mod camera;
+pub mod huc3;
+pub mod mbc3;
+pub mod mbc5;
+pub mod mbc7;
+pub mod rtc;
 
 pub use self::bare::Bare;
+pub use self::camera::Camera;
+pub use self::huc1::HuC1;
+pub use self::huc3::HuC3;
 pub use self::mbc1::Mbc1;
+pub use self::mbc2::Mbc2;
 pub use self::mbc3::Mbc3;
 pub use self::mbc5::Mbc5;
+pub use self::mbc7::Mbc7;
+pub use self::mmm01::Mmm01;
+pub use self::sachen::Sachen;
+pub use self::wisdom::WisdomTree;
 
 /// Memory data.
 type Data = Box<[Byte]>;
@@ -63,10 +97,42 @@ pub enum Body {
     Bare(Bare),
     /// MBC1 cartridge type.
     Mbc1(Mbc1),
-    /// MBC5 cartridge type.
+    /// MBC2 cartridge type.
+    Mbc2(Mbc2),
+    /// MBC3 cartridge type.
     Mbc3(Mbc3),
     /// MBC5 cartridge type.
     Mbc5(Mbc5),
+    /// MBC7 cartridge type.
+    Mbc7(Mbc7),
+    /// MMM01 cartridge type.
+    Mmm01(Mmm01),
+    /// HuC1 cartridge type.
+    HuC1(HuC1),
+    /// HuC3 cartridge type.
+    HuC3(HuC3),
+    /// Pocket Camera cartridge type.
+    Camera(Camera),
+    /// Sachen cartridge type.
+    Sachen(Sachen),
+    /// Wisdom Tree cartridge type.
+    WisdomTree(WisdomTree),
+}
+
+/// Cartridge body options.
+///
+/// Selects hardware variants that cannot be determined from the header.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Use MBC1 multicart (MBC1M) wiring.
+    ///
+    /// When unspecified, this will be [detected](Mbc1::detect) heuristically.
+    pub mbc1m: Option<bool>,
+    /// Real-time clock time source.
+    ///
+    /// Used by cartridges with a clock, such as the [MBC3](Mbc3::rtc) and
+    /// [HuC3](HuC3::rtc).
+    pub clock: rtc::Source,
 }
 
 impl Body {
@@ -77,6 +143,18 @@ impl Body {
     /// Returns an error if unsupported cartridge type is specified in the
     /// header.
     pub fn new(head: &Header, rom: &[Byte]) -> Result<Self> {
+        Self::with(head, rom, &Options::default())
+    }
+
+    /// Constructs a new `Body` with the provided options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if unsupported cartridge type is specified in the
+    /// header.
+    pub fn with(head: &Header, rom: &[Byte], opts: &Options) -> Result<Self> {
+        // Detect hardware variants
+        let mbc1m = opts.mbc1m.unwrap_or_else(|| Mbc1::detect(rom));
         // Initialize ROM
         let rom = make::rom(head, rom);
         if !rom.is_empty() {
@@ -88,13 +166,36 @@ impl Body {
             trace!("RAM:\n{ram}", ram = hexd::Printer::<Byte>::new(0, &ram));
         }
         // Construct body
-        match &head.info {
+        let body = match &head.info {
             &Info::Bare { .. } => Ok(Body::Bare(Bare::new(rom, ram))),
+            &Info::Mbc1 { .. } if mbc1m => Ok(Body::Mbc1(Mbc1::multicart(rom, ram))),
             &Info::Mbc1 { .. } => Ok(Body::Mbc1(Mbc1::new(rom, ram))),
-            &Info::Mbc3 { .. } => Ok(Body::Mbc3(Mbc3::new(rom, ram))),
-            &Info::Mbc5 { .. } => Ok(Body::Mbc5(Mbc5::new(rom, ram))),
+            &Info::Mbc2 { .. } => Ok(Body::Mbc2(Mbc2::new(rom))),
+            &Info::Mbc3 { rtc: false, .. } => Ok(Body::Mbc3(Mbc3::new(rom, ram))),
+            &Info::Mbc3 { rtc: true, .. } => Ok(Body::Mbc3(Mbc3::with_rtc(rom, ram))),
+            &Info::Mbc5 { vib: false, .. } => Ok(Body::Mbc5(Mbc5::new(rom, ram))),
+            &Info::Mbc5 { vib: true, .. } => Ok(Body::Mbc5(Mbc5::with_rumble(rom, ram))),
+            &Info::Mbc7 => Ok(Body::Mbc7(Mbc7::new(rom))),
+            &Info::Mmm01 { .. } => Ok(Body::Mmm01(Mmm01::new(rom, ram))),
+            &Info::HuC1 => Ok(Body::HuC1(HuC1::new(rom, ram))),
+            &Info::HuC3 => Ok(Body::HuC3(HuC3::new(rom, ram))),
+            &Info::Camera => Ok(Body::Camera(Camera::new(rom, ram))),
+            &Info::Sachen1 => Ok(Body::Sachen(Sachen::mmc1(rom, ram))),
+            &Info::Sachen2 => Ok(Body::Sachen(Sachen::mmc2(rom, ram))),
+            &Info::WisdomTree => Ok(Body::WisdomTree(WisdomTree::new(rom, ram))),
             kind => Err(Error::Unsupported(kind.clone())),
+        }?;
+        // Configure real-time clock
+        match &body {
+            Body::Mbc3(mbc) => {
+                if let Some(rtc) = mbc.rtc() {
+                    rtc.borrow_mut().set_source(opts.clock);
+                }
+            }
+            Body::HuC3(mbc) => mbc.rtc().borrow_mut().set_source(opts.clock),
+            _ => (),
         }
+        Ok(body)
     }
 }
 
@@ -105,7 +206,7 @@ mod make {
     use log::{info, warn};
     use rugby_arch::Byte;
 
-    use super::{Data, Header};
+    use super::{Data, Header, Info};
 
     /// Constructs a new ROM.
     pub fn rom(head: &Header, rom: &[Byte]) -> Data {
@@ -139,7 +240,11 @@ mod make {
 
     /// Constructs a new RAM.
     pub fn ram(head: &Header) -> Data {
-        if head.info.has_ram() && head.ramsz == 0 {
+        // MBC2 and MBC7 use built-in RAM, so do not specify a size
+        if head.info.has_ram()
+            && head.ramsz == 0
+            && !matches!(head.info, Info::Mbc2 { .. } | Info::Mbc7)
+        {
             warn!("cartridge supports RAM, but specified size is zero");
         }
         if !head.info.has_ram() && head.ramsz > 0 {
@@ -157,8 +262,16 @@ impl Block for Body {
         match self {
             Body::Bare(mbc) => mbc.ready(),
             Body::Mbc1(mbc) => mbc.ready(),
+            Body::Mbc2(mbc) => mbc.ready(),
             Body::Mbc3(mbc) => mbc.ready(),
             Body::Mbc5(mbc) => mbc.ready(),
+            Body::Mbc7(mbc) => mbc.ready(),
+            Body::Mmm01(mbc) => mbc.ready(),
+            Body::HuC1(mbc) => mbc.ready(),
+            Body::HuC3(mbc) => mbc.ready(),
+            Body::Camera(mbc) => mbc.ready(),
+            Body::Sachen(mbc) => mbc.ready(),
+            Body::WisdomTree(mbc) => mbc.ready(),
         }
     }
 
@@ -166,8 +279,16 @@ impl Block for Body {
         match self {
             Body::Bare(mbc) => mbc.cycle(),
             Body::Mbc1(mbc) => mbc.cycle(),
+            Body::Mbc2(mbc) => mbc.cycle(),
             Body::Mbc3(mbc) => mbc.cycle(),
             Body::Mbc5(mbc) => mbc.cycle(),
+            Body::Mbc7(mbc) => mbc.cycle(),
+            Body::Mmm01(mbc) => mbc.cycle(),
+            Body::HuC1(mbc) => mbc.cycle(),
+            Body::HuC3(mbc) => mbc.cycle(),
+            Body::Camera(mbc) => mbc.cycle(),
+            Body::Sachen(mbc) => mbc.cycle(),
+            Body::WisdomTree(mbc) => mbc.cycle(),
         }
     }
 
@@ -175,8 +296,16 @@ impl Block for Body {
         match self {
             Body::Bare(mbc) => mbc.reset(),
             Body::Mbc1(mbc) => mbc.reset(),
+            Body::Mbc2(mbc) => mbc.reset(),
             Body::Mbc3(mbc) => mbc.reset(),
             Body::Mbc5(mbc) => mbc.reset(),
+            Body::Mbc7(mbc) => mbc.reset(),
+            Body::Mmm01(mbc) => mbc.reset(),
+            Body::HuC1(mbc) => mbc.reset(),
+            Body::HuC3(mbc) => mbc.reset(),
+            Body::Camera(mbc) => mbc.reset(),
+            Body::Sachen(mbc) => mbc.reset(),
+            Body::WisdomTree(mbc) => mbc.reset(),
         }
     }
 }
@@ -186,8 +315,16 @@ impl Mbc for Body {
         match self {
             Body::Bare(mbc) => mbc.rom(),
             Body::Mbc1(mbc) => mbc.rom(),
+            Body::Mbc2(mbc) => mbc.rom(),
             Body::Mbc3(mbc) => mbc.rom(),
             Body::Mbc5(mbc) => mbc.rom(),
+            Body::Mbc7(mbc) => mbc.rom(),
+            Body::Mmm01(mbc) => mbc.rom(),
+            Body::HuC1(mbc) => mbc.rom(),
+            Body::HuC3(mbc) => mbc.rom(),
+            Body::Camera(mbc) => mbc.rom(),
+            Body::Sachen(mbc) => mbc.rom(),
+            Body::WisdomTree(mbc) => mbc.rom(),
         }
     }
 
@@ -195,8 +332,16 @@ impl Mbc for Body {
         match self {
             Body::Bare(mbc) => mbc.ram(),
             Body::Mbc1(mbc) => mbc.ram(),
+            Body::Mbc2(mbc) => mbc.ram(),
             Body::Mbc3(mbc) => mbc.ram(),
             Body::Mbc5(mbc) => mbc.ram(),
+            Body::Mbc7(mbc) => mbc.ram(),
+            Body::Mmm01(mbc) => mbc.ram(),
+            Body::HuC1(mbc) => mbc.ram(),
+            Body::HuC3(mbc) => mbc.ram(),
+            Body::Camera(mbc) => mbc.ram(),
+            Body::Sachen(mbc) => mbc.ram(),
+            Body::WisdomTree(mbc) => mbc.ram(),
         }
     }
 
@@ -205,8 +350,16 @@ impl Mbc for Body {
         match self {
             Body::Bare(mbc) => mbc.flash(buf),
             Body::Mbc1(mbc) => mbc.flash(buf),
+            Body::Mbc2(mbc) => mbc.flash(buf),
             Body::Mbc3(mbc) => mbc.flash(buf),
             Body::Mbc5(mbc) => mbc.flash(buf),
+            Body::Mbc7(mbc) => mbc.flash(buf),
+            Body::Mmm01(mbc) => mbc.flash(buf),
+            Body::HuC1(mbc) => mbc.flash(buf),
+            Body::HuC3(mbc) => mbc.flash(buf),
+            Body::Camera(mbc) => mbc.flash(buf),
+            Body::Sachen(mbc) => mbc.flash(buf),
+            Body::WisdomTree(mbc) => mbc.flash(buf),
         }
     }
 
@@ -215,8 +368,16 @@ impl Mbc for Body {
         match self {
             Body::Bare(mbc) => mbc.dump(buf),
             Body::Mbc1(mbc) => mbc.dump(buf),
+            Body::Mbc2(mbc) => mbc.dump(buf),
             Body::Mbc3(mbc) => mbc.dump(buf),
             Body::Mbc5(mbc) => mbc.dump(buf),
+            Body::Mbc7(mbc) => mbc.dump(buf),
+            Body::Mmm01(mbc) => mbc.dump(buf),
+            Body::HuC1(mbc) => mbc.dump(buf),
+            Body::HuC3(mbc) => mbc.dump(buf),
+            Body::Camera(mbc) => mbc.dump(buf),
+            Body::Sachen(mbc) => mbc.dump(buf),
+            Body::WisdomTree(mbc) => mbc.dump(buf),
         }
     }
 }
@@ -232,3 +393,40 @@ impl Mmio for Body {
         assert!(bus.unmap(&self.ram()));
     }
 }
+
+/// Cartridge ROM is not saved, as it cannot be modified.
+impl Snapshot for Body {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        match self {
+            Body::Bare(mbc) => mbc.save(buf),
+            Body::Mbc1(mbc) => mbc.save(buf),
+            Body::Mbc2(mbc) => mbc.save(buf),
+            Body::Mbc3(mbc) => mbc.save(buf),
+            Body::Mbc5(mbc) => mbc.save(buf),
+            Body::Mbc7(mbc) => mbc.save(buf),
+            Body::Mmm01(mbc) => mbc.save(buf),
+            Body::HuC1(mbc) => mbc.save(buf),
+            Body::HuC3(mbc) => mbc.save(buf),
+            Body::Camera(mbc) => mbc.save(buf),
+            Body::Sachen(mbc) => mbc.save(buf),
+            Body::WisdomTree(mbc) => mbc.save(buf),
+        }
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        match self {
+            Body::Bare(mbc) => mbc.restore(buf),
+            Body::Mbc1(mbc) => mbc.restore(buf),
+            Body::Mbc2(mbc) => mbc.restore(buf),
+            Body::Mbc3(mbc) => mbc.restore(buf),
+            Body::Mbc5(mbc) => mbc.restore(buf),
+            Body::Mbc7(mbc) => mbc.restore(buf),
+            Body::Mmm01(mbc) => mbc.restore(buf),
+            Body::HuC1(mbc) => mbc.restore(buf),
+            Body::HuC3(mbc) => mbc.restore(buf),
+            Body::Camera(mbc) => mbc.restore(buf),
+            Body::Sachen(mbc) => mbc.restore(buf),
+            Body::WisdomTree(mbc) => mbc.restore(buf),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Real-time clock timekeeping.
+//!
+//! Shared by cartridges with a real-time clock, such as the [MBC3](super::Mbc3)
+//! and [HuC3](super::HuC3).
+
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::warn;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::Byte;
+
+use crate::dmg::FREQ;
+
+/// Clock time source.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Source {
+    /// Emulated cycles.
+    ///
+    /// Time advances along with the emulator, such that the clock remains
+    /// deterministic.
+    #[default]
+    Cycle,
+    /// Wall-clock time.
+    ///
+    /// Time advances with the host's system clock, regardless of emulation
+    /// speed.
+    Wall,
+}
+
+/// Clock timekeeper.
+///
+/// Measures the passage of time in whole seconds from the configured
+/// [source](Source), leaving the counters themselves to each clock.
+#[derive(Debug)]
+pub(super) struct Timer {
+    /// Time source.
+    src: Source,
+    /// Sub-second cycle counter.
+    pub(super) sub: u32,
+    /// Wall-clock reference.
+    pub(super) now: SystemTime,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self {
+            src: Source::default(),
+            sub: u32::default(),
+            now: SystemTime::now(),
+        }
+    }
+}
+
+impl Timer {
+    /// Gets the time source.
+    pub(super) fn source(&self) -> Source {
+        self.src
+    }
+
+    /// Sets the time source.
+    ///
+    /// Callers should [synchronize](Self::sync) beforehand, as any wall-clock
+    /// time not yet accounted for is discarded.
+    pub(super) fn set_source(&mut self, src: Source) {
+        self.src = src;
+        self.now = SystemTime::now();
+    }
+
+    /// Advances by a single cycle, returning whether a second has elapsed.
+    pub(super) fn cycle(&mut self) -> bool {
+        if self.src != Source::Cycle {
+            return false;
+        }
+        self.sub += 1;
+        if self.sub < FREQ {
+            return false;
+        }
+        self.sub = 0;
+        true
+    }
+
+    /// Synchronizes with the wall-clock time, returning the elapsed seconds.
+    pub(super) fn sync(&mut self) -> u64 {
+        if self.src != Source::Wall {
+            return 0;
+        }
+        let Ok(time) = self.now.elapsed() else {
+            return 0;
+        };
+        let secs = time.as_secs();
+        self.now = self
+            .now
+            .checked_add(Duration::from_secs(secs))
+            .unwrap_or_else(SystemTime::now);
+        secs
+    }
+
+    /// Loads a little-endian UNIX timestamp, returning the seconds elapsed
+    /// since.
+    ///
+    /// Returns `None` if the timestamp is out of range, in which case the clock
+    /// data should be ignored.
+    pub(super) fn flash(&mut self, time: &[Byte]) -> Option<u64> {
+        let mut data = [0; 8];
+        data[..time.len()].copy_from_slice(time);
+        let time = Duration::from_secs(u64::from_le_bytes(data));
+        let Some(then) = UNIX_EPOCH.checked_add(time) else {
+            warn!("ignored invalid clock data (timestamp out of range)");
+            return None;
+        };
+        self.sub = 0;
+        self.now = SystemTime::now();
+        Some(
+            self.now
+                .duration_since(then)
+                .map_or(0, |time| time.as_secs()),
+        )
+    }
+
+    /// Saves the current time as a little-endian UNIX timestamp.
+    pub(super) fn dump() -> [Byte; 8] {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs())
+            .to_le_bytes()
+    }
+}
+
+/// The time source is not saved, as it is configured by the frontend.
+impl Snapshot for Timer {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.sub.save(buf)?;
+        self.now.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.sub.restore(buf)?;
+        self.now.restore(buf)
+    }
+}
+
+/// Reads a clock footer, returning the number of bytes read.
+///
+/// Stops short of filling `data` only once the end of the input is reached.
+pub(super) fn read(buf: &mut impl io::Read, data: &mut [Byte]) -> io::Result<usize> {
+    let mut nbytes = 0;
+    while nbytes < data.len() {
+        match buf.read(&mut data[nbytes..]) {
+            Ok(0) => break,
+            Ok(n) => nbytes += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(nbytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timer_flash_works() {
+        let mut tmr = Timer::default();
+        // Timestamps may be 32-bit
+        let secs = tmr.flash(&Timer::dump()[..4]).unwrap();
+        assert!(secs < 60);
+        // Timestamps out of range are ignored
+        assert_eq!(tmr.flash(&u64::MAX.to_le_bytes()), None);
+    }
+}
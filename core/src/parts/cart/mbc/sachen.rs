@@ -0,0 +1,391 @@
+use std::io;
+
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::Device;
+use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::{Data, Mbc};
+use crate::parts::cart::header::LOGO;
+
+/// Cartridge RAM.
+type Ram = rugby_arch::mem::Ram<Data>;
+
+/// [Sachen][sachen] cartridge type.
+///
+/// Unlicensed mapper which scrambles accesses to the header's page of ROM,
+/// allowing the boot ROM to display a custom logo while still verifying
+/// Nintendo's. Two revisions exist:
+/// - MMC1, which displays its own logo on every model; and
+/// - MMC2, which passes through Nintendo's logo on the DMG, only substituting
+///   its own on the CGB.
+///
+/// # Note
+///
+/// The CGB lock stage of MMC2 is entered upon the boot ROM's first access to
+/// work RAM, which is not visible to the cartridge. As such, MMC2 is always
+/// modelled as though attached to a DMG.
+///
+/// [sachen]: https://gbdev.gg8.se/wiki/articles/Sachen_MMC1
+#[derive(Debug)]
+pub struct Sachen {
+    ctl: Control,
+    rom: Shared<Rom>,
+    ram: Shared<Ram>,
+}
+
+impl Sachen {
+    /// Constructs a new `Sachen` MMC1.
+    #[must_use]
+    pub fn mmc1(rom: Data, ram: Data) -> Self {
+        Self::new(Control::default(), rom, ram)
+    }
+
+    /// Constructs a new `Sachen` MMC2.
+    #[must_use]
+    pub fn mmc2(rom: Data, ram: Data) -> Self {
+        Self::new(
+            Control {
+                mmc2: true,
+                ..Default::default()
+            },
+            rom,
+            ram,
+        )
+    }
+
+    /// Constructs a new `Sachen` using the provided registers.
+    fn new(ctl: Control, rom: Data, ram: Data) -> Self {
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::from(ram)),
+            ctl,
+        }
+    }
+
+    /// Checks if the ROM appears to be a Sachen cartridge.
+    ///
+    /// As the header is scrambled, these cartridges are detected heuristically
+    /// by Nintendo's logo only being present once the header is
+    /// [unscrambled](Self::unscramble).
+    #[must_use]
+    pub fn detect(rom: &[Byte]) -> bool {
+        rom.len() >= 0x8000
+            && rom[0x104..0x134] != LOGO
+            && (0x104..0x134)
+                .map(|addr| rom[usize::from(unscramble(addr))])
+                .eq(LOGO)
+    }
+
+    /// Unscrambles the ROM's header page.
+    ///
+    /// Produces a copy of the ROM as seen once the cartridge is unlocked. As
+    /// only addresses within the page are permuted, the global checksum is
+    /// unaffected.
+    #[must_use]
+    pub fn unscramble(rom: &[Byte]) -> Data {
+        let mut out = Data::from(rom);
+        if let Some(page) = out.get_mut(0x100..0x200) {
+            for (addr, byte) in (0x100..0x200).zip(page) {
+                *byte = rom[usize::from(unscramble(addr))];
+            }
+        }
+        out
+    }
+}
+
+/// Unscrambles an address within the header's page.
+///
+/// Address bits 0 and 6 are swapped, as are bits 1 and 4.
+fn unscramble(addr: Word) -> Word {
+    addr & 0xffac
+        | (addr & 0x40) >> 6
+        | (addr & 0x10) >> 3
+        | (addr & 0x02) << 3
+        | (addr & 0x01) << 6
+}
+
+impl Block for Sachen {
+    fn reset(&mut self) {
+        self.ctl.reset();
+    }
+}
+
+impl Mbc for Sachen {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        buf.read(self.ram.borrow_mut().inner_mut())
+    }
+
+    fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        buf.write(self.ram.borrow().inner())
+    }
+}
+
+impl Snapshot for Sachen {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.base.save(buf)?;
+        self.ctl.rom.save(buf)?;
+        self.ctl.mask.save(buf)?;
+        self.ctl.lock.save(buf)?;
+        self.ram.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.base.restore(buf)?;
+        self.ctl.rom.restore(buf)?;
+        self.ctl.mask.restore(buf)?;
+        self.ctl.lock.restore(buf)?;
+        self.ram.restore(buf)
+    }
+}
+
+snapshot!(RomBank, Lock);
+
+/// Sachen registers.
+///
+/// |     Address     | Size | Name | Description           |
+/// |:---------------:|------|------|-----------------------|
+/// | `$0000..=$1FFF` | 8bit | BASE | Base ROM Bank Number. |
+/// | `$2000..=$3FFF` | 8bit | ROM  | ROM Bank Number.      |
+/// | `$4000..=$5FFF` | 8bit | MASK | ROM Bank Mask.        |
+///
+/// The base and mask may only be written while bits 5:4 of the ROM bank
+/// number are set. Bits of the bank number selected by the mask are taken from
+/// the base instead.
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+struct Control {
+    /// Base ROM Bank Number.
+    base: Shared<RomBank>,
+    /// ROM Bank Number.
+    rom: Shared<RomBank>,
+    /// ROM Bank Mask.
+    mask: Shared<RomBank>,
+    /// Logo lock.
+    lock: Shared<Lock>,
+    /// MMC2 revision.
+    mmc2: bool,
+}
+
+impl Control {
+    /// Checks if the base and mask registers are writable.
+    fn unlocked(&self) -> bool {
+        self.rom.borrow().0 & 0x30 == 0x30
+    }
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        self.base.take();
+        self.rom.take();
+        self.mask.take();
+        self.lock.take();
+    }
+}
+
+/// ROM Bank Number.
+#[derive(Debug, Default)]
+struct RomBank(Byte);
+
+impl Memory for RomBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RomBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = value;
+        debug!("ROM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// Logo lock.
+///
+/// Counts accesses to the header's page until the boot ROM has finished
+/// reading the logo.
+#[derive(Debug, Default)]
+struct Lock(Byte);
+
+impl Lock {
+    /// Number of header accesses before unlocking.
+    const COUNT: Byte = 0x31;
+
+    /// Checks if the cartridge is locked.
+    fn locked(&self) -> bool {
+        self.0 < Self::COUNT
+    }
+
+    /// Records an access to the header's page.
+    fn tick(&mut self) {
+        self.0 += 1;
+        if !self.locked() {
+            debug!("unlocked");
+        }
+    }
+}
+
+/// Sachen ROM.
+#[derive(Debug)]
+struct Rom {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let mask = self.ctl.mask.load();
+        let base = self.ctl.base.load() & mask;
+        let bank = if addr < 0x4000 {
+            // Bank 0
+            base
+        } else {
+            // Bank N (zero check applies before masking)
+            let rom = match self.ctl.rom.load() {
+                0 => 1,
+                x => x,
+            };
+            base | rom & !mask
+        };
+        let bank = usize::from(bank);
+        let addr = usize::from(addr);
+        (bank << 14 | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, mut addr: Word) -> Result<Byte> {
+        // Scramble header accesses
+        if addr & 0xff00 == 0x0100 {
+            let mut lock = self.ctl.lock.borrow_mut();
+            if lock.locked() {
+                lock.tick();
+                // MMC1 forces A7 high while locked
+                if lock.locked() && !self.ctl.mmc2 {
+                    addr |= 0x0080;
+                }
+            }
+            addr = unscramble(addr);
+        }
+        let index = match addr {
+            0x0000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        trace!("Sachen::write({addr:#06x}, {data:#04x})");
+        match addr {
+            // Base ROM Bank Number
+            0x0000..=0x1fff if self.ctl.unlocked() => {
+                // ctl.base <- data
+                self.ctl.base.store(data);
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // ctl.rom <- data
+                self.ctl.rom.store(data);
+            }
+            // ROM Bank Mask
+            0x4000..=0x5fff if self.ctl.unlocked() => {
+                // ctl.mask <- data
+                self.ctl.mask.store(data);
+            }
+            // Unmapped
+            0x0000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a ROM with each bank tagged by its number.
+    fn rom(banks: usize) -> Data {
+        let mut rom = vec![0; banks << 14];
+        for (idx, bank) in rom.chunks_mut(0x4000).enumerate() {
+            bank[0] = u8::try_from(idx).unwrap();
+        }
+        rom.into()
+    }
+
+    #[test]
+    fn rom_banking_works() {
+        let mbc = Sachen::mmc1(rom(64), Data::default());
+        let mut rom = mbc.rom();
+        // Bank 0 maps to bank 1
+        assert_eq!(rom.read(0x4000), Ok(0x01));
+        // Base and mask require bits 5:4 of the bank number
+        rom.write(0x0000, 0x20).unwrap();
+        rom.write(0x4000, 0xf0).unwrap();
+        assert_eq!(rom.read(0x0000), Ok(0x00));
+        rom.write(0x2000, 0x30).unwrap();
+        rom.write(0x0000, 0x20).unwrap();
+        rom.write(0x4000, 0xf0).unwrap();
+        // Masked bits are taken from the base
+        assert_eq!(rom.read(0x0000), Ok(0x20));
+        rom.write(0x2000, 0x05).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x25));
+        rom.write(0x2000, 0x00).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x21));
+    }
+
+    #[test]
+    fn logo_scrambling_works() {
+        let mut rom = vec![0; 0x8000];
+        // Store the custom logo where it is read while locked
+        for addr in 0x0104..0x0134 {
+            rom[usize::from(unscramble(addr | 0x80))] = 0xaa;
+        }
+        // Store Nintendo's logo where it is read once unlocked
+        for (addr, byte) in (0x0104..0x0134).zip(LOGO) {
+            rom[usize::from(unscramble(addr))] = byte;
+        }
+        assert!(Sachen::detect(&rom));
+        assert_eq!(Sachen::unscramble(&rom)[0x104..0x134], LOGO);
+        // MMC1 displays the custom logo, then unlocks
+        let mbc = Sachen::mmc1(rom.clone().into(), Data::default());
+        let rom1 = mbc.rom();
+        assert!((0x0104..0x0134).all(|addr| rom1.read(addr) == Ok(0xaa)));
+        assert!((0x0104..0x0134)
+            .map(|addr| rom1.read(addr).unwrap())
+            .eq(LOGO));
+        // MMC2 always passes through Nintendo's logo
+        let mbc = Sachen::mmc2(rom.into(), Data::default());
+        let rom2 = mbc.rom();
+        assert!((0x0104..0x0134)
+            .map(|addr| rom2.read(addr).unwrap())
+            .eq(LOGO));
+    }
+}
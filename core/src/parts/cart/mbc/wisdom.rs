@@ -0,0 +1,223 @@
+use std::io;
+
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::Device;
+use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::{Data, Mbc};
+
+/// Cartridge RAM.
+type Ram = rugby_arch::mem::Ram<Data>;
+
+/// [Wisdom Tree][wisdom] cartridge type.
+///
+/// Unlicensed mapper which switches the entire 32 KiB address space at once,
+/// selecting the bank using the address written to (rather than the data).
+///
+/// [wisdom]: https://gbdev.gg8.se/wiki/articles/Wisdom_Tree
+#[derive(Debug)]
+pub struct WisdomTree {
+    ctl: Control,
+    rom: Shared<Rom>,
+    ram: Shared<Ram>,
+}
+
+impl WisdomTree {
+    /// Constructs a new `WisdomTree`.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let ctl = Control::default();
+        Self {
+            rom: Shared::new(Rom::new(ctl.clone(), rom)),
+            ram: Shared::new(Ram::from(ram)),
+            ctl,
+        }
+    }
+
+    /// Checks if the ROM appears to be a Wisdom Tree cartridge.
+    ///
+    /// As these cartridges declare either no mapper or an invalid one in their
+    /// header, they are instead detected heuristically by the publisher's name
+    /// appearing within a ROM larger than 32 KiB.
+    #[must_use]
+    pub fn detect(rom: &[Byte]) -> bool {
+        rom.len() > 0x8000
+            && matches!(rom.get(0x147), Some(0x00 | 0xc0))
+            && rom
+                .windows(11)
+                .any(|name| name == b"WISDOM TREE" || name == b"WISDOM\0TREE")
+    }
+}
+
+impl Block for WisdomTree {
+    fn reset(&mut self) {
+        self.ctl.reset();
+    }
+}
+
+impl Mbc for WisdomTree {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        buf.read(self.ram.borrow_mut().inner_mut())
+    }
+
+    fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        buf.write(self.ram.borrow().inner())
+    }
+}
+
+impl Snapshot for WisdomTree {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.ctl.rom.save(buf)?;
+        self.ram.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.ctl.rom.restore(buf)?;
+        self.ram.restore(buf)
+    }
+}
+
+snapshot!(RomBank);
+
+/// Wisdom Tree registers.
+///
+/// |     Address     | Size | Name | Description      |
+/// |:---------------:|------|------|------------------|
+/// | `$0000..=$3FFF` | 6bit | ROM  | ROM Bank Number. |
+///
+/// The bank number is taken from the lower bits of the address written to.
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+struct Control {
+    /// ROM Bank Number.
+    rom: Shared<RomBank>,
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        self.rom.take();
+    }
+}
+
+/// ROM Bank Number.
+#[derive(Debug, Default)]
+struct RomBank(Byte);
+
+impl Memory for RomBank {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, addr: Word, _: Byte) -> Result<()> {
+        self.store(addr.to_le_bytes()[0]);
+        Ok(())
+    }
+}
+
+impl Register for RomBank {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        self.0 & 0x3f
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = 0x3f & value;
+        debug!("ROM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// Wisdom Tree ROM.
+#[derive(Debug)]
+struct Rom {
+    ctl: Control,
+    mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(ctl: Control, mem: Data) -> Self {
+        Self { ctl, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: Word) -> usize {
+        let bank = usize::from(self.ctl.rom.load());
+        let addr = usize::from(addr);
+        (bank << 15 | addr & 0x7fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        let index = match addr {
+            0x0000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        trace!("WisdomTree::write({addr:#06x}, {data:#04x})");
+        match addr {
+            // ROM Bank Number
+            0x0000..=0x3fff => {
+                // ctl.rom[5:0] <- addr[5:0]
+                self.ctl.rom.write(addr, data)?;
+            }
+            // Unmapped
+            0x4000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rom_banking_works() {
+        // Tag each ROM bank with its number
+        let mut rom = vec![0; 8 << 15];
+        for (idx, bank) in rom.chunks_mut(0x8000).enumerate() {
+            bank[0x0000] = u8::try_from(idx).unwrap();
+            bank[0x4000] = u8::try_from(idx).unwrap();
+        }
+        let mbc = WisdomTree::new(rom.into(), Data::default());
+        let mut rom = mbc.rom();
+        assert_eq!(rom.read(0x0000), Ok(0x00));
+        // Bank is selected by the address, not the data
+        rom.write(0x0005, 0x00).unwrap();
+        assert_eq!(rom.read(0x0000), Ok(0x05));
+        assert_eq!(rom.read(0x4000), Ok(0x05));
+        rom.write(0x3f03, 0xff).unwrap();
+        assert_eq!(rom.read(0x4000), Ok(0x03));
+        // Upper half does not select banks
+        rom.write(0x4001, 0x00).unwrap();
+        assert_eq!(rom.read(0x0000), Ok(0x03));
+    }
+
+    #[test]
+    fn detect_works() {
+        let mut rom = vec![0; 0x10000];
+        assert!(!WisdomTree::detect(&rom));
+        rom[0x8000..0x800b].copy_from_slice(b"WISDOM TREE");
+        assert!(WisdomTree::detect(&rom));
+        assert!(!WisdomTree::detect(&rom[..0x8000]));
+        rom[0x147] = 0x01;
+        assert!(!WisdomTree::detect(&rom));
+    }
+}
@@ -10,12 +10,16 @@
 //! [header]: https://gbdev.io/pandocs/The_Cartridge_Header.html
 //! [mbcs]:   https://gbdev.io/pandocs/MBCs.html
 
+use std::borrow::Cow;
+use std::io;
+
 use rugby_arch::mio::{Bus, Mmio};
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte};
 use thiserror::Error;
 
 use self::header::{Header, Info};
-use self::mbc::Body;
+use self::mbc::{rtc, Body, Options, Sachen, WisdomTree};
 
 pub mod header;
 pub mod mbc;
@@ -40,11 +44,7 @@ impl Cartridge {
     /// Returns an error when the cartridge could not be constructed. This will
     /// either be due to invalid header bytes or an unsupported cartridge type.
     pub fn new(rom: &[Byte]) -> Result<Self> {
-        let head = Header::new(rom)?;
-        Ok(Self {
-            body: Body::new(&head, rom)?,
-            head,
-        })
+        Builder::new().build(rom)
     }
 
     /// Constructs a new `Cartridge` explicitly checking the entire header.
@@ -54,11 +54,7 @@ impl Cartridge {
     /// Returns an error when the cartridge could not be constructed. This will
     /// either be due to invalid header bytes or an unsupported cartridge type.
     pub fn checked(rom: &[Byte]) -> Result<Self> {
-        let head = Header::checked(rom)?;
-        Ok(Self {
-            body: Body::new(&head, rom)?,
-            head,
-        })
+        Builder::new().check(true).build(rom)
     }
 
     /// Constructs a new `Cartridge` without checking the header.
@@ -68,11 +64,13 @@ impl Cartridge {
     /// Returns an error when the cartridge could not be constructed. This will
     /// either be due to missing header bytes or an unsupported cartridge type.
     pub fn unchecked(rom: &[Byte]) -> Result<Self> {
-        let head = Header::unchecked(rom)?;
-        Ok(Self {
-            body: Body::new(&head, rom)?,
-            head,
-        })
+        Builder::new().check(false).build(rom)
+    }
+
+    /// Constructs a [`Builder`] for a `Cartridge`.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::new()
     }
 
     /// Gets the cartridge's title.
@@ -101,6 +99,14 @@ impl Cartridge {
 }
 
 impl Block for Cartridge {
+    fn ready(&self) -> bool {
+        self.body.ready()
+    }
+
+    fn cycle(&mut self) {
+        self.body.cycle();
+    }
+
     fn reset(&mut self) {
         self.body.reset();
     }
@@ -116,6 +122,148 @@ impl Mmio for Cartridge {
     }
 }
 
+impl Snapshot for Cartridge {
+    fn save(&self, buf: &mut impl io::Write) -> io::Result<()> {
+        self.body.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl io::Read) -> io::Result<()> {
+        self.body.restore(buf)
+    }
+}
+
+/// Cartridge builder.
+///
+/// Allows for configuring how a [`Cartridge`] is constructed.
+#[derive(Debug, Default)]
+pub struct Builder {
+    /// Header integrity checking.
+    check: Option<bool>,
+    /// Forced cartridge type.
+    info: Option<Info>,
+    /// Body options.
+    opts: Options,
+}
+
+impl Builder {
+    /// Constructs a new `Builder`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the header is checked.
+    ///
+    /// When enabled, the cartridge's integrity is [checked](Header::checked)
+    /// using both checksums. When disabled, invalid header bytes are
+    /// [ignored](Header::unchecked). Otherwise, the header is parsed
+    /// [normally](Header::new).
+    #[must_use]
+    pub fn check(mut self, check: bool) -> Self {
+        self.check = Some(check);
+        self
+    }
+
+    /// Forces whether MBC1 multicart (MBC1M) wiring is used.
+    ///
+    /// By default, this is [detected](mbc::Mbc1::detect) heuristically.
+    #[must_use]
+    pub fn mbc1m(mut self, mbc1m: bool) -> Self {
+        self.opts.mbc1m = Some(mbc1m);
+        self
+    }
+
+    /// Sets the time source of the cartridge's real-time clock, if present.
+    ///
+    /// By default, the clock advances along with [emulated
+    /// cycles](rtc::Source::Cycle).
+    #[must_use]
+    pub fn clock(mut self, src: rtc::Source) -> Self {
+        self.opts.clock = src;
+        self
+    }
+
+    /// Forces the cartridge type, overriding the header.
+    ///
+    /// By default, the cartridge type is parsed from the header, except for
+    /// unlicensed cartridges which are [detected](detect) heuristically.
+    #[must_use]
+    pub fn mapper(mut self, info: Info) -> Self {
+        self.info = Some(info);
+        self
+    }
+
+    /// Builds a `Cartridge` from the provided ROM.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the cartridge could not be constructed. This will
+    /// either be due to invalid header bytes or an unsupported cartridge type.
+    pub fn build(self, rom: &[Byte]) -> Result<Cartridge> {
+        // Determine unlicensed cartridge type
+        let info = self.info.or_else(|| detect(rom));
+        // Parse header
+        let view = match info {
+            Some(Info::Sachen1 | Info::Sachen2) => Cow::Owned(Sachen::unscramble(rom).into()),
+            _ => Cow::Borrowed(rom),
+        };
+        let head = match self.check {
+            None => Header::new(locate(&view)),
+            Some(true) => Header::checked(locate(&view)),
+            Some(false) => Header::unchecked(locate(&view)),
+        };
+        let mut head = match (head, &info) {
+            // Ignore invalid cartridge types when overridden
+            (Err(header::Error::Kind(_)), Some(_)) => Header::unchecked(locate(&view)),
+            (head, _) => head,
+        }?;
+        if let Some(info) = info {
+            // Unlicensed headers may misreport the ROM size
+            head.romsz = head.romsz.max(rom.len().next_power_of_two());
+            head.info = info;
+        }
+        Ok(Cartridge {
+            body: Body::with(&head, rom, &self.opts)?,
+            head,
+        })
+    }
+}
+
+/// Locates the cartridge header within the ROM.
+///
+/// MMM01 multicarts boot into a menu stored in the last 32 KiB of ROM, whose
+/// header describes the entire cartridge. (The header at the start of ROM
+/// instead belongs to the first game.) To avoid misidentifying other
+/// cartridges, the menu's header checksum must also be valid.
+fn locate(rom: &[Byte]) -> &[Byte] {
+    rom.len()
+        .checked_sub(0x8000)
+        .filter(|&base| base > 0)
+        .map(|base| &rom[base..])
+        .filter(|menu| matches!(menu.get(0x147), Some(0x0b..=0x0d)))
+        .filter(|menu| menu.get(0x14d) == Some(&header::hchk(menu)))
+        .unwrap_or(rom)
+}
+
+/// Detects unlicensed cartridge types.
+///
+/// Unlicensed cartridges cannot be identified by their header's cartridge
+/// type, so are instead detected using heuristics:
+/// - [Wisdom Tree](WisdomTree::detect) cartridges by their publisher's name.
+/// - [Sachen](Sachen::detect) cartridges by their scrambled logo. The MMC2
+///   revision is assumed for cartridges supporting the CGB.
+#[must_use]
+pub fn detect(rom: &[Byte]) -> Option<Info> {
+    if WisdomTree::detect(rom) {
+        Some(Info::WisdomTree)
+    } else if Sachen::detect(rom) {
+        let cgb = Sachen::unscramble(rom)[0x143] & 0x80 != 0;
+        Some(if cgb { Info::Sachen2 } else { Info::Sachen1 })
+    } else {
+        None
+    }
+}
+
 /// A convenient type alias for [`Result`](std::result::Result).
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -17,7 +17,7 @@
 
 use rugby_arch::Byte;
 
-use super::{help, Cpu, Error, Execute, Flag, Ime, Result, Status};
+use super::{help, Cpu, Error, Execute, Flag, Idu, Ime, Result, Status};
 
 type Return = Result<Option<Operation>>;
 
@@ -136,6 +136,104 @@ impl Execute for Operation {
     }
 }
 
+/// Implements [`Snapshot`](rugby_arch::snap::Snapshot) for an operation's
+/// state.
+///
+/// Each variant is saved by its index, followed by its fields.
+macro_rules! snapshot {
+    ($name:ident { $($var:ident $(($($arg:ident),+))?),+ $(,)? }) => {
+        const _: () = {
+            use std::io::{self, ErrorKind, Read, Write};
+
+            use rugby_arch::snap::Snapshot;
+
+            /// Variant index.
+            #[repr(u8)]
+            enum Tag {
+                $($var),+
+            }
+
+            impl Snapshot for $name {
+                fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+                    match self {
+                        $(Self::$var $(($($arg),+))? => {
+                            (Tag::$var as u8).save(buf)?;
+                            $($($arg.save(buf)?;)+)?
+                        })+
+                    }
+                    Ok(())
+                }
+
+                fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+                    let tag = u8::decode(buf)?;
+                    $(if tag == Tag::$var as u8 {
+                        *self = Self::$var $(($({
+                            let $arg = Snapshot::decode(buf)?;
+                            $arg
+                        }),+))?;
+                        return Ok(());
+                    })+
+                    Err(ErrorKind::InvalidData.into())
+                }
+            }
+        };
+    };
+}
+
+snapshot!(Operation {
+    Adc(op),
+    Add(op),
+    Addw(op),
+    And(op),
+    Bit(op),
+    Call(op),
+    Ccf(op),
+    Cp(op),
+    Cpl(op),
+    Daa(op),
+    Dec(op),
+    Decw(op),
+    Di(op),
+    Ei(op),
+    Halt(op),
+    Inc(op),
+    Incw(op),
+    Int(op),
+    Jp(op),
+    Jr(op),
+    Ld(op),
+    Ldh(op),
+    Ldw(op),
+    Nop(op),
+    Or(op),
+    Pop(op),
+    Prefix(op),
+    Push(op),
+    Res(op),
+    Ret(op),
+    Reti(op),
+    Rl(op),
+    Rla(op),
+    Rlc(op),
+    Rlca(op),
+    Rr(op),
+    Rra(op),
+    Rrc(op),
+    Rrca(op),
+    Rst(op),
+    Sbc(op),
+    Scf(op),
+    Set(op),
+    Sla(op),
+    Sra(op),
+    Srl(op),
+    Stop(op),
+    Sub(op),
+    Swap(op),
+    Unused(op),
+    Xor(op),
+});
+
 /// Arithmetic add with carry.
 pub(super) mod adc;
 
@@ -30,6 +30,8 @@ impl From<Adc> for Operation {
     }
 }
 
+snapshot!(Adc { Fetch, Execute(op2) });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
@@ -30,6 +30,8 @@ impl From<Add> for Operation {
     }
 }
 
+snapshot!(Add { Fetch, Execute(op2) });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
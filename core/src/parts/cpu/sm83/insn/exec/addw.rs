@@ -12,9 +12,9 @@ pub enum Addw {
     #[default]
     Fetch,
     Execute(u16, u16),
-    Fetch0xE8,
     Delay0xE8(Byte),
     Execute0xE8(Byte),
+    Done,
 }
 
 impl Execute for Addw {
@@ -23,9 +23,9 @@ impl Execute for Addw {
         match self {
             Self::Fetch             => fetch(code, cpu),
             Self::Execute(op1, op2) => execute(code, cpu, op1, op2),
-            Self::Fetch0xE8         => fetch_0xe8(code, cpu),
             Self::Delay0xE8(e8)     => delay_0xe8(code, cpu, e8),
             Self::Execute0xE8(e8)   => execute_0xe8(code, cpu, e8),
+            Self::Done              => done(code, cpu),
         }
     }
 }
@@ -36,6 +36,8 @@ impl From<Addw> for Operation {
     }
 }
 
+snapshot!(Addw { Fetch, Execute(op1, op2), Delay0xE8(e8), Execute0xE8(e8), Done });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
@@ -64,8 +66,8 @@ fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
             Ok(Some(Addw::Execute(op1, op2).into()))
         }
         0xe8 => {
-            // Proceed
-            Ok(Some(Addw::Fetch0xE8.into()))
+            // Continue
+            fetch_0xe8(code, cpu)
         }
         code => Err(Error::Opcode(code)),
     }
@@ -117,6 +119,11 @@ fn execute_0xe8(_: Byte, cpu: &mut Cpu, e8: Byte) -> Return {
     Flag::C.set(flags, 0x00ff < (op1 & 0x00ff) + (op2 & 0x00ff));
     cpu.reg.f.store(*flags);
 
+    // Proceed
+    Ok(Some(Addw::Done.into()))
+}
+
+fn done(_: Byte, _: &mut Cpu) -> Return {
     // Finish
     Ok(None)
 }
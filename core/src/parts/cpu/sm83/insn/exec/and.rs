@@ -32,6 +32,8 @@ impl From<And> for Operation {
     }
 }
 
+snapshot!(And { Fetch, Execute(op2) });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
@@ -38,6 +38,8 @@ impl From<Call> for Operation {
     }
 }
 
+snapshot!(Call { Fetch0, Fetch1(lsb), Check(a16), Push0(a16), Push1(a16), Jump(a16) });
+
 fn fetch0(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
@@ -28,6 +28,8 @@ impl From<Ccf> for Operation {
     }
 }
 
+snapshot!(Ccf { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x3f {
@@ -30,6 +30,8 @@ impl From<Cp> for Operation {
     }
 }
 
+snapshot!(Cp { Fetch, Execute(op2) });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
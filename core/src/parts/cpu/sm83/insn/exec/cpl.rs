@@ -28,6 +28,8 @@ impl From<Cpl> for Operation {
     }
 }
 
+snapshot!(Cpl { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x2f {
@@ -28,6 +28,8 @@ impl From<Daa> for Operation {
     }
 }
 
+snapshot!(Daa { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x27 {
@@ -1,7 +1,7 @@
 use rugby_arch::reg::Register;
 use rugby_arch::Byte;
 
-use super::{Cpu, Error, Execute, Operation, Return};
+use super::{Cpu, Error, Execute, Idu, Operation, Return};
 
 pub const fn default() -> Operation {
     Operation::Decw(Decw::Fetch)
@@ -30,6 +30,8 @@ impl From<Decw> for Operation {
     }
 }
 
+snapshot!(Decw { Fetch, Execute(op1) });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     let op1 = match code {
@@ -39,6 +41,8 @@ fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
         0x3b => cpu.reg.sp.load(),
         code => return Err(Error::Opcode(code)),
     };
+    // Signal IDU access
+    cpu.signal(Idu::Write(op1));
 
     // Proceed
     Ok(Some(Decw::Execute(op1).into()))
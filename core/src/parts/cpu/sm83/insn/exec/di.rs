@@ -27,6 +27,8 @@ impl From<Di> for Operation {
     }
 }
 
+snapshot!(Di { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0xf3 {
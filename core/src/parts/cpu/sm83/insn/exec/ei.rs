@@ -27,6 +27,8 @@ impl From<Ei> for Operation {
     }
 }
 
+snapshot!(Ei { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0xfb {
@@ -27,6 +27,8 @@ impl From<Halt> for Operation {
     }
 }
 
+snapshot!(Halt { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x76 {
@@ -32,6 +32,8 @@ impl From<Inc> for Operation {
     }
 }
 
+snapshot!(Inc { Fetch, Execute(op1), Done });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
@@ -1,7 +1,7 @@
 use rugby_arch::reg::Register;
 use rugby_arch::Byte;
 
-use super::{Cpu, Error, Execute, Ime, Operation, Return};
+use super::{Cpu, Execute, Ime, Operation, Return};
 use crate::parts::pic::Interrupt;
 
 #[derive(Clone, Debug, Default)]
@@ -11,18 +11,18 @@ pub enum Int {
     Nop,
     Push0,
     Push1,
-    Jump,
+    Jump(Byte),
 }
 
 impl Execute for Int {
     #[rustfmt::skip]
     fn exec(self, code: Byte, cpu: &mut Cpu) -> Return {
         match self {
-            Self::Fetch => fetch(code, cpu),
-            Self::Nop   => nop(code, cpu),
-            Self::Push0 => push0(code, cpu),
-            Self::Push1 => push1(code, cpu),
-            Self::Jump  => jump(code, cpu),
+            Self::Fetch      => fetch(code, cpu),
+            Self::Nop        => nop(code, cpu),
+            Self::Push0      => push0(code, cpu),
+            Self::Push1      => push1(code, cpu),
+            Self::Jump(addr) => jump(code, cpu, addr),
         }
     }
 }
@@ -33,6 +33,14 @@ impl From<Int> for Operation {
     }
 }
 
+snapshot!(Int {
+    Fetch,
+    Nop,
+    Push0,
+    Push1,
+    Jump(addr)
+});
+
 fn fetch(_: Byte, cpu: &mut Cpu) -> Return {
     // Disable interrupts
     cpu.etc.ime = Ime::Disabled;
@@ -60,6 +68,17 @@ fn push0(_: Byte, cpu: &mut Cpu) -> Return {
 }
 
 fn push1(_: Byte, cpu: &mut Cpu) -> Return {
+    // Acknowledge the interrupt
+    //
+    // NOTE: The interrupt is only selected after pushing the MSB, which may
+    //       have overwritten IE. Should this leave no interrupts pending,
+    //       dispatch is cancelled and instead jumps to `$0000`.
+    let int = cpu.int.fetch();
+    if let Some(int) = int {
+        cpu.int.clear(int);
+    }
+    let addr = int.map_or(0x00, Interrupt::handler);
+
     // Load LSB
     let lsb = cpu.reg.pc.load().to_le_bytes()[0];
 
@@ -67,13 +86,12 @@ fn push1(_: Byte, cpu: &mut Cpu) -> Return {
     cpu.pushbyte(lsb);
 
     // Proceed
-    Ok(Some(Int::Jump.into()))
+    Ok(Some(Int::Jump(addr).into()))
 }
 
-fn jump(code: Byte, cpu: &mut Cpu) -> Return {
+fn jump(_: Byte, cpu: &mut Cpu, addr: Byte) -> Return {
     // Perform jump
-    let int = Interrupt::try_from(code).map_err(|_| Error::Opcode(code))?;
-    cpu.reg.pc.store(int.handler() as u16);
+    cpu.reg.pc.store(addr.into());
 
     // Finish
     Ok(None)
@@ -34,6 +34,8 @@ impl From<Jp> for Operation {
     }
 }
 
+snapshot!(Jp { Fetch0, Fetch1(a8), Check(a16), Jump(a16) });
+
 fn fetch0(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
@@ -32,6 +32,8 @@ impl From<Jr> for Operation {
     }
 }
 
+snapshot!(Jr { Fetch, Check(e8), Jump(e8) });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
@@ -1,7 +1,7 @@
 use rugby_arch::reg::Register;
 use rugby_arch::Byte;
 
-use super::{help, Cpu, Error, Execute, Operation, Return};
+use super::{help, Cpu, Error, Execute, Idu, Operation, Return};
 
 pub const fn default() -> Operation {
     Operation::Ld(Ld::Fetch)
@@ -43,6 +43,8 @@ impl From<Ld> for Operation {
     }
 }
 
+snapshot!(Ld { Fetch, Update, Write(op2), Byte(op2), Fetch0, Fetch1(lsb), Word(a16), Done });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
@@ -60,6 +62,10 @@ fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
             let op2 = cpu.reg.a.load();
             // Write A
             cpu.write(r16, op2);
+            // Signal IDU access
+            if matches!(code, 0x22 | 0x32) {
+                cpu.signal(Idu::Write(r16));
+            }
             // Proceed
             match code {
                 0x02 | 0x12 => Ok(Some(Ld::Done.into())),
@@ -79,6 +85,10 @@ fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
             .load();
             // Read r16
             let op2 = cpu.read(r16);
+            // Signal IDU access
+            if matches!(code, 0x2a | 0x3a) {
+                cpu.signal(Idu::Read(r16));
+            }
             // Proceed
             Ok(Some(Ld::Byte(op2).into()))
         }
@@ -34,6 +34,8 @@ impl From<Ldh> for Operation {
     }
 }
 
+snapshot!(Ldh { Fetch, Read(a8), Write(a8), Delay });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
@@ -45,6 +45,18 @@ impl From<Ldw> for Operation {
     }
 }
 
+snapshot!(Ldw {
+    Fetch,
+    ReadLsb,
+    ReadMsb(lsb),
+    Execute(op2),
+    Load(a16),
+    WriteLsb(a16, sp),
+    WriteMsb(a16, sp),
+    Add(e8),
+    Delay,
+});
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     #[allow(clippy::match_same_arms)]
@@ -109,12 +121,12 @@ fn execute(code: Byte, cpu: &mut Cpu, op2: u16) -> Return {
     Ok(None)
 }
 
-fn load(_: Byte, cpu: &mut Cpu, a16: u16) -> Return {
+fn load(code: Byte, cpu: &mut Cpu, a16: u16) -> Return {
     // Load SP
     let sp = cpu.reg.sp.load();
 
-    // Proceed
-    Ok(Some(Ldw::WriteLsb(a16, sp).into()))
+    // Continue
+    write_lsb(code, cpu, a16, sp)
 }
 
 fn write_lsb(_: Byte, cpu: &mut Cpu, a16: u16, sp: u16) -> Return {
@@ -130,8 +142,8 @@ fn write_msb(_: Byte, cpu: &mut Cpu, mut a16: u16, sp: u16) -> Return {
     a16 = a16.wrapping_add(1);
     cpu.write(a16, sp.to_le_bytes()[1]);
 
-    // Finish
-    Ok(None)
+    // Proceed
+    Ok(Some(Ldw::Delay.into()))
 }
 
 fn add(_: Byte, cpu: &mut Cpu, e8: Byte) -> Return {
@@ -27,6 +27,8 @@ impl From<Nop> for Operation {
     }
 }
 
+snapshot!(Nop { Execute });
+
 fn execute(code: Byte, _cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x00 {
@@ -32,6 +32,8 @@ impl From<Or> for Operation {
     }
 }
 
+snapshot!(Or { Fetch, Execute(op2) });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
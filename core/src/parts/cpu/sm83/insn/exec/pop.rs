@@ -1,7 +1,7 @@
 use rugby_arch::reg::Register;
 use rugby_arch::Byte;
 
-use super::{Cpu, Error, Execute, Operation, Return};
+use super::{Cpu, Error, Execute, Idu, Operation, Return};
 
 pub const fn default() -> Operation {
     Operation::Pop(Pop::Pop1)
@@ -32,7 +32,11 @@ impl From<Pop> for Operation {
     }
 }
 
+snapshot!(Pop { Pop1, Pop0, Delay });
+
 fn pop1(code: Byte, cpu: &mut Cpu) -> Return {
+    // Signal IDU access
+    cpu.signal(Idu::Read(cpu.reg.sp.load()));
     // Pop LSB <- [SP++]
     let mut lsb = cpu.popbyte();
     if code == 0xf1 {
@@ -54,6 +58,8 @@ fn pop1(code: Byte, cpu: &mut Cpu) -> Return {
 }
 
 fn pop0(code: Byte, cpu: &mut Cpu) -> Return {
+    // Signal IDU access
+    cpu.signal(Idu::Read(cpu.reg.sp.load()));
     // Pop MSB <- [SP++]
     let msb = cpu.popbyte();
 
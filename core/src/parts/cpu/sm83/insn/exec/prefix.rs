@@ -27,6 +27,8 @@ impl From<Prefix> for Operation {
     }
 }
 
+snapshot!(Prefix { Fetch });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0xcb {
@@ -1,7 +1,7 @@
 use rugby_arch::reg::Register;
 use rugby_arch::Byte;
 
-use super::{Cpu, Error, Execute, Operation, Return};
+use super::{Cpu, Error, Execute, Idu, Operation, Return};
 
 pub const fn default() -> Operation {
     Operation::Push(Push::Fetch)
@@ -34,8 +34,16 @@ impl From<Push> for Operation {
     }
 }
 
-fn fetch(_: Byte, _: &mut Cpu) -> Return {
-    // Delay by 1 cycle
+snapshot!(Push {
+    Fetch,
+    Push0,
+    Push1,
+    Delay
+});
+
+fn fetch(_: Byte, cpu: &mut Cpu) -> Return {
+    // Signal IDU access
+    cpu.signal(Idu::Write(cpu.reg.sp.load()));
 
     // Proceed
     Ok(Some(Push::Push0.into()))
@@ -52,6 +60,8 @@ fn push0(code: Byte, cpu: &mut Cpu) -> Return {
     }
     .load();
 
+    // Signal IDU access
+    cpu.signal(Idu::Write(cpu.reg.sp.load()));
     // Push MSB
     cpu.pushbyte(msb);
 
@@ -70,6 +80,8 @@ fn push1(code: Byte, cpu: &mut Cpu) -> Return {
     }
     .load();
 
+    // Signal IDU access
+    cpu.signal(Idu::Write(cpu.reg.sp.load()));
     // Push LSB
     cpu.pushbyte(lsb);
 
@@ -36,6 +36,8 @@ impl From<Ret> for Operation {
     }
 }
 
+snapshot!(Ret { Check, Pop0, Pop1(pc0), Jump(pc), Done });
+
 fn check(code: Byte, cpu: &mut Cpu) -> Return {
     // Evaluate condition
     let flags = &cpu.reg.f.load();
@@ -34,6 +34,8 @@ impl From<Reti> for Operation {
     }
 }
 
+snapshot!(Reti { Pop0, Pop1(pc0), Jump(pc), Done });
+
 fn pop0(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0xd9 {
@@ -67,7 +69,9 @@ fn jump(_: Byte, cpu: &mut Cpu, pc: u16) -> Return {
 
 fn done(_: Byte, cpu: &mut Cpu) -> Return {
     // Enable interrupts
-    cpu.etc.ime = Ime::WillEnable;
+    //
+    // NOTE: Unlike `EI`, this takes effect immediately.
+    cpu.etc.ime = Ime::Enabled;
 
     // Finish
     Ok(None)
@@ -28,6 +28,8 @@ impl From<Rla> for Operation {
     }
 }
 
+snapshot!(Rla { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x17 {
@@ -28,6 +28,8 @@ impl From<Rlca> for Operation {
     }
 }
 
+snapshot!(Rlca { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x07 {
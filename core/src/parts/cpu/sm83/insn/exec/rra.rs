@@ -28,6 +28,8 @@ impl From<Rra> for Operation {
     }
 }
 
+snapshot!(Rra { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x1f {
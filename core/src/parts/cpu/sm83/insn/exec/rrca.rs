@@ -28,6 +28,8 @@ impl From<Rrca> for Operation {
     }
 }
 
+snapshot!(Rrca { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x0f {
@@ -34,6 +34,8 @@ impl From<Rst> for Operation {
     }
 }
 
+snapshot!(Rst { Fetch, Push0(v8), Push1(v8), Jump(v8) });
+
 fn fetch(code: Byte, _: &mut Cpu) -> Return {
     // Check opcode
     let v8 = match code {
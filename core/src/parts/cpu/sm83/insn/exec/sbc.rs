@@ -30,6 +30,8 @@ impl From<Sbc> for Operation {
     }
 }
 
+snapshot!(Sbc { Fetch, Execute(op2) });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
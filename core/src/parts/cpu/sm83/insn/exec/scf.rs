@@ -28,6 +28,8 @@ impl From<Scf> for Operation {
     }
 }
 
+snapshot!(Scf { Execute });
+
 fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x37 {
@@ -31,6 +31,8 @@ impl From<Set> for Operation {
     }
 }
 
+snapshot!(Set { Fetch, Execute(op2), Delay });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
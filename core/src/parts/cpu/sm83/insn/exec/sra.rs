@@ -32,6 +32,8 @@ impl From<Sra> for Operation {
     }
 }
 
+snapshot!(Sra { Fetch, Execute(op1), Delay });
+
 fn fetch(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     match code {
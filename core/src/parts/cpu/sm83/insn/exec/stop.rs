@@ -1,6 +1,6 @@
 use rugby_arch::Byte;
 
-use super::{Cpu, Error, Execute, Operation, Return};
+use super::{Cpu, Error, Execute, Operation, Return, Status};
 
 pub const fn default() -> Operation {
     Operation::Stop(Stop::Execute)
@@ -27,7 +27,9 @@ impl From<Stop> for Operation {
     }
 }
 
-fn execute(code: Byte, _: &mut Cpu) -> Return {
+snapshot!(Stop { Execute });
+
+fn execute(code: Byte, cpu: &mut Cpu) -> Return {
     // Check opcode
     if code != 0x10 {
         return Err(Error::Opcode(code));
@@ -35,10 +37,52 @@ fn execute(code: Byte, _: &mut Cpu) -> Return {
 
     // Execute STOP
     // <https://gbdev.io/pandocs/imgs/gb_stop.png>
-    #[cfg(debug_assertions)]
-    return Err(Error::Unimplemented(code));
+    let held = cpu.read(0xff00) & 0x0f != 0x0f;
+    let pending = cpu.int.pending();
+
+    // Check for held buttons
+    if held {
+        // Enter HALT, unless an interrupt is pending
+        if !pending {
+            // Skip the next byte
+            cpu.fetchbyte();
+            cpu.etc.run = Status::Halted;
+        }
+        // Do not execute STOP
+        return Ok(None);
+    }
+
+    // Reset DIV
+    cpu.write(0xff04, 0x00);
+
+    // Perform speed switch (CGB)
+    if cpu.spd.borrow().armed() {
+        return switch(cpu, pending);
+    }
+
+    // Skip the next byte, unless an interrupt is pending
+    if !pending {
+        cpu.fetchbyte();
+    }
+    // Enter STOP
+    cpu.etc.run = Status::Stopped;
+
+    // Finish
+    Ok(None)
+}
+
+fn switch(cpu: &mut Cpu, pending: bool) -> Return {
+    // Switch speed
+    cpu.spd.borrow_mut().switch();
+
+    // Pause for 2050 M-cycles while the clock stabilizes
+    cpu.etc.pause = 2050;
+
+    // Skip the next byte, unless an interrupt is pending
+    if !pending {
+        cpu.fetchbyte();
+    }
 
     // Finish
-    #[allow(unreachable_code)]
     Ok(None)
 }
@@ -27,6 +27,8 @@ impl From<Unused> for Operation {
     }
 }
 
+snapshot!(Unused { Execute });
+
 fn execute(code: Byte, _: &mut Cpu) -> Return {
     Err(Error::Illegal(code))
 }
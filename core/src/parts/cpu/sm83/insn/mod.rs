@@ -1,13 +1,15 @@
 //! Instruction set architecture.
 
 use std::fmt::{Debug, Display};
+use std::io::{self, ErrorKind, Read, Write};
 
 use log::trace;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::Byte;
 use thiserror::Error;
 
 use self::exec::Operation;
-use super::{Cpu, Flag, Ime, Status};
+use super::{Cpu, Flag, Idu, Ime, Status};
 use crate::parts::pic::Interrupt;
 
 mod exec;
@@ -93,6 +95,35 @@ impl From<Interrupt> for Instruction {
     }
 }
 
+impl Snapshot for Instruction {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        // Identify the instruction's decoding
+        let kind = match self.oper {
+            Operation::Int(_) => 2,
+            _ => u8::from(self.repr != table::DECODE[usize::from(self.code)].repr),
+        };
+        kind.save(buf)?;
+        self.code.save(buf)?;
+        self.oper.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        let kind = u8::decode(buf)?;
+        let code = Byte::decode(buf)?;
+        // Decode the instruction...
+        *self = match kind {
+            0 => Self::decode(code),
+            1 => Self::prefix(code),
+            2 => Interrupt::try_from(code)
+                .map(Self::int)
+                .map_err(|_| ErrorKind::InvalidData)?,
+            _ => return Err(ErrorKind::InvalidData.into()),
+        };
+        // ... then resume its operation
+        self.oper.restore(buf)
+    }
+}
+
 /// Helper functions.
 mod help {
     use rugby_arch::reg::Register;
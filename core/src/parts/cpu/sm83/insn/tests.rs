@@ -1,15 +1,37 @@
 use std::iter;
 
+use rugby_arch::mem::Ram;
 use rugby_arch::mio::Bus;
+use rugby_arch::reg::Register;
+use rugby_arch::Shared;
 
 use super::*;
-use crate::parts::pic::Pic;
+use crate::parts::pic::{Interrupt, Pic};
 
 fn setup() -> Cpu {
     let bus = Bus::default();
     Cpu::new(bus, Pic::new().line)
 }
 
+/// Sets up a CPU to execute `STOP`, with the provided joypad state.
+fn setup_stop(joyp: Byte, pending: bool) -> Cpu {
+    let mut pic = Pic::new();
+    if pending {
+        pic.reg.ena.store(0x1f);
+        pic.line.clone().raise(Interrupt::VBlank);
+    }
+    let bus = Bus::from([
+        (0x0000..=0x00ff, Shared::dev(Ram::from([0x00; 0x100]))),
+        (0xff00..=0xff00, Shared::dev(Ram::from([joyp]))),
+        (0xff04..=0xff04, Shared::dev(Ram::from([0xab]))),
+    ]);
+    let mut cpu = Cpu::new(bus, pic.line);
+    // Execute STOP
+    let insn = Instruction::decode(0x10).exec(&mut cpu).unwrap();
+    assert!(insn.is_none());
+    cpu
+}
+
 #[test]
 fn cycle_count() {
     // Test each instruction
@@ -68,9 +90,49 @@ fn prefix_cycle_count() {
     }
 }
 
+#[test]
+fn stop_works() {
+    let cpu = setup_stop(0xff, false);
+    // Enters STOP, skipping the next byte
+    assert_eq!(cpu.status(), Status::Stopped);
+    assert_eq!(cpu.reg.pc, 0x0001);
+    // Resets DIV
+    assert_eq!(cpu.read(0xff04), 0x00);
+}
+
+#[test]
+fn stop_pending_works() {
+    let cpu = setup_stop(0xff, true);
+    // Enters STOP, without skipping the next byte
+    assert_eq!(cpu.status(), Status::Stopped);
+    assert_eq!(cpu.reg.pc, 0x0000);
+    // Resets DIV
+    assert_eq!(cpu.read(0xff04), 0x00);
+}
+
+#[test]
+fn stop_held_works() {
+    let cpu = setup_stop(0xfe, false);
+    // Enters HALT, skipping the next byte
+    assert_eq!(cpu.status(), Status::Halted);
+    assert_eq!(cpu.reg.pc, 0x0001);
+    // Leaves DIV untouched
+    assert_eq!(cpu.read(0xff04), 0xab);
+}
+
+#[test]
+fn stop_held_pending_works() {
+    let cpu = setup_stop(0xfe, true);
+    // Executes as a 1-byte opcode
+    assert_eq!(cpu.status(), Status::Enabled);
+    assert_eq!(cpu.reg.pc, 0x0000);
+    // Leaves DIV untouched
+    assert_eq!(cpu.read(0xff04), 0xab);
+}
+
 const CYCLES: [usize; 0x100] = [
     1, 3, 2, 2, 1, 1, 2, 1, 5, 2, 2, 2, 1, 1, 2, 1, // 0x00
-    0, 3, 2, 2, 1, 1, 2, 1, 3, 2, 2, 2, 1, 1, 2, 1, // 0x10
+    1, 3, 2, 2, 1, 1, 2, 1, 3, 2, 2, 2, 1, 1, 2, 1, // 0x10
     3, 3, 2, 2, 1, 1, 2, 1, 2, 2, 2, 2, 1, 1, 2, 1, // 0x20
     3, 3, 2, 2, 3, 3, 3, 1, 2, 2, 2, 2, 1, 1, 2, 1, // 0x30
     1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1, // 0x40
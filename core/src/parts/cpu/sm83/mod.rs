@@ -1,12 +1,14 @@
 //! SM83 processor core.
 
 use std::fmt::{Debug, Display};
+use std::io::{self, ErrorKind, Read, Write};
 
 use log::{debug, error, trace, warn};
-use rugby_arch::mem::Memory;
+use rugby_arch::mem::{Memory, Result};
 use rugby_arch::mio::Bus;
 use rugby_arch::reg::{Port, Register};
-use rugby_arch::{Block, Byte, Word};
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
 
 use self::insn::Instruction;
 use crate::api::part::proc::Processor;
@@ -73,6 +75,10 @@ pub struct Cpu {
     bus: Bus,
     /// Interrupt line.
     int: pic::Line,
+    /// Speed switch (CGB).
+    pub spd: Shared<Speed>,
+    /// IDU access line.
+    pub idu: Shared<Option<Idu>>,
 }
 
 /// Processor internals.
@@ -80,6 +86,11 @@ pub struct Cpu {
 struct Internal {
     /// Prefix instruction.
     prefix: bool,
+    /// Instruction register.
+    ///
+    /// Holds the next opcode, prefetched during the final cycle of the
+    /// previous instruction.
+    ir: Option<Byte>,
     /// Execution stage.
     stage: Stage,
     /// Running status.
@@ -90,6 +101,11 @@ struct Internal {
     ///
     /// [bug]: https://gbdev.io/pandocs/halt.html#halt-bug
     halt_bug: bool,
+    /// Speed switch pause.
+    ///
+    /// Remaining cycles for which execution is paused while the clock
+    /// stabilizes after a speed switch (CGB).
+    pause: u16,
 }
 
 impl Internal {
@@ -107,6 +123,8 @@ impl Cpu {
             etc: Internal::default(),
             bus,
             int,
+            spd: Shared::default(),
+            idu: Shared::default(),
         }
     }
 
@@ -116,6 +134,12 @@ impl Cpu {
         &self.etc.stage
     }
 
+    /// Gets the CPU's running status.
+    #[must_use]
+    pub fn status(&self) -> Status {
+        self.etc.run
+    }
+
     /// Read the byte at the given address.
     #[must_use]
     pub fn read(&self, addr: Word) -> Byte {
@@ -175,6 +199,11 @@ impl Cpu {
         byte
     }
 
+    /// Signal an IDU access.
+    fn signal(&mut self, idu: Idu) {
+        self.idu.replace(Some(idu));
+    }
+
     /// Push to the byte at SP.
     fn pushbyte(&mut self, byte: Byte) {
         // Increment SP
@@ -192,12 +221,55 @@ impl Block for Cpu {
     }
 
     fn cycle(&mut self) {
+        // Wait out speed switch pause
+        if self.etc.pause > 0 {
+            self.etc.pause -= 1;
+            return;
+        }
         self.etc.stage = std::mem::take(&mut self.etc.stage).exec(self);
     }
 
     fn reset(&mut self) {
         self.etc.reset();
         self.reg.reset();
+        self.spd.take();
+        self.idu.take();
+    }
+}
+
+impl Snapshot for Cpu {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.reg.save(buf)?;
+        self.etc.save(buf)?;
+        self.spd.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.reg.restore(buf)?;
+        self.etc.restore(buf)?;
+        self.spd.restore(buf)
+    }
+}
+
+impl Snapshot for Internal {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.prefix.save(buf)?;
+        self.ir.save(buf)?;
+        self.stage.save(buf)?;
+        self.run.save(buf)?;
+        self.ime.save(buf)?;
+        self.halt_bug.save(buf)?;
+        self.pause.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.prefix.restore(buf)?;
+        self.ir.restore(buf)?;
+        self.stage.restore(buf)?;
+        self.run.restore(buf)?;
+        self.ime.restore(buf)?;
+        self.halt_bug.restore(buf)?;
+        self.pause.restore(buf)
     }
 }
 
@@ -252,7 +324,10 @@ impl Port<Word> for Cpu {
             Select16::DE => self.reg.de_mut().store(value),
             Select16::HL => self.reg.hl_mut().store(value),
             Select16::SP => self.reg.sp.store(value),
-            Select16::PC => self.reg.pc.store(value),
+            Select16::PC => {
+                self.reg.pc.store(value);
+                self.etc.ir.take();
+            }
         }
     }
 }
@@ -264,9 +339,9 @@ impl Processor for Cpu {
         if let Stage::Execute(insn) = &self.etc.stage {
             insn.clone()
         } else {
-            // Fetch opcode at PC
+            // Fetch opcode at PC, unless already prefetched
             let pc = self.reg.pc.load();
-            let op = self.read(pc);
+            let op = self.etc.ir.unwrap_or_else(|| self.read(pc));
             // Construct instruction
             Instruction::decode(op)
         }
@@ -274,6 +349,7 @@ impl Processor for Cpu {
 
     fn goto(&mut self, addr: Word) {
         self.reg.pc.store(addr);
+        self.etc.ir.take();
     }
 
     fn exec(&mut self, code: Byte) {
@@ -300,6 +376,107 @@ impl Processor for Cpu {
     }
 }
 
+/// Speed switch (KEY1).
+///
+/// Used on the CGB to prepare switching between normal and double speed, which
+/// is then performed upon executing `STOP`.
+///
+/// | Bit | Name          | Use |
+/// |-----|---------------|-----|
+/// |  7  | Current speed | R   |
+/// |  0  | Switch armed  | R/W |
+#[derive(Debug, Default)]
+pub struct Speed {
+    /// Switch armed.
+    armed: bool,
+    /// Double speed.
+    double: bool,
+}
+
+impl Speed {
+    /// Checks if a speed switch is armed.
+    #[must_use]
+    pub fn armed(&self) -> bool {
+        self.armed
+    }
+
+    /// Checks if running at double speed.
+    #[must_use]
+    pub fn double(&self) -> bool {
+        self.double
+    }
+
+    /// Performs the armed speed switch.
+    pub fn switch(&mut self) {
+        self.armed = false;
+        self.double = !self.double;
+        debug!(
+            "switched to {} speed",
+            if self.double { "double" } else { "normal" }
+        );
+    }
+}
+
+impl Snapshot for Speed {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.armed.save(buf)?;
+        self.double.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.armed.restore(buf)?;
+        self.double.restore(buf)
+    }
+}
+
+impl Memory for Speed {
+    fn read(&self, _: Word) -> Result<Byte> {
+        Ok(self.load())
+    }
+
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Speed {
+    type Value = Byte;
+
+    fn load(&self) -> Self::Value {
+        0x7e | Byte::from(self.double) << 7 | Byte::from(self.armed)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.armed = value & 0x01 != 0;
+    }
+}
+
+/// Increment/decrement unit (IDU) access.
+///
+/// Whenever the IDU increments or decrements a register pair, its value is
+/// placed on the address bus. Should this point into OAM while the PPU is
+/// scanning it, the [OAM corruption bug][bug] is triggered on the DMG.
+///
+/// [bug]: https://gbdev.io/pandocs/OAM_Corruption_Bug.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Idu {
+    /// Increment or decrement, possibly alongside a write.
+    Write(Word),
+    /// Increment or decrement alongside a read.
+    Read(Word),
+}
+
+impl Idu {
+    /// Gets the address placed on the bus.
+    #[must_use]
+    pub fn addr(self) -> Word {
+        match self {
+            Self::Write(addr) | Self::Read(addr) => addr,
+        }
+    }
+}
+
 /// Processor registers.
 ///
 /// | Size | Name | Description                   |
@@ -410,6 +587,24 @@ impl Block for Control {
     }
 }
 
+impl Snapshot for Control {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        [
+            self.a, self.f, self.b, self.c, self.d, self.e, self.h, self.l,
+        ]
+        .save(buf)?;
+        [self.sp, self.pc].save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        [
+            self.a, self.f, self.b, self.c, self.d, self.e, self.h, self.l,
+        ] = Snapshot::decode(buf)?;
+        [self.sp, self.pc] = Snapshot::decode(buf)?;
+        Ok(())
+    }
+}
+
 impl Display for Control {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "┌───┬────┬───┬────┐")?;
@@ -557,6 +752,22 @@ pub enum Status {
     Stopped,
 }
 
+impl Snapshot for Status {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        (*self as u8).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = match u8::decode(buf)? {
+            0 => Self::Enabled,
+            1 => Self::Halted,
+            2 => Self::Stopped,
+            _ => return Err(ErrorKind::InvalidData.into()),
+        };
+        Ok(())
+    }
+}
+
 /// Processor execution stage.
 #[derive(Clone, Debug, Default)]
 pub enum Stage {
@@ -569,6 +780,33 @@ pub enum Stage {
     Done,
 }
 
+impl Snapshot for Stage {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        match self {
+            Stage::Fetch => 0u8.save(buf),
+            Stage::Execute(insn) => {
+                1u8.save(buf)?;
+                insn.save(buf)
+            }
+            Stage::Done => 2u8.save(buf),
+        }
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = match u8::decode(buf)? {
+            0 => Stage::Fetch,
+            1 => {
+                let mut insn = Instruction::decode(0x00);
+                insn.restore(buf)?;
+                Stage::Execute(insn)
+            }
+            2 => Stage::Done,
+            _ => return Err(ErrorKind::InvalidData.into()),
+        };
+        Ok(())
+    }
+}
+
 impl Stage {
     fn exec(mut self, cpu: &mut Cpu) -> Self {
         // If done, proceed to fetch this cycle
@@ -583,8 +821,8 @@ impl Stage {
 
             // Handle pending interrupt...
             if let Some(int) = int {
-                // Acknowledge the interrupt
-                cpu.int.clear(int);
+                // Discard the prefetched opcode
+                cpu.etc.ir.take();
                 // Skip `Stage::Fetch`
                 let insn = Instruction::int(int);
                 debug!("{:#06x}: {insn}", int.handler());
@@ -599,9 +837,15 @@ impl Stage {
 
         // If fetch, proceed to execute this cycle
         if let Stage::Fetch = self {
-            // Read the next instruction
+            // Read the next instruction, unless already prefetched
             let pc = cpu.reg.pc.load();
-            let op = cpu.fetchbyte();
+            let op = match cpu.etc.ir.take() {
+                Some(op) => {
+                    cpu.reg.pc.store(pc.wrapping_add(1));
+                    op
+                }
+                None => cpu.fetchbyte(),
+            };
 
             // Decode the instruction
             let insn = if std::mem::take(&mut cpu.etc.prefix) {
@@ -648,7 +892,17 @@ impl Stage {
             // Proceed to next stage
             self = match insn {
                 Ok(Some(insn)) => Stage::Execute(insn),
-                Ok(None) => Stage::Done,
+                Ok(None) => {
+                    // Prefetch the next opcode
+                    //
+                    // NOTE: The SM83 overlaps fetching the next opcode with
+                    //       the final cycle of each instruction.
+                    if cpu.etc.run == Status::Enabled {
+                        let pc = cpu.reg.pc.load();
+                        cpu.etc.ir = Some(cpu.read(pc));
+                    }
+                    Stage::Done
+                }
                 Err(err) => {
                     // Log the error
                     error!("{err}");
@@ -681,3 +935,19 @@ impl Ime {
         self == Self::Enabled
     }
 }
+
+impl Snapshot for Ime {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        (*self as u8).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = match u8::decode(buf)? {
+            0 => Self::Disabled,
+            1 => Self::Enabled,
+            2 => Self::WillEnable,
+            _ => return Err(ErrorKind::InvalidData.into()),
+        };
+        Ok(())
+    }
+}
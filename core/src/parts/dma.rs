@@ -1,9 +1,12 @@
 //! Direct memory access.
 
-use log::{debug, trace, warn};
-use rugby_arch::mem::Memory;
+use std::io::{self, ErrorKind, Read, Write};
+
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::mio::Bus;
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared, Word};
 
 pub use super::ppu::Oam;
@@ -45,8 +48,14 @@ impl Block for Dma {
             State::Off => {
                 unreachable!("cannot to cycle DMA while disabled");
             }
-            State::Req(src) => {
-                // FIXME: Disable OAM
+            State::Req { src, busy } => {
+                // Prepare transfer
+                //
+                // NOTE: A restarted transfer is interrupted immediately, yet
+                //       keeps OAM blocked while the new transfer starts.
+                State::Init { src, busy }
+            }
+            State::Init { src, .. } => {
                 // Initiate transfer
                 trace!("started: 0xfe00 <- {src:#04x}00");
                 State::On { hi: src, lo: 0x00 }
@@ -62,7 +71,6 @@ impl Block for Dma {
                 if usize::from(lo) < self.oam.borrow().inner().len() {
                     State::On { hi, lo }
                 } else {
-                    // FIXME: Enable OAM
                     // Complete transfer
                     debug!("finished: 0xfe00 <- {hi:#04x}00");
                     State::Off
@@ -78,6 +86,16 @@ impl Block for Dma {
     }
 }
 
+impl Snapshot for Dma {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.reg.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.reg.restore(buf)
+    }
+}
+
 /// DMA control register.
 #[derive(Debug, Default)]
 pub struct Control {
@@ -87,18 +105,77 @@ pub struct Control {
     mpage: Byte,
 }
 
+impl Control {
+    /// Checks if OAM is blocked by a transfer.
+    #[must_use]
+    pub fn busy(&self) -> bool {
+        match self.state {
+            State::Off => false,
+            State::Req { busy, .. } | State::Init { busy, .. } => busy,
+            State::On { .. } => true,
+        }
+    }
+
+    /// Gets the address currently being read by a transfer.
+    #[must_use]
+    pub fn addr(&self) -> Option<Word> {
+        match self.state {
+            State::On { hi, lo } => Some(u16::from_be_bytes([hi, lo])),
+            _ => None,
+        }
+    }
+}
+
 impl Block for Control {
     fn reset(&mut self) {
         std::mem::take(self);
     }
 }
 
+impl Snapshot for Control {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        match self.state {
+            State::Off => 0u8.save(buf)?,
+            State::Req { src, busy } => [1, src, busy.into()].save(buf)?,
+            State::Init { src, busy } => [2, src, busy.into()].save(buf)?,
+            State::On { hi, lo } => [3, hi, lo].save(buf)?,
+        }
+        self.mpage.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.state = match u8::decode(buf)? {
+            0 => State::Off,
+            1 => {
+                let [src, busy] = Snapshot::decode(buf)?;
+                State::Req {
+                    src,
+                    busy: busy != 0,
+                }
+            }
+            2 => {
+                let [src, busy] = Snapshot::decode(buf)?;
+                State::Init {
+                    src,
+                    busy: busy != 0,
+                }
+            }
+            3 => {
+                let [hi, lo] = Snapshot::decode(buf)?;
+                State::On { hi, lo }
+            }
+            _ => return Err(ErrorKind::InvalidData.into()),
+        };
+        self.mpage.restore(buf)
+    }
+}
+
 impl Memory for Control {
-    fn read(&self, _: Word) -> rugby_arch::mem::Result<Byte> {
+    fn read(&self, _: Word) -> Result<Byte> {
         Ok(self.load())
     }
 
-    fn write(&mut self, _: Word, data: Byte) -> rugby_arch::mem::Result<()> {
+    fn write(&mut self, _: Word, data: Byte) -> Result<()> {
         self.store(data);
         Ok(())
     }
@@ -112,16 +189,16 @@ impl Register for Control {
     }
 
     fn store(&mut self, value: Self::Value) {
-        match self.state {
-            State::Off => {
-                // Request a new transfer
-                self.state = State::Req(value);
-                debug!("request: 0xfe00 <- {:#04x}00", value);
-            }
-            State::Req(_) | State::On { .. } => {
-                warn!("ignored request; already in progress");
-            }
+        // Request a new transfer, restarting any in progress
+        if let State::Off = self.state {
+            debug!("request: 0xfe00 <- {value:#04x}00");
+        } else {
+            debug!("restart: 0xfe00 <- {value:#04x}00");
         }
+        self.state = State::Req {
+            src: value,
+            busy: self.busy(),
+        };
         // Always update stored value
         self.mpage.store(value);
     }
@@ -134,7 +211,94 @@ enum State {
     #[default]
     Off,
     /// Requested.
-    Req(Byte),
+    Req { src: Byte, busy: bool },
+    /// Starting.
+    Init { src: Byte, busy: bool },
     /// In-progress.
     On { hi: Byte, lo: Byte },
 }
+
+/// DMA bus arbiter.
+///
+/// Guards the CPU's accesses to a bus shared with the DMA. While a transfer
+/// is reading from this bus, any CPU access to it instead observes the address
+/// driven by the DMA. See more details [here][conflicts].
+///
+/// [conflicts]: https://gbdev.io/pandocs/OAM_DMA_Transfer.html#bus-conflicts
+#[derive(Debug)]
+pub struct Arbiter {
+    /// DMA register.
+    reg: Shared<Control>,
+    /// Guarded bus.
+    bus: Shared<Bus>,
+}
+
+impl Arbiter {
+    /// Constructs a new `Arbiter`.
+    #[must_use]
+    pub fn new(reg: Shared<Control>, bus: Shared<Bus>) -> Self {
+        Self { reg, bus }
+    }
+
+    /// Routes an address subject to bus conflicts.
+    ///
+    /// Conflicts are determined by the addresses mapped to the bus, regardless
+    /// of whether the device there responds.
+    fn route(&self, bus: &Bus, addr: Word) -> Word {
+        match self.reg.borrow().addr() {
+            Some(src) if bus.contains(src) && bus.contains(addr) => src,
+            _ => addr,
+        }
+    }
+}
+
+impl Memory for Arbiter {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        let bus = self.bus.borrow();
+        bus.read(self.route(&bus, addr))
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        let mut bus = self.bus.borrow_mut();
+        let addr = self.route(&bus, addr);
+        bus.write(addr, data)
+    }
+}
+
+/// OAM lock.
+///
+/// Blocks the CPU's accesses to OAM while a transfer is in progress, during
+/// which reads return `$FF` and writes are ignored.
+#[derive(Debug)]
+pub struct Lock {
+    /// DMA register.
+    reg: Shared<Control>,
+}
+
+impl Lock {
+    /// Constructs a new `Lock`.
+    #[must_use]
+    pub fn new(reg: Shared<Control>) -> Self {
+        Self { reg }
+    }
+}
+
+/// Accesses are only claimed while blocked, otherwise falling through to the
+/// underlying bus.
+impl Memory for Lock {
+    fn read(&self, _: Word) -> Result<Byte> {
+        if self.reg.borrow().busy() {
+            Ok(0xff)
+        } else {
+            Err(Error::Range)
+        }
+    }
+
+    fn write(&mut self, _: Word, _: Byte) -> Result<()> {
+        if self.reg.borrow().busy() {
+            Ok(())
+        } else {
+            Err(Error::Range)
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! VRAM direct memory access (CGB).
+
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::{Bus, Mmio};
+use rugby_arch::{Block, Byte, Shared, Word};
+
+/// VRAM direct memory access unit.
+///
+/// Copies data to VRAM in blocks of 16 bytes, either all at once
+/// (general-purpose), or one block per horizontal blank. The CPU is halted
+/// while a block is being copied.
+#[derive(Debug)]
+pub struct Hdma {
+    /// HDMA registers.
+    pub reg: Shared<Control>,
+    // Shared
+    bus: Bus,
+}
+
+impl Hdma {
+    /// Number of bytes in a block.
+    const BLOCK: Word = 0x10;
+
+    /// Constructs a new `Hdma`.
+    #[must_use]
+    pub fn new(bus: Bus) -> Self {
+        Self {
+            // Control
+            reg: Shared::default(),
+            // Shared
+            bus,
+        }
+    }
+
+    /// Signals the start of a horizontal blank.
+    ///
+    /// When a horizontal blank transfer is in progress, this allows the next
+    /// block to be copied.
+    pub fn hblank(&mut self) {
+        let mut reg = self.reg.borrow_mut();
+        if let State::HBlank { ref mut ready } = reg.state {
+            *ready = true;
+        }
+    }
+}
+
+impl Block for Hdma {
+    fn ready(&self) -> bool {
+        matches!(
+            self.reg.borrow().state,
+            State::General | State::HBlank { ready: true }
+        )
+    }
+
+    fn cycle(&mut self) {
+        let reg = &mut *self.reg.borrow_mut();
+        // Transfer two bytes
+        for _ in 0..2 {
+            let src = reg.src.wrapping_add(reg.idx);
+            let dst = 0x8000 | (reg.dst.wrapping_add(reg.idx) & 0x1fff);
+            let data = self.bus.read(src).unwrap_or(0xff);
+            let _ = self.bus.write(dst, data);
+            trace!("copied: {dst:#06x} <- {src:#06x}, data: {data:#04x}");
+            reg.idx += 1;
+        }
+        // Check for completed block
+        if reg.idx < Self::BLOCK {
+            return;
+        }
+        reg.idx = 0;
+        reg.src = reg.src.wrapping_add(Self::BLOCK);
+        reg.dst = reg.dst.wrapping_add(Self::BLOCK) & 0x1ff0;
+        reg.len = reg.len.wrapping_sub(1) & 0x7f;
+        // Determine next state
+        reg.state = if reg.len == 0x7f {
+            // Complete transfer
+            debug!("finished");
+            State::Off
+        } else if let State::HBlank { .. } = reg.state {
+            // Wait for next horizontal blank
+            State::HBlank { ready: false }
+        } else {
+            // Continue transfer
+            State::General
+        };
+    }
+
+    fn reset(&mut self) {
+        self.reg.take();
+    }
+}
+
+impl Mmio for Hdma {
+    fn attach(&self, bus: &mut Bus) {
+        bus.map(0xff51..=0xff55, self.reg.clone().into());
+    }
+}
+
+/// HDMA registers.
+///
+/// | Address | Size | Name  | Description                      |
+/// |:-------:|------|-------|----------------------------------|
+/// | `$FF51` | Byte | HDMA1 | Source address (HI)              |
+/// | `$FF52` | Byte | HDMA2 | Source address (LO)              |
+/// | `$FF53` | Byte | HDMA3 | Destination address (HI)         |
+/// | `$FF54` | Byte | HDMA4 | Destination address (LO)         |
+/// | `$FF55` | Byte | HDMA5 | Transfer length, mode, and start |
+///
+/// Address registers are write-only. Reading `HDMA5` yields the number of
+/// remaining blocks (minus one), with bit 7 clear while a transfer is active.
+#[derive(Debug)]
+pub struct Control {
+    /// Source address.
+    src: Word,
+    /// Destination address.
+    dst: Word,
+    /// Remaining blocks (minus one).
+    len: Byte,
+    /// Block byte index.
+    idx: Word,
+    /// Transfer state.
+    state: State,
+}
+
+impl Default for Control {
+    fn default() -> Self {
+        Self {
+            src: Word::default(),
+            dst: Word::default(),
+            len: 0x7f,
+            idx: Word::default(),
+            state: State::default(),
+        }
+    }
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        std::mem::take(self);
+    }
+}
+
+impl Memory for Control {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        match addr {
+            0..=3 => Ok(0xff),
+            4 => Ok(Byte::from(matches!(self.state, State::Off)) << 7 | self.len),
+            _ => Err(Error::Range),
+        }
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        let [lo, hi] = [0x00ff, 0xff00];
+        match addr {
+            0 => self.src = self.src & lo | Word::from(data) << 8,
+            1 => self.src = self.src & hi | Word::from(data & 0xf0),
+            2 => self.dst = self.dst & lo | Word::from(data & 0x1f) << 8,
+            3 => self.dst = self.dst & hi | Word::from(data & 0xf0),
+            4 => match (&self.state, data & 0x80 != 0) {
+                (State::HBlank { .. }, false) => {
+                    // Cancel transfer
+                    self.state = State::Off;
+                    debug!("cancelled");
+                }
+                (_, hblank) => {
+                    // Start transfer
+                    self.len = data & 0x7f;
+                    self.idx = 0;
+                    self.state = if hblank {
+                        State::HBlank { ready: false }
+                    } else {
+                        State::General
+                    };
+                    debug!(
+                        "request: {dst:#06x} <- {src:#06x}, len: {len:#06x}, mode: {mode}",
+                        dst = 0x8000 | self.dst,
+                        src = self.src,
+                        len = (Word::from(self.len) + 1) * Hdma::BLOCK,
+                        mode = if hblank { "hblank" } else { "general" },
+                    );
+                }
+            },
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// HDMA transfer state.
+#[derive(Debug, Default)]
+enum State {
+    /// Disabled.
+    #[default]
+    Off,
+    /// General-purpose transfer.
+    General,
+    /// Horizontal blank transfer.
+    HBlank { ready: bool },
+}
@@ -1,12 +1,14 @@
 //! Player input device.
 
 use std::collections::HashSet;
+use std::io::{self, ErrorKind, Read, Write};
 use std::ops::{BitOr, Not};
 
 use log::{debug, trace};
 use rugby_arch::mem::Memory;
 use rugby_arch::mio::{Bus, Mmio};
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared, Word};
 
 use super::pic::{self, Interrupt};
@@ -27,6 +29,18 @@ pub enum Button {
 }
 
 impl Button {
+    /// All buttons.
+    pub(crate) const ALL: [Self; 8] = [
+        Self::A,
+        Self::B,
+        Self::Select,
+        Self::Start,
+        Self::Right,
+        Self::Left,
+        Self::Up,
+        Self::Down,
+    ];
+
     fn mode(self) -> Byte {
         Mode::MASK & self as Byte
     }
@@ -78,10 +92,16 @@ impl From<Byte> for Mode {
 }
 
 /// Joypad controller.
+///
+/// Input events received from the frontend are queued, to be applied when the
+/// joypad next cycles. This ensures the core determines exactly when input
+/// takes effect.
 #[derive(Debug)]
 pub struct Joypad {
     /// Joypad register.
     pub con: Shared<Control>,
+    /// Pending input.
+    inp: Vec<Event<Button>>,
     /// Interrupt line.
     int: pic::Line,
 }
@@ -92,15 +112,56 @@ impl Joypad {
     pub fn new(int: pic::Line) -> Self {
         Self {
             con: Shared::new(Control::default()),
+            inp: Vec::new(),
             int,
         }
     }
+
+    /// Gets the currently pressed buttons.
+    #[must_use]
+    pub fn pressed(&self) -> Vec<Button> {
+        let mut keys: Vec<_> = self.con.borrow().keys.iter().copied().collect();
+        keys.sort_by_key(|&btn| btn as Byte);
+        keys
+    }
+
+    /// Checks if any selected button is held.
+    ///
+    /// Occurs whenever any of the [control] bits 0:3 are low, which wakes the
+    /// CPU from `STOP`.
+    ///
+    /// [control]: Control
+    #[must_use]
+    pub fn held(&self) -> bool {
+        self.con.borrow().load() & Mode::KEYS != Mode::KEYS
+    }
+
+    /// Gets the pending input events.
+    #[must_use]
+    pub fn pending(&self) -> &[Event<Button>] {
+        &self.inp
+    }
+
+    /// Discards any pending input events.
+    pub fn discard(&mut self) {
+        self.inp.clear();
+    }
 }
 
 impl Api for Joypad {
     type Button = Button;
 
     fn recv(&mut self, events: impl IntoIterator<Item = Event<Self::Button>>) {
+        self.inp.extend(events);
+    }
+}
+
+impl Block for Joypad {
+    fn ready(&self) -> bool {
+        !self.inp.is_empty()
+    }
+
+    fn cycle(&mut self) {
         // Borrow controller state
         let mut con = self.con.borrow_mut();
         let mode = con.mode;
@@ -108,7 +169,7 @@ impl Api for Joypad {
         // Update pressed keys
         let mut updated = false;
         let mut trigger = false;
-        for Event { input: btn, state } in events {
+        for Event { input: btn, state } in self.inp.drain(..) {
             trace!("event: {btn:?}, {state:?}");
             // Update internal state
             updated |= match state {
@@ -129,11 +190,10 @@ impl Api for Joypad {
             trace!("received no input events");
         }
     }
-}
 
-impl Block for Joypad {
     fn reset(&mut self) {
         self.con.reset();
+        self.inp.clear();
     }
 }
 
@@ -141,6 +201,44 @@ impl Mmio for Joypad {
     fn attach(&self, bus: &mut Bus) {
         bus.map(0xff00..=0xff00, self.con.clone().into());
     }
+
+    fn detach(&self, bus: &mut Bus) {
+        assert!(bus.unmap(&self.con.clone().into()));
+    }
+}
+
+/// Pending input is saved, as it has already been received by the core.
+impl Snapshot for Joypad {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.con.save(buf)?;
+        self.inp.len().save(buf)?;
+        for &Event { input, state } in &self.inp {
+            (input as Byte).save(buf)?;
+            (state == State::Up).save(buf)?;
+        }
+        Ok(())
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.con.restore(buf)?;
+        let len = usize::decode(buf)?;
+        self.inp = (0..len)
+            .map(|_| {
+                let code = Byte::decode(buf)?;
+                let input = Button::ALL
+                    .into_iter()
+                    .find(|&btn| btn as Byte == code)
+                    .ok_or(ErrorKind::InvalidData)?;
+                let state = if bool::decode(buf)? {
+                    State::Up
+                } else {
+                    State::Dn
+                };
+                Ok(Event { input, state })
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(())
+    }
 }
 
 /// Joypad register.
@@ -156,6 +254,19 @@ impl Block for Control {
     }
 }
 
+/// Only the selected mode is saved, as pressed keys are provided by the
+/// frontend.
+impl Snapshot for Control {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.mode.mode().save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.mode = Mode::from(Byte::decode(buf)?);
+        Ok(())
+    }
+}
+
 impl Memory for Control {
     fn read(&self, _: Word) -> rugby_arch::mem::Result<Byte> {
         Ok(self.load())
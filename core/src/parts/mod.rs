@@ -9,6 +9,7 @@ pub mod boot;
 pub mod cart;
 pub mod cpu;
 pub mod dma;
+pub mod hdma;
 pub mod joypad;
 pub mod pic;
 pub mod ppu;
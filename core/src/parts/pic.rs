@@ -1,11 +1,13 @@
 //! Interrupt handling.
 
 use std::fmt::Display;
+use std::io::{self, Read, Write};
 
 use log::trace;
 use rugby_arch::mem::Memory;
 use rugby_arch::mio::{Bus, Mmio};
 use rugby_arch::reg::{Port, Register as _};
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared, Word};
 use thiserror::Error;
 
@@ -181,6 +183,18 @@ impl Mmio for Pic {
     }
 }
 
+impl Snapshot for Pic {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.reg.flg.borrow().0.save(buf)?;
+        self.reg.ena.borrow().0.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.reg.flg.borrow_mut().0.restore(buf)?;
+        self.reg.ena.borrow_mut().0.restore(buf)
+    }
+}
+
 impl Port<Byte> for Pic {
     type Select = Select;
 
@@ -1,6 +1,9 @@
+use std::io::{self, Read, Write};
+
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Word};
 
-use super::meta::{Layer, Meta, Row};
+use super::meta::{Attributes, Layer, Meta, Row};
 use super::{Fifo, Lcdc, Ppu, Step};
 
 /// Background fetcher.
@@ -10,10 +13,17 @@ pub struct Fetcher {
     pub fifo: Fifo,
     /// Fetcher step.
     pub step: Step,
+    /// Step access pending.
+    ///
+    /// VRAM accesses take 2 dots, with the access itself occurring on the
+    /// first.
+    pub wait: bool,
     /// X position counter.
     pub xpos: Byte,
     /// Graphics layer.
     pub layer: Layer,
+    /// Tile attributes (CGB).
+    pub attr: Attributes,
 }
 
 impl Default for Fetcher {
@@ -21,8 +31,10 @@ impl Default for Fetcher {
         Self {
             fifo: Fifo::default(),
             step: Step::Fetch,
+            wait: bool::default(),
             xpos: Byte::default(),
             layer: Layer::Background,
+            attr: Attributes::default(),
         }
     }
 }
@@ -30,33 +42,75 @@ impl Default for Fetcher {
 impl Fetcher {
     /// Executes a cycle of the fetcher.
     pub fn exec(&mut self, ppu: &mut Ppu) {
+        // Idle on the second dot of VRAM accesses
+        if std::mem::take(&mut self.wait) {
+            // Attempt to push after the final read
+            if let Step::Push { data } = self.step {
+                self.step = exec::push(self, data);
+            }
+            return;
+        }
+        self.wait = !matches!(self.step, Step::Push { .. });
         self.step = match self.step {
             Step::Fetch => exec::fetch(ppu, self),
-            Step::Read0 { tdat } => exec::read0(ppu, tdat),
-            Step::Read1 { tdat, data } => exec::read1(ppu, tdat, data),
+            Step::Read0 { tnum } => exec::read0(ppu, tnum, exec::tdat(ppu, self, tnum)),
+            Step::Read1 { tnum, data } => exec::read1(ppu, exec::tdat(ppu, self, tnum), data),
             Step::Push { data } => exec::push(self, data),
         }
     }
+
+    /// Checks if the fetcher has reached the final read of its tile.
+    ///
+    /// Sprite fetches must wait until this is the case before they can begin.
+    pub fn ready(&self) -> bool {
+        matches!(self.step, Step::Push { .. }) && !self.fifo.is_empty()
+    }
 }
 
 impl Block for Fetcher {
     fn reset(&mut self) {
         std::mem::take(&mut self.fifo);
         std::mem::take(&mut self.step);
+        std::mem::take(&mut self.wait);
         std::mem::take(&mut self.xpos);
+        std::mem::take(&mut self.attr);
+    }
+}
+
+impl Snapshot for Fetcher {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.fifo.save(buf)?;
+        self.step.save(buf)?;
+        self.wait.save(buf)?;
+        self.xpos.save(buf)?;
+        self.layer.save(buf)?;
+        self.attr.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.fifo.restore(buf)?;
+        self.step.restore(buf)?;
+        self.wait.restore(buf)?;
+        self.xpos.restore(buf)?;
+        self.layer.restore(buf)?;
+        self.attr.restore(buf)
     }
 }
 
 /// Execution steps.
 pub(super) mod exec {
     use log::trace;
-    use rugby_arch::mem::Memory;
     use rugby_arch::reg::Register;
 
-    use super::{Byte, Fetcher, Layer, Lcdc, Meta, Ppu, Row, Step, Word};
+    use super::{Attributes, Byte, Fetcher, Layer, Lcdc, Meta, Ppu, Row, Step, Word};
 
     /// Executes fetch tile step.
     pub fn fetch(ppu: &Ppu, fetch: &mut Fetcher) -> Step {
+        // Return to the background if the window has been disabled
+        if fetch.layer == Layer::Window && !ppu.lcdc(Lcdc::WinEnable) {
+            trace!("window disabled; resuming background");
+            fetch.layer = Layer::Background;
+        }
         // Determine which tile map to use
         let tmap = [0x1800, 0x1c00][usize::from(ppu.lcdc(match fetch.layer {
             Layer::Background => Lcdc::BgMap,
@@ -82,30 +136,52 @@ pub(super) mod exec {
         };
         // Read the tile number from the tile map
         let addr = tmap + toff;
-        let tnum = ppu.mem.vram.read(addr).unwrap();
+        let tnum = ppu.vram(addr);
         trace!("read tile index: VRAM[${addr:04x}] -> #{tnum}");
-        // Calculate the tile data address
-        let tdat = ppu.tdat(fetch.layer, tnum);
+        // Read the tile attributes from the attribute map (CGB)
+        fetch.attr = if ppu.native() {
+            Attributes::from(ppu.vram(addr | 0x2000))
+        } else {
+            Attributes::default()
+        };
 
         // Progress to next step
-        Step::Read0 { tdat }
+        Step::Read0 { tnum }
+    }
+
+    /// Calculates the tile data address of the current row.
+    pub fn tdat(ppu: &Ppu, fetch: &Fetcher, tnum: Byte) -> Word {
+        // Calculate the tile data address
+        let mut tdat = ppu.tdat(fetch.layer, tnum);
+        // Perform vertical flip
+        if fetch.attr.yflip {
+            tdat ^= 0b0000_1110;
+        }
+        // Select the VRAM bank
+        if fetch.attr.bank {
+            tdat |= 0x2000;
+        }
+        tdat
     }
 
     /// Executes read tile data low.
-    pub fn read0(ppu: &Ppu, tdat: Word) -> Step {
+    ///
+    /// Tile data addresses are banked, such that bit 13 selects the VRAM bank
+    /// (CGB only).
+    pub fn read0(ppu: &Ppu, tnum: Byte, tdat: Word) -> Step {
         // Fetch the low byte of the tile
-        let data = ppu.mem.vram.read(tdat).unwrap();
+        let data = ppu.vram(tdat);
         trace!("read lower byte: VRAM[${tdat:04x}] -> {data:#04x}");
 
         // Progress to next step
-        let tdat = tdat + 1;
-        Step::Read1 { tdat, data }
+        Step::Read1 { tnum, data }
     }
 
     /// Executes read tile data high.
     pub fn read1(ppu: &Ppu, tdat: Word, data0: Byte) -> Step {
         // Fetch the high byte of the tile
-        let data1 = ppu.mem.vram.read(tdat).unwrap();
+        let tdat = tdat + 1;
+        let data1 = ppu.vram(tdat);
         trace!("read upper byte: VRAM[${tdat:04x}] -> {data1:#04x}");
 
         // Progress to next step
@@ -117,8 +193,12 @@ pub(super) mod exec {
     /// Executes push to FIFO.
     pub fn push(fetch: &mut Fetcher, data: [Byte; 2]) -> Step {
         // Decode pixel row from bytes
-        let row = Row::from(data);
-        let meta = Meta::bgwin();
+        let mut row = Row::from(data);
+        // Perform horizontal flip
+        if fetch.attr.xflip {
+            row.xflip();
+        }
+        let meta = Meta::tile(fetch.layer, &fetch.attr);
 
         // Only push when the FIFO is empty
         if fetch.fifo.is_empty() {
@@ -1,4 +1,8 @@
+use std::io::{self, ErrorKind, Read, Write};
+
+use rugby_arch::mem::Memory;
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Byte, Word};
 
 use super::fifo::Fifo;
@@ -18,14 +22,69 @@ pub enum Step {
     #[default]
     Fetch,
     /// Read tile data (low).
-    Read0 { tdat: Word },
+    ///
+    /// The tile data address is determined from the tile number at the time
+    /// of each read, such that mid-fetch register writes take effect.
+    Read0 { tnum: Byte },
     /// Read tile data (high).
-    Read1 { tdat: Word, data: Byte },
+    Read1 { tnum: Byte, data: Byte },
     /// Push pixels to FIFO.
     Push { data: [Byte; 2] },
 }
 
+impl Snapshot for Step {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        match *self {
+            Step::Fetch => 0u8.save(buf),
+            Step::Read0 { tnum } => {
+                1u8.save(buf)?;
+                tnum.save(buf)
+            }
+            Step::Read1 { tnum, data } => {
+                2u8.save(buf)?;
+                tnum.save(buf)?;
+                data.save(buf)
+            }
+            Step::Push { data } => {
+                3u8.save(buf)?;
+                data.save(buf)
+            }
+        }
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = match u8::decode(buf)? {
+            0 => Step::Fetch,
+            1 => Step::Read0 {
+                tnum: Byte::decode(buf)?,
+            },
+            2 => Step::Read1 {
+                tnum: Byte::decode(buf)?,
+                data: Byte::decode(buf)?,
+            },
+            3 => Step::Push {
+                data: Snapshot::decode(buf)?,
+            },
+            _ => return Err(ErrorKind::InvalidData.into()),
+        };
+        Ok(())
+    }
+}
+
 impl Ppu {
+    /// Reads a byte from a banked VRAM address.
+    ///
+    /// Bit 13 of the address selects the VRAM bank, which is only present on
+    /// the CGB.
+    #[inline]
+    pub(crate) fn vram(&self, addr: Word) -> Byte {
+        match &self.cgb {
+            Some(cgb) if addr & 0x2000 != 0 => cgb.vram.read(addr & 0x1fff),
+            _ => self.mem.vram.read(addr & 0x1fff),
+        }
+        .unwrap()
+    }
+
     /// Gets the tile address base for the configured addressing mode.
     #[inline]
     pub(crate) fn base(&self, layer: Layer) -> Word {
@@ -62,10 +121,9 @@ impl Ppu {
     #[inline]
     pub(crate) fn toff(&self, layer: Layer) -> Word {
         Word::from(match layer {
-            Layer::Background | Layer::Sprite => {
-                self.reg.ly.load().wrapping_add(self.reg.scy.load())
-            }
+            Layer::Background => self.reg.ly.load().wrapping_add(self.reg.scy.load()),
             Layer::Window => self.etc.ywin,
+            Layer::Sprite => unreachable!(),
         }) % 8
     }
 
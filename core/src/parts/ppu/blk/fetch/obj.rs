@@ -1,4 +1,7 @@
-use rugby_arch::{Block, Byte};
+use std::io::{self, Read, Write};
+
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Word};
 
 use super::meta::{Layer, Row, Sprite};
 use super::{Fifo, Lcdc, Ppu, Step};
@@ -10,6 +13,8 @@ pub struct Fetcher {
     pub fifo: Fifo,
     /// Fetcher step.
     pub step: Step,
+    /// Step access pending.
+    pub wait: bool,
 }
 
 impl Fetcher {
@@ -22,19 +27,54 @@ impl Default for Fetcher {
         Self {
             fifo: Fifo::default(),
             step: Step::Fetch,
+            wait: bool::default(),
         }
     }
 }
 
+impl Snapshot for Fetcher {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.fifo.save(buf)?;
+        self.step.save(buf)?;
+        self.wait.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.fifo.restore(buf)?;
+        self.step.restore(buf)?;
+        self.wait.restore(buf)
+    }
+}
+
 impl Fetcher {
     /// Executes a cycle of the fetcher.
-    pub fn exec(&mut self, ppu: &mut Ppu, obj: &Sprite) {
+    ///
+    /// Returns `true` once the sprite has been pushed to the FIFO.
+    pub fn exec(&mut self, ppu: &mut Ppu, obj: &Sprite) -> bool {
+        // Wait on the first dot of VRAM accesses
+        self.wait = !self.wait;
+        if self.wait {
+            return false;
+        }
         self.step = match self.step {
-            Step::Fetch => exec::fetch(ppu, obj),
-            Step::Read0 { tdat } => exec::read0(ppu, tdat),
-            Step::Read1 { tdat, data } => exec::read1(ppu, tdat, data),
+            Step::Fetch => exec::fetch(obj),
+            Step::Read0 { tnum } => exec::read0(ppu, tnum, exec::tdat(ppu, obj, tnum)),
+            Step::Read1 { tnum, data } => {
+                match exec::read1(ppu, exec::tdat(ppu, obj, tnum), data) {
+                    // Push on the same dot as the final read
+                    Step::Push { data } => exec::push(self, data, obj),
+                    step => step,
+                }
+            }
             Step::Push { data } => exec::push(self, data, obj),
-        }
+        };
+        matches!(self.step, Step::Fetch)
+    }
+
+    /// Cancels an in-progress sprite fetch.
+    pub fn cancel(&mut self) {
+        std::mem::take(&mut self.step);
+        std::mem::take(&mut self.wait);
     }
 }
 
@@ -42,6 +82,7 @@ impl Block for Fetcher {
     fn reset(&mut self) {
         std::mem::take(&mut self.fifo);
         std::mem::take(&mut self.step);
+        std::mem::take(&mut self.wait);
     }
 }
 
@@ -50,40 +91,49 @@ pub(super) mod exec {
     use log::trace;
     use rugby_arch::reg::Register;
 
-    use super::{Byte, Fetcher, Lcdc, Ppu, Row, Sprite, Step};
+    use super::{Byte, Fetcher, Lcdc, Ppu, Row, Sprite, Step, Word};
 
     /// Executes fetch tile step.
-    pub fn fetch(ppu: &Ppu, obj: &Sprite) -> Step {
-        // Read the tile number from the tilemap
+    pub fn fetch(obj: &Sprite) -> Step {
+        // Read the tile number from OAM
+        let tnum = obj.tnum;
+        trace!("used tile index: sprite.tnum -> #{tnum}");
+
+        // Progress to next step
+        Step::Read0 { tnum }
+    }
+
+    /// Calculates the tile data address of the sprite's current row.
+    pub fn tdat(ppu: &Ppu, obj: &Sprite, tnum: Byte) -> Word {
+        // Determine the sprite's row on this scanline
+        let row = ppu.reg.ly.load().wrapping_add(16).wrapping_sub(obj.ypos);
+        // Determine the tile number
         let tnum = {
             // Check if the sprite is tall
             if ppu.lcdc(Lcdc::ObjSize) {
                 // Tall (8x16) sprites span two tiles; must check if flipped
-                let upper = {
-                    let xpos = obj.ypos;
-                    let line = ppu.reg.ly.load().saturating_add(16);
-                    (xpos..xpos + 8).contains(&line)
-                };
+                let upper = row < 8;
                 if upper ^ obj.attr.yflip {
-                    obj.tnum & 0b1111_1110 // use upper tile
+                    tnum & 0b1111_1110 // use upper tile
                 } else {
-                    obj.tnum | 0b0000_0001 // use lower tile
+                    tnum | 0b0000_0001 // use lower tile
                 }
             } else {
                 // Short (8x8) sprites only span a single tile
-                obj.tnum
+                tnum
             }
         };
-        trace!("used tile index: sprite.tnum -> #{tnum}");
         // Calculate the tile data address
-        let mut tdat = ppu.tdat(Fetcher::LAYER, tnum);
+        let mut tdat = ppu.tidx(Fetcher::LAYER, tnum) | Word::from(row % 8) << 1;
         // Perform vertical flip
         if obj.attr.yflip {
             tdat ^= 0b0000_1110;
         }
-
-        // Progress to next step
-        Step::Read0 { tdat }
+        // Select the VRAM bank (CGB)
+        if obj.attr.bank && ppu.native() {
+            tdat |= 0x2000;
+        }
+        tdat
     }
 
     pub use super::super::bgw::exec::{read0, read1};
@@ -97,11 +147,12 @@ pub(super) mod exec {
             row.xflip();
         }
 
-        // Push to the FIFO
+        // Merge into the FIFO
         //
-        // NOTE: Some pixels may be discarded when FIFO is non-empty.
-        trace!("pushed row of pixels: {row:?}");
-        fetch.fifo.push(row, obj.meta());
+        // NOTE: Pixels of earlier sprites are only replaced where they are
+        //       transparent.
+        trace!("merged row of pixels: {row:?}");
+        fetch.fifo.merge(row, obj.meta());
         // Restart from beginning
         Step::Fetch
     }
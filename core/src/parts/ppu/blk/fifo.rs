@@ -1,6 +1,9 @@
 use std::collections::VecDeque;
+use std::io::{self, Read, Write};
 
-use super::meta::{Meta, Pixel, Row};
+use rugby_arch::snap::Snapshot;
+
+use super::meta::{Color, Meta, Pixel, Row};
 
 /// Pixel FIFO.
 #[derive(Clone, Debug, Default)]
@@ -28,6 +31,26 @@ impl Fifo {
         );
     }
 
+    /// Merges a row of pixels into the FIFO.
+    ///
+    /// Pixels already in the FIFO are only replaced when transparent.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn merge(&mut self, row: Row, meta: Meta) {
+        for (idx, col) in row.into_iter().enumerate() {
+            let pixel = Pixel::new(col, meta.clone());
+            match self.0.get_mut(idx) {
+                Some(old) if old.col == Color::C0 => *old = pixel,
+                Some(_) => (),
+                None => self.0.push_back(pixel),
+            }
+        }
+    }
+
+    /// Inserts a pixel at the front of the FIFO.
+    pub fn insert(&mut self, pixel: Pixel) {
+        self.0.push_front(pixel);
+    }
+
     /// Removes the next pixel from the FIFO and returns it.
     pub fn pop(&mut self) -> Option<Pixel> {
         self.0.pop_front()
@@ -48,3 +71,13 @@ impl Fifo {
         self.0.len() >= 8
     }
 }
+
+impl Snapshot for Fifo {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.0.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.0.restore(buf)
+    }
+}
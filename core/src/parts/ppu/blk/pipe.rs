@@ -1,9 +1,12 @@
+use std::io::{self, Read, Write};
+
 use log::trace;
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte};
 
-use super::fetch::{self, Step};
-use super::meta::{Color, Layer, Pixel, Sprite};
+use super::fetch;
+use super::meta::{Color, Layer, Meta, Pixel, Row, Sprite};
 use super::{Lcdc, Ppu};
 
 /// Pixel pipeline.
@@ -12,6 +15,8 @@ pub struct Pipeline {
     /// Warmup completed.
     pub ready: bool,
     /// Scroll offset.
+    ///
+    /// Number of pixels remaining to be discarded at the start of the line.
     pub scroll: u8,
     /// LCD X-coordinate.
     pub lx: Byte,
@@ -19,82 +24,213 @@ pub struct Pipeline {
     pub bgw: fetch::Background,
     /// Sprite channel.
     pub obj: fetch::Sprite,
+    /// Sprite being fetched.
+    pub spr: Option<Sprite>,
+    /// Window reached this line.
+    pub win: bool,
+    /// Window border matched on the previous dot.
+    pub hit: bool,
+}
+
+impl Snapshot for Pipeline {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.ready.save(buf)?;
+        self.scroll.save(buf)?;
+        self.lx.save(buf)?;
+        self.bgw.save(buf)?;
+        self.obj.save(buf)?;
+        self.spr.save(buf)?;
+        self.win.save(buf)?;
+        self.hit.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.ready.restore(buf)?;
+        self.scroll.restore(buf)?;
+        self.lx.restore(buf)?;
+        self.bgw.restore(buf)?;
+        self.obj.restore(buf)?;
+        self.spr.restore(buf)?;
+        self.win.restore(buf)?;
+        self.hit.restore(buf)
+    }
 }
 
 impl Pipeline {
-    /// Performs a fetch for the next pixels to the appropriate FIFO.
-    pub fn fetch(&mut self, ppu: &mut Ppu, objs: &[Sprite]) {
-        // Check if we're at an object
-        if let Some(obj) = objs.iter().find(|obj| obj.xpos == self.lx + 8) {
-            if ppu.lcdc(Lcdc::ObjEnable) && !self.obj.fifo.is_full() {
-                trace!("found sprite: {obj:?}");
-                // Fetch the sprite
-                self.obj.exec(ppu, obj);
-                // Stall the background fetcher
-                return;
-            }
+    /// Executes a single dot of the pipeline, possibly producing a pixel.
+    pub fn exec(&mut self, ppu: &mut Ppu, objs: &mut Vec<Sprite>) -> Option<Pixel> {
+        // Prepare the pipeline on the first dot
+        if !self.ready {
+            self.warmup(ppu);
         }
 
-        // Execute the background fetcher
-        self.bgw.exec(ppu);
-
-        // Restart background fetcher when:
+        // Check if the window border has been reached
         //
-        // 1. The first "warm-up" fetch completes
-        let done_warmup = !self.ready && matches!(self.bgw.step, Step::Push { .. });
-        if done_warmup {
-            // We're now ready for real fetches
-            self.ready = true;
-            trace!("pipeline warmup complete");
-        }
-        // 2. The window border has been reached
-        let window_reached = {
-            // 1. The window is enabled
-            let win_enabled = ppu.lcdc(Lcdc::WinEnable);
-            // 2. Fetcher is still at the background
-            let fetch_at_bg = self.bgw.layer == Layer::Background;
-            // 3. Y-coordinate is below the window
-            let y_below_win = ppu.reg.wy.load() <= ppu.reg.ly.load();
-            // 4. X-coordinate is right of window
-            let x_right_win = ppu.reg.wx.load() <= self.lx + 7;
-            //
-            // Determine result:
-            win_enabled && fetch_at_bg && y_below_win && x_right_win
-        };
+        // 1. The window is enabled
+        //
+        // NOTE: On the DMG, enabling the window is observed a dot late,
+        //       whereas disabling it is observed immediately.
+        let win_enabled = Lcdc::WinEnable.get(ppu.reg.lcdc.load() & Self::lcdc(ppu));
+        // 2. Fetcher is still at the background
+        let fetch_at_bg = self.bgw.layer == Layer::Background;
+        // 3. Y-coordinate is below the window
+        let y_below_win = ppu.reg.wy.load() <= ppu.reg.ly.load();
+        // 4. X-coordinate is at the window
+        //
+        // NOTE: On the DMG, a match from the previous dot is also accepted,
+        //       such that the window can still be triggered when enabled a
+        //       dot late.
+        let hit = self.pos() == Self::border(ppu);
+        let was = std::mem::replace(&mut self.hit, hit);
+        let x_at_win = hit || was && ppu.cgb.is_none();
+        //
+        // Determine result:
+        let window_reached = win_enabled && fetch_at_bg && y_below_win && x_at_win;
         if window_reached {
             trace!(
                 "window border reached at: (row: {ly}, col: {lx})",
                 ly = ppu.reg.ly.load(),
                 lx = self.lx
             );
-            // Update the fetcher's location
+            // Advance the window's line counter if it was already drawn on
+            // this scanline
+            //
+            // NOTE: This allows the window to skip lines when re-enabled.
+            if std::mem::replace(&mut self.win, true) {
+                ppu.etc.ywin = ppu.etc.ywin.wrapping_add(1);
+            }
+            // Restart the fetcher at the window
+            self.bgw.reset();
             self.bgw.layer = Layer::Window;
+            // Delay the fetcher by a dot when triggered at `WX=0` while `SCX`
+            // is not tile-aligned (DMG only)
+            //
+            // NOTE: This causes the window to "stutter" horizontally with
+            //       changes to `SCX`.
+            let aligned = ppu.reg.scx.load().is_multiple_of(8);
+            if ppu.cgb.is_none() && Self::border(ppu) == 1 && !aligned {
+                self.bgw.wait = true;
+            }
+        } else if y_below_win && hit && !was && self.bgw.fifo.is_full() && ppu.cgb.is_none() {
+            // Matching the window border on a tile boundary without
+            // triggering the window inserts a single blank pixel (DMG only)
+            trace!("window border matched again at: (col: {lx})", lx = self.lx);
+            self.bgw.fifo.insert(Pixel::new(Color::C0, Meta::bgwin()));
         }
-        //
-        // If either condition is met...
-        if done_warmup || window_reached {
-            // ... reset background fetcher
-            self.bgw.reset();
+
+        // Cancel sprite fetches if objects are disabled
+        if self.spr.is_some() && !ppu.lcdc(Lcdc::ObjEnable) {
+            trace!("cancelled sprite fetch");
+            self.spr = None;
+            self.obj.cancel();
         }
+        // Check if we're at an object
+        if self.spr.is_none() && ppu.lcdc(Lcdc::ObjEnable) {
+            if let Some(idx) = objs.iter().position(|obj| obj.xpos == self.pos()) {
+                let obj = objs.remove(idx);
+                trace!("found sprite: {obj:?}");
+                self.spr = Some(obj);
+            }
+        }
+        // Fetch the sprite, stalling the pipeline
+        if let Some(obj) = &self.spr {
+            if self.bgw.ready() {
+                // Fetch the sprite once the background fetcher is ready
+                if self.obj.exec(ppu, obj) {
+                    self.spr = None;
+                }
+            } else {
+                // Otherwise, allow the background fetcher to continue
+                self.bgw.exec(ppu);
+            }
+            return None;
+        }
+
+        // Shift out the next pixel
+        let pixel = self.shift(ppu);
+        // Execute the background fetcher
+        self.bgw.exec(ppu);
+
+        pixel
     }
 
-    /// Shift out a blended pixel from the FIFOs.
-    pub fn shift(&mut self, ppu: &Ppu) -> Option<Pixel> {
-        // Check the sprite FIFO isn't in progress
-        if !matches!(self.obj.step, Step::Fetch) {
-            return None;
+    /// Prepares the pipeline at the start of the line.
+    ///
+    /// The FIFO begins the line with a row of junk pixels, which are discarded
+    /// along with those scrolled off by `SCX`.
+    fn warmup(&mut self, ppu: &Ppu) {
+        self.bgw.fifo.push(Row::from([0, 0]), Meta::bgwin());
+        self.scroll = 8 + ppu.reg.scx.load() % 8;
+        self.ready = true;
+    }
+
+    /// Gets the position of the next pixel, offset by 8.
+    ///
+    /// This is the coordinate system used by sprites, such that a sprite at
+    /// `X=8` begins at the left edge of the LCD. Discarded pixels occupy
+    /// positions to the left of this.
+    fn pos(&self) -> Byte {
+        (self.lx + 8).wrapping_sub(self.scroll)
+    }
+
+    /// Gets the value of `LCDC` as observed by the pipeline.
+    ///
+    /// On the DMG, writes are observed by the pipeline a dot late.
+    fn lcdc(ppu: &Ppu) -> Byte {
+        if ppu.cgb.is_none() {
+            ppu.etc.lcdc
+        } else {
+            ppu.reg.lcdc.load()
         }
+    }
 
+    /// Gets the position at which the window border is reached.
+    ///
+    /// On the DMG, writes to `WX` are observed by the pipeline two dots late.
+    fn border(ppu: &Ppu) -> Byte {
+        if ppu.cgb.is_some() {
+            ppu.reg.wx.load()
+        } else {
+            ppu.etc.wx[0]
+        }
+        .wrapping_add(1)
+    }
+
+    /// Shift out a blended pixel from the FIFOs.
+    fn shift(&mut self, ppu: &Ppu) -> Option<Pixel> {
         // Pop from the background FIFO
         let mut bgwin = self.bgw.fifo.pop()?;
 
+        // Determine which layers are enabled
+        //
+        // NOTE: The first pixel of the line observes writes to `LCDC`
+        //       immediately, even on the DMG.
+        let lcdc = if self.lx == 0 {
+            ppu.reg.lcdc.load()
+        } else {
+            Self::lcdc(ppu)
+        };
+
         // Overwrite if the background/window is disabled
-        if !ppu.lcdc(Lcdc::BgWinEnable) {
+        //
+        // NOTE: In CGB mode, this instead removes background/window priority.
+        let enable = Lcdc::BgWinEnable.get(lcdc);
+        if !enable && !ppu.native() {
             bgwin.col = Color::C0;
         }
 
         // Pop from the sprite FIFO
-        let pixel = if let Some(sprite) = self.obj.fifo.pop() {
+        //
+        // NOTE: Sprite pixels already in the FIFO are hidden while objects
+        //       are disabled.
+        let sprite = self.obj.fifo.pop().filter(|_| Lcdc::ObjEnable.get(lcdc));
+        let pixel = if let Some(mut sprite) = sprite {
+            // Sprites always have priority when the background/window is
+            // disabled
+            if !enable {
+                bgwin.meta.bgp = false;
+                sprite.meta.bgp = false;
+            }
             Pixel::blend(bgwin, sprite) // blend the pixels together
         } else {
             bgwin // no sprite; use background/window pixel
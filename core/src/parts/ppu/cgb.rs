@@ -0,0 +1,243 @@
+//! Color graphics.
+//!
+//! Extensions to the PPU present on the CGB.
+
+use std::io::{self, Read, Write};
+
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::mio::{Bus, Mmio};
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Block, Byte, Shared, Word};
+
+use super::{Color, Ppu, Rgb, Vram, LCD};
+use crate::api::part::video::{self, Aspect, Video as Api};
+
+/// Color framebuffer.
+pub type Frame = video::Frame<Rgb, { LCD.depth() }>;
+
+/// Allocates a blank color framebuffer.
+fn framebuffer() -> Box<Frame> {
+    vec![Rgb::default(); LCD.depth()]
+        .into_boxed_slice()
+        .try_into()
+        .unwrap()
+}
+
+/// Color graphics extension.
+///
+/// | Address | Size | Name  | Description                   |
+/// |:-------:|------|-------|-------------------------------|
+/// | `$FF68` | Byte | BCPS  | Background color palette spec |
+/// | `$FF69` | Byte | BCPD  | Background color palette data |
+/// | `$FF6A` | Byte | OCPS  | Object color palette spec     |
+/// | `$FF6B` | Byte | OCPD  | Object color palette data     |
+///
+/// Additionally provides the second bank of [VRAM](Vram), which stores tile
+/// data alongside the background map attributes.
+#[derive(Debug)]
+pub struct Cgb {
+    /// Video RAM (bank 1).
+    pub vram: Shared<Vram>,
+    /// Background color palettes.
+    pub bcp: Shared<Cram>,
+    /// Object color palettes.
+    pub ocp: Shared<Cram>,
+    /// Compatibility mode.
+    pub compat: Shared<bool>,
+    /// Color framebuffer.
+    pub(super) buf: Box<Frame>,
+}
+
+impl Cgb {
+    /// Constructs a new `Cgb`.
+    #[must_use]
+    pub fn new(vram: Shared<Vram>) -> Self {
+        Self {
+            vram,
+            bcp: Shared::default(),
+            ocp: Shared::default(),
+            compat: Shared::default(),
+            buf: framebuffer(),
+        }
+    }
+
+    /// Checks if running in DMG compatibility mode.
+    #[must_use]
+    pub fn compat(&self) -> bool {
+        *self.compat.borrow()
+    }
+}
+
+impl Block for Cgb {
+    fn reset(&mut self) {
+        self.bcp.borrow_mut().reset();
+        self.ocp.borrow_mut().reset();
+    }
+}
+
+impl Mmio for Cgb {
+    fn attach(&self, bus: &mut Bus) {
+        bus.map(0xff68..=0xff69, self.bcp.clone().into());
+        bus.map(0xff6a..=0xff6b, self.ocp.clone().into());
+    }
+}
+
+/// The second bank of VRAM is not saved, as it is owned by the motherboard.
+impl Snapshot for Cgb {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.bcp.save(buf)?;
+        self.ocp.save(buf)?;
+        self.compat.save(buf)?;
+        self.buf.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.bcp.restore(buf)?;
+        self.ocp.restore(buf)?;
+        self.compat.restore(buf)?;
+        self.buf.restore(buf)
+    }
+}
+
+/// Color palette memory.
+///
+/// 64 byte RAM storing 8 palettes of 4 [colors](Rgb) each, accessed indirectly
+/// through a pair of registers:
+///
+/// | Offset | Name | Description                                        |
+/// |:------:|------|----------------------------------------------------|
+/// |  `+0`  | SPEC | Address (bits 5:0), and auto-increment (bit 7).    |
+/// |  `+1`  | DATA | Palette data at the address.                       |
+#[derive(Debug)]
+pub struct Cram {
+    /// Palette address.
+    idx: Byte,
+    /// Auto-increment.
+    inc: bool,
+    /// Palette data.
+    mem: [Byte; 0x40],
+}
+
+impl Cram {
+    /// Looks up a color within a palette.
+    #[must_use]
+    pub fn color(&self, pal: Byte, col: Color) -> Rgb {
+        let idx = usize::from(pal & 0x07) << 3 | (col as usize) << 1;
+        Rgb::from([self.mem[idx], self.mem[idx + 1]])
+    }
+
+    /// Loads a palette's colors.
+    pub fn load(&mut self, pal: Byte, cols: [Word; 4]) {
+        let base = usize::from(pal & 0x07) << 3;
+        for (idx, col) in cols.into_iter().enumerate() {
+            let [lo, hi] = col.to_le_bytes();
+            self.mem[base + 2 * idx] = lo;
+            self.mem[base + 2 * idx + 1] = hi;
+        }
+    }
+}
+
+impl Default for Cram {
+    fn default() -> Self {
+        Self {
+            idx: Byte::default(),
+            inc: bool::default(),
+            mem: [0; 0x40],
+        }
+    }
+}
+
+impl Block for Cram {
+    fn reset(&mut self) {
+        self.idx = 0;
+        self.inc = false;
+    }
+}
+
+impl Snapshot for Cram {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.idx.save(buf)?;
+        self.inc.save(buf)?;
+        self.mem.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.idx.restore(buf)?;
+        self.inc.restore(buf)?;
+        self.mem.restore(buf)
+    }
+}
+
+impl Memory for Cram {
+    fn read(&self, addr: Word) -> Result<Byte> {
+        match addr {
+            0 => Ok(0x40 | Byte::from(self.inc) << 7 | self.idx),
+            1 => Ok(self.mem[usize::from(self.idx)]),
+            _ => Err(Error::Range),
+        }
+    }
+
+    fn write(&mut self, addr: Word, data: Byte) -> Result<()> {
+        match addr {
+            0 => {
+                self.idx = data & 0x3f;
+                self.inc = data & 0x80 != 0;
+            }
+            1 => {
+                self.mem[usize::from(self.idx)] = data;
+                if self.inc {
+                    self.idx = (self.idx + 1) & 0x3f;
+                }
+            }
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// Color display.
+///
+/// Latches the PPU's color framebuffer once each frame is complete, presenting
+/// it as [15-bit color](Rgb).
+#[derive(Debug)]
+pub struct Lcd {
+    /// Latched frame.
+    buf: Box<Frame>,
+    /// Vertical sync.
+    vsync: bool,
+}
+
+impl Lcd {
+    /// Synchronizes with the PPU.
+    pub fn sync(&mut self, ppu: &Ppu) {
+        self.vsync = ppu.vsync();
+        if self.vsync {
+            if let Some(cgb) = &ppu.cgb {
+                self.buf.copy_from_slice(&*cgb.buf);
+            }
+        }
+    }
+}
+
+impl Default for Lcd {
+    fn default() -> Self {
+        Self {
+            buf: framebuffer(),
+            vsync: bool::default(),
+        }
+    }
+}
+
+impl Api for Lcd {
+    const SIZE: Aspect = LCD;
+
+    type Pixel = Rgb;
+
+    fn vsync(&self) -> bool {
+        self.vsync
+    }
+
+    fn frame(&self) -> &[Self::Pixel] {
+        &*self.buf
+    }
+}
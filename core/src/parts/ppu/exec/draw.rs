@@ -1,9 +1,12 @@
+use std::io::{self, Read, Write};
+
 use log::{debug, trace};
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 
 use super::ppu::blk::pipe::Pipeline;
-use super::ppu::meta::{Layer, Palette, Pixel, Sprite};
-use super::ppu::Color;
+use super::ppu::meta::{Palette, Pixel, Sprite};
+use super::ppu::{Color, Rgb, Tagged};
 use super::scan::Scan;
 use super::{Mode, Ppu, LCD};
 
@@ -16,18 +19,70 @@ pub struct Draw {
     pub(super) objs: Vec<Sprite>,
 }
 
+impl Snapshot for Draw {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.pipe.save(buf)?;
+        self.objs.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.pipe.restore(buf)?;
+        self.objs.restore(buf)
+    }
+}
+
 impl Draw {
-    pub fn exec(mut self, ppu: &mut Ppu) -> Mode {
-        // Initialize discarded scroll pixels
-        if !self.pipe.ready {
-            self.pipe.scroll = ppu.reg.scx.load() % 8;
+    /// Dot at which rendering begins.
+    const START: u16 = 83;
+
+    /// Number of dots by which `STAT` leads the pipeline.
+    ///
+    /// Mode 3 is reported this many dots before the end of the scan.
+    pub const LEAD: u16 = 5;
+
+    /// Number of dots by which `STAT` leads the end of the pipeline.
+    ///
+    /// Mode 0 is reported this many dots before the final pixel is drawn.
+    pub const TAIL: u16 = 7;
+
+    /// Checks if the pipeline will finish drawing within [`TAIL`](Self::TAIL)
+    /// dots.
+    ///
+    /// As this depends on any sprite fetches still to come, a copy of the
+    /// pipeline is run ahead, assuming no further register writes.
+    pub(super) fn ending(&self, ppu: &mut Ppu) -> bool {
+        // Skip running ahead while too many pixels remain
+        if LCD.wd - u16::from(self.pipe.lx) > Self::TAIL {
+            return false;
+        }
+        // Run a copy of the pipeline until it finishes
+        //
+        // NOTE: The window line counter is the only PPU state modified by the
+        //       pipeline, so it must be restored afterwards.
+        let ywin = ppu.etc.ywin;
+        let mut pipe = self.pipe.clone();
+        let mut objs = self.objs.clone();
+        let mut dots = 0;
+        while u16::from(pipe.lx) < LCD.wd && dots <= Self::TAIL {
+            if pipe.exec(ppu, &mut objs).is_some() {
+                pipe.lx += 1;
+            }
+            dots += 1;
         }
+        ppu.etc.ywin = ywin;
+        dots <= Self::TAIL
+    }
 
-        // Execute the next fetch cycle
-        self.pipe.fetch(ppu, &self.objs);
+    pub fn exec(mut self, ppu: &mut Ppu) -> Mode {
+        // Wait for rendering to begin
+        if ppu.etc.dot < Self::START {
+            return Mode::Draw(self);
+        }
 
+        // Execute the next dot of the pipeline
+        //
         // If we have a pixel to draw, draw it
-        if let Some(pixel) = self.pipe.shift(ppu) {
+        if let Some(pixel) = self.pipe.exec(ppu, &mut self.objs) {
             // Fetch pixel coordinates
             let ly: u16 = ppu.reg.ly.load().into();
             let lx: u16 = self.pipe.lx.into();
@@ -37,6 +92,16 @@ impl Draw {
             let pidx = (ly * LCD.wd) + lx; // calculate index
             ppu.etc.buf[usize::from(pidx)] = color;
             trace!("wrote pixel: {color:?} -> (row: {ly}, col: {lx})");
+            // Write pixel into the layer-tagged framebuffer
+            ppu.etc.tag[usize::from(pidx)] = Tagged {
+                src: (&pixel.meta).into(),
+                idx: pixel.col,
+                col: color,
+            };
+            // Write pixel into the color framebuffer (CGB)
+            if let (Some(rgb), Some(cgb)) = (ppu.rgb(&pixel), ppu.cgb.as_mut()) {
+                cgb.buf[usize::from(pidx)] = rgb;
+            }
 
             // Move to next pixel
             self.pipe.lx += 1;
@@ -48,8 +113,8 @@ impl Draw {
             Mode::Draw(self)
         } else {
             // Increment window internal line counter
-            if self.pipe.bgw.layer == Layer::Window {
-                ppu.etc.ywin += 1;
+            if self.pipe.win {
+                ppu.etc.ywin = ppu.etc.ywin.wrapping_add(1);
             }
             // Enter hblank
             debug!("entered mode 0: hblank");
@@ -71,11 +136,16 @@ impl Ppu {
     /// Color a pixel using the current palette.
     pub(in super::super) fn color(&self, pixel: &Pixel) -> Color {
         // Load palette data
-        let pal = match pixel.meta.pal {
+        let mut pal = match pixel.meta.pal {
             Palette::BgWin => self.reg.bgp.load(),
             Palette::Obp0 => self.reg.obp0.load(),
             Palette::Obp1 => self.reg.obp1.load(),
         };
+        // Palette writes glitch on the DMG, such that during the dot of the
+        // write both the old and new values are observed
+        if self.cgb.is_none() {
+            pal |= self.etc.pal[pixel.meta.pal as usize];
+        }
         // Assign colors using palette
         #[allow(clippy::identity_op)]
         let col = Color::from(match pixel.col {
@@ -91,4 +161,28 @@ impl Ppu {
         );
         col
     }
+
+    /// Color a pixel using the current color palettes (CGB).
+    ///
+    /// In compatibility mode, pixels are first colored using the monochrome
+    /// palettes, then by fixed color palettes (as set up by the boot ROM).
+    pub(in super::super) fn rgb(&self, pixel: &Pixel) -> Option<Rgb> {
+        let cgb = self.cgb.as_ref()?;
+        // Determine color index
+        let idx = if cgb.compat() {
+            self.color(pixel)
+        } else {
+            pixel.col
+        };
+        // Determine color palette
+        let (cram, pal) = match (cgb.compat(), pixel.meta.pal) {
+            (true, Palette::BgWin) => (&cgb.bcp, 0),
+            (true, Palette::Obp0) => (&cgb.ocp, 0),
+            (true, Palette::Obp1) => (&cgb.ocp, 1),
+            (false, Palette::BgWin) => (&cgb.bcp, pixel.meta.cpal),
+            (false, Palette::Obp0 | Palette::Obp1) => (&cgb.ocp, pixel.meta.cpal),
+        };
+        // Look up color in palette memory
+        Some(cram.borrow().color(pal, idx))
+    }
 }
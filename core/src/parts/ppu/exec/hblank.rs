@@ -1,7 +1,9 @@
 use log::debug;
 use rugby_arch::reg::Register;
+use rugby_arch::Byte;
 
 use super::draw::Draw;
+use super::scan::Scan;
 use super::{Interrupt, Mode, Ppu, LCD};
 
 /// Mode 0: Horizontal blank.
@@ -12,15 +14,42 @@ impl HBlank {
     /// Maximum number of dots per scanline.
     pub const DOTS: u16 = 456;
 
+    /// Dot at which `LY` is incremented.
+    ///
+    /// The scanline is incremented a few dots before the end of the current
+    /// scanline, ahead of the mode 2 interrupt.
+    pub const NEXT: u16 = Self::DOTS - 8;
+
+    /// Dot at which mode 2 is reported.
+    ///
+    /// The next scanline's mode is reported (and its interrupt requested) a
+    /// few dots before OAM is scanned.
+    pub const EARLY: u16 = Self::DOTS - 4;
+
     pub fn exec(self, ppu: &mut Ppu) -> Mode {
+        // Increment scanline
+        if ppu.etc.dot + 1 == Self::NEXT {
+            ppu.reg.ly.store(ppu.reg.ly.load() + 1);
+        }
+
+        // Check for OAM corruption
+        if Self::early(ppu) && u16::from(ppu.reg.ly.load()) < LCD.ht {
+            if let Some(idu) = ppu.idu.take() {
+                Scan::corrupt(ppu, idu);
+            }
+        }
+
         // Determine next mode
         if ppu.etc.dot + 1 < Self::DOTS {
-            // Continue vblank
+            // Continue hblank
             Mode::HBlank(self)
         } else {
-            // Increment scanline
-            let ly = ppu.reg.ly.load() + 1;
-            ppu.reg.ly.store(ly);
+            let ly = ppu.reg.ly.load();
+            // Leave the first scanline
+            //
+            // NOTE: Upon enabling, the PPU idles through the last few dots of
+            //       a preceding hblank, which must not end the first scanline.
+            ppu.etc.init &= ly == 0;
 
             // Determine next scanline type
             if u16::from(ly) < LCD.ht {
@@ -39,6 +68,27 @@ impl HBlank {
     }
 }
 
+impl HBlank {
+    /// Checks if the next scanline has already begun.
+    ///
+    /// Mode 2 is reported shortly after `LY` is incremented, ahead of OAM
+    /// being scanned.
+    pub fn early(ppu: &Ppu) -> bool {
+        ppu.etc.dot >= Self::EARLY && ppu.reg.ly.load() != 0
+    }
+
+    /// Checks if OAM is in use for the next scanline.
+    ///
+    /// OAM is accessed from when `LY` is incremented, shortly before mode 2 is
+    /// reported.
+    pub fn fetch(ppu: &Ppu) -> bool {
+        // NOTE: As `LY` is incremented at the end of a dot, this is observed a
+        //       dot early.
+        let ly = ppu.reg.ly.load() + Byte::from(ppu.etc.dot + 1 == Self::NEXT);
+        ppu.etc.dot + 1 >= Self::NEXT && ly != 0 && u16::from(ly) < LCD.ht
+    }
+}
+
 impl From<Draw> for HBlank {
     fn from(Draw { .. }: Draw) -> Self {
         Self
@@ -1,5 +1,8 @@
+use std::io::{self, ErrorKind, Read, Write};
+
 use ppu::{Interrupt, Lcdc, Ppu, LCD};
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::Byte;
 
 use self::draw::Draw;
@@ -7,6 +10,7 @@ use self::hblank::HBlank;
 use self::scan::Scan;
 use self::vblank::VBlank;
 use super::super::ppu;
+use super::lock::Lock;
 
 pub mod draw;
 pub mod hblank;
@@ -32,30 +36,79 @@ impl Mode {
         // Update status register
         let ly = ppu.reg.ly.load();
         let lyc = ppu.reg.lyc.load();
+        // NOTE: The LY=LYC comparison is suppressed from when `LY` is
+        //       incremented until the next scanline is reported. As `LY` is
+        //       incremented at the end of a dot, this is observed a dot early.
+        let eq = ly == lyc && !(HBlank::NEXT..HBlank::EARLY).contains(&(ppu.etc.dot + 1));
+        let mode = self.stat(ppu);
         let stat = {
-            let mut stat = ppu.reg.stat.load();
-            stat ^= (0x03 & stat) ^ Byte::from(&self);
-            stat ^= (0x04 & stat) ^ Byte::from(ly == lyc) << 2;
+            let mut stat = ppu.reg.stat.load() | 0x80;
+            stat ^= (0x03 & stat) ^ mode;
+            stat ^= (0x04 & stat) ^ Byte::from(eq) << 2;
             stat
         };
         ppu.reg.stat.store(stat);
 
-        // Trigger interrupts
-        if ppu.etc.dot == 0 {
+        // Update interrupt line
+        //
+        // Each enabled source is OR'ed together into a single line, with an
+        // interrupt only being requested on its rising edge. As such, while
+        // any source holds the line high, others are blocked from requesting
+        // an interrupt.
+        let line = {
             let mut int = 0;
             // LYC=LY
-            int |= Byte::from(lyc == ly) << 6;
+            int |= Byte::from(eq) << 6;
             // Mode 2
-            int |= Byte::from(matches!(self, Mode::Scan(_))) << 5;
+            //
+            // NOTE: Other than on the first scanline, the mode 2 source is
+            //       raised a few dots before OAM is scanned. It is also raised
+            //       upon entering vblank, despite mode 2 not occurring.
+            int |= Byte::from(match self {
+                Mode::Scan(_) => !ppu.etc.init,
+                Mode::HBlank(_) => HBlank::early(ppu) && u16::from(ly) <= LCD.ht,
+                _ => false,
+            }) << 5;
             // Mode 1
             int |= Byte::from(matches!(self, Mode::VBlank(_))) << 4;
             // Mode 0
-            int |= Byte::from(matches!(self, Mode::HBlank(_))) << 3;
-            // Check for interrupts
-            if int & (stat & 0x78) != 0 {
-                // Request an interrupt
-                ppu.int.raise(Interrupt::LcdStat);
-            }
+            //
+            // NOTE: Unlike `STAT`, the mode 0 source is only raised once the
+            //       pipeline has finished drawing.
+            int |= Byte::from(matches!(self, Mode::HBlank(_)) && mode == 0) << 3;
+            // Check enabled sources
+            int & (stat & 0x78) != 0
+        };
+        if line && !std::mem::replace(&mut ppu.etc.line, line) {
+            // Request an interrupt
+            ppu.int.raise(Interrupt::LcdStat);
+        }
+        ppu.etc.line = line;
+
+        // Update memory locks
+        //
+        // NOTE: OAM is in use from when `LY` is incremented, although writes
+        //       are only blocked once mode 2 is reported. Just before mode 3
+        //       is reported, the PPU switches over to VRAM, briefly allowing
+        //       OAM writes while blocking VRAM reads.
+        let next = match self {
+            Mode::HBlank(_) => HBlank::fetch(ppu),
+            Mode::VBlank(_) => ppu.etc.dot + 1 >= HBlank::NEXT && ly == 0,
+            _ => false,
+        };
+        let swap = matches!(self, Mode::Scan(_))
+            && mode == 0b10
+            && ppu.etc.dot >= Scan::DOTS - Draw::LEAD - 4;
+        {
+            let mut acc = ppu.acc.borrow_mut();
+            acc.oam = Lock {
+                read: matches!(mode, 0b10 | 0b11) || next,
+                write: matches!(mode, 0b10 | 0b11) && !swap,
+            };
+            acc.vram = Lock {
+                read: mode == 0b11 || swap,
+                write: mode == 0b11,
+            };
         }
 
         // Execute the current mode
@@ -66,6 +119,14 @@ impl Mode {
             Mode::VBlank(vblank) => vblank.exec(ppu),
         };
 
+        // Discard unobserved IDU accesses
+        ppu.idu.take();
+
+        // Record registers for the next dot
+        ppu.etc.pal = [&ppu.reg.bgp, &ppu.reg.obp0, &ppu.reg.obp1].map(Register::load);
+        ppu.etc.lcdc = ppu.reg.lcdc.load();
+        ppu.etc.wx = [ppu.etc.wx[1], ppu.reg.wx.load()];
+
         // Increment dot count
         ppu.etc.dot += 1;
         ppu.etc.dot %= HBlank::DOTS;
@@ -75,12 +136,58 @@ impl Mode {
     }
 }
 
+impl Mode {
+    /// Gets the mode as reported by `STAT`.
+    ///
+    /// Mode changes are not always reported in lockstep with the PPU:
+    /// - The first scanline after enabling reports mode 0 in place of mode 2,
+    ///   as OAM is not scanned.
+    /// - Both mode 3 and mode 0 are reported a few dots before the rendering
+    ///   pipeline begins and finishes respectively, including any sprite
+    ///   fetches still to come.
+    /// - Mode 2 is reported a dot before its interrupt is requested, alongside
+    ///   the LY=LYC comparison resuming.
+    fn stat(&self, ppu: &mut Ppu) -> Byte {
+        let ly = ppu.reg.ly.load();
+        let next = ppu.etc.dot + 1 >= HBlank::EARLY && ly != 0 && u16::from(ly) < LCD.ht;
+        match self {
+            Mode::Scan(_) if ppu.etc.dot >= Scan::DOTS - Draw::LEAD => 0b11,
+            Mode::Scan(_) if ppu.etc.init => 0b00,
+            Mode::Draw(draw) if draw.ending(ppu) => 0b00,
+            Mode::HBlank(_) if next => 0b10,
+            mode => mode.into(),
+        }
+    }
+}
+
 impl Default for Mode {
     fn default() -> Self {
         Self::Scan(Scan::default())
     }
 }
 
+impl Snapshot for Mode {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        Byte::from(self).save(buf)?;
+        match self {
+            Mode::Scan(scan) => scan.save(buf),
+            Mode::Draw(draw) => draw.save(buf),
+            Mode::HBlank(_) | Mode::VBlank(_) => Ok(()),
+        }
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = match Byte::decode(buf)? {
+            0b10 => Mode::Scan(Snapshot::decode(buf)?),
+            0b11 => Mode::Draw(Snapshot::decode(buf)?),
+            0b00 => Mode::HBlank(HBlank),
+            0b01 => Mode::VBlank(VBlank),
+            _ => return Err(ErrorKind::InvalidData.into()),
+        };
+        Ok(())
+    }
+}
+
 #[rustfmt::skip]
 impl From<&Mode> for Byte {
     fn from(mode: &Mode) -> Self {
@@ -1,10 +1,14 @@
+use std::io::{self, Read, Write};
+
 use log::{debug, trace};
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::Register;
+use rugby_arch::snap::Snapshot;
 use rugby_arch::Word;
 
 use super::hblank::HBlank;
 use super::ppu::meta::Sprite;
+use super::ppu::Idu;
 use super::vblank::VBlank;
 use super::{Lcdc, Mode, Ppu};
 
@@ -18,7 +22,17 @@ pub struct Scan {
 }
 
 impl Scan {
+    /// Number of dots per scan.
+    pub const DOTS: u16 = 80;
+
     pub fn exec(mut self, ppu: &mut Ppu) -> Mode {
+        // Check for OAM corruption
+        //
+        // NOTE: OAM is not accessed during the first scanline after enabling.
+        if let Some(idu) = ppu.idu.take().filter(|_| !ppu.etc.init) {
+            Self::corrupt(ppu, idu);
+        }
+
         // Scanning a single entry takes 2 dots
         if ppu.etc.dot % 2 == 0 {
             // Sprites should only be scanned when:
@@ -41,20 +55,19 @@ impl Scan {
                 // Parse sprite from bytes
                 let obj = Sprite::from(obj);
 
-                // Record sprites to be rendered that:
+                // Record sprites to be rendered that are visible this
+                // scanline
                 //
-                // 1. Are not hidden (x-coordinate is zero)
-                let not_hidden = obj.xpos != 0;
-                // 2. Are visible this scanline
+                // NOTE: Sprites are recorded regardless of their x-coordinate,
+                //       such that those offscreen still count towards the
+                //       limit.
                 let is_visible = {
                     let ypos = obj.ypos;
                     let size = [8, 16][usize::from(ppu.lcdc(Lcdc::ObjSize))];
                     let line = ppu.reg.ly.load().saturating_add(16);
                     (ypos..ypos.saturating_add(size)).contains(&line)
                 };
-                //
-                // When all conditions are met, push scanned sprite
-                if not_hidden && is_visible {
+                if is_visible {
                     trace!("scanned sprite: {obj:?}");
                     self.objs.push(obj);
                 }
@@ -69,7 +82,7 @@ impl Scan {
         self.addr += 2;
 
         // Determine next mode
-        if ppu.etc.dot + 1 < 80 {
+        if ppu.etc.dot + 1 < Self::DOTS {
             Mode::Scan(self)
         } else {
             // Enter draw
@@ -79,6 +92,89 @@ impl Scan {
     }
 }
 
+impl Scan {
+    /// Corrupts OAM upon an IDU access.
+    ///
+    /// When the IDU places an address in `$FE00..=$FEFF` on the bus during an
+    /// OAM scan, the row of OAM currently being accessed by the PPU is
+    /// corrupted. See more details [here][bug].
+    ///
+    /// [bug]: https://gbdev.io/pandocs/OAM_Corruption_Bug.html
+    pub(super) fn corrupt(ppu: &mut Ppu, idu: Idu) {
+        // Only the DMG is affected
+        if ppu.cgb.is_some() || !(0xfe00..=0xfeff).contains(&idu.addr()) {
+            return;
+        }
+        // Each 8-byte row is accessed over a single M-cycle
+        //
+        // NOTE: OAM is accessed from when `LY` is incremented, which occurs a
+        //       few dots before the scan begins.
+        let dot = ppu.etc.dot + HBlank::DOTS - HBlank::NEXT;
+        let row = usize::from(dot % HBlank::DOTS / 4);
+        // The first row is never corrupted, nor are rows past the end of OAM
+        if row == 0 || row >= 20 {
+            return;
+        }
+        trace!("corrupted OAM row {row}: {idu:?}");
+
+        // Access OAM as rows of words
+        let mut oam = ppu.mem.oam.borrow_mut();
+        let oam = oam.inner_mut();
+        let get = |oam: &[u8], row: usize, idx: usize| {
+            let addr = 8 * row + 2 * idx;
+            Word::from_le_bytes([oam[addr], oam[addr + 1]])
+        };
+        let set = |oam: &mut [u8], row: usize, idx: usize, word: Word| {
+            let addr = 8 * row + 2 * idx;
+            oam[addr..addr + 2].copy_from_slice(&word.to_le_bytes());
+        };
+        let copy = |oam: &mut [u8], src: usize, dst: usize, idx: usize| {
+            oam.copy_within(8 * src + 2 * idx..8 * (src + 1), 8 * dst + 2 * idx);
+        };
+
+        // Apply corruption
+        match idu {
+            Idu::Write(_) => {
+                let a = get(oam, row, 0);
+                let b = get(oam, row - 1, 0);
+                let c = get(oam, row - 1, 2);
+                set(oam, row, 0, ((a ^ c) & (b ^ c)) ^ c);
+                copy(oam, row - 1, row, 1);
+            }
+            Idu::Read(_) => {
+                // Rows surrounding the accessed row are first corrupted when
+                // it lies between the fifth and second-last rows
+                if (4..19).contains(&row) {
+                    let a = get(oam, row - 2, 0);
+                    let b = get(oam, row - 1, 0);
+                    let c = get(oam, row, 0);
+                    let d = get(oam, row - 1, 2);
+                    set(oam, row - 1, 0, (b & (a | c | d)) | (a & c & d));
+                    copy(oam, row - 1, row - 2, 0);
+                }
+                // Followed by a regular read corruption
+                let a = get(oam, row, 0);
+                let b = get(oam, row - 1, 0);
+                let c = get(oam, row - 1, 2);
+                set(oam, row, 0, b | (a & c));
+                copy(oam, row - 1, row, 1);
+            }
+        }
+    }
+}
+
+impl Snapshot for Scan {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.addr.save(buf)?;
+        self.objs.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.addr.restore(buf)?;
+        self.objs.restore(buf)
+    }
+}
+
 impl From<HBlank> for Scan {
     fn from(HBlank { .. }: HBlank) -> Self {
         Self {
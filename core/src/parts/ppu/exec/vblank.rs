@@ -2,6 +2,7 @@ use log::debug;
 use rugby_arch::reg::Register;
 
 use super::hblank::HBlank;
+use super::scan::Scan;
 use super::{Mode, Ppu, LCD};
 
 /// Mode 1: Vertical blank.
@@ -12,27 +13,38 @@ impl VBlank {
     /// Number of scanlines of vblank.
     pub const LAST: u16 = LCD.ht + 10;
 
+    /// Dot at which `LY` is reset on the last scanline.
+    pub const WRAP: u16 = 1;
+
     pub fn exec(self, ppu: &mut Ppu) -> Mode {
+        let ly = ppu.reg.ly.load();
+        // Reset scanline early on the last scanline
+        //
+        // NOTE: `LY` only reads as 153 for the first few dots of the last
+        //       scanline, reading as 0 for the remainder.
+        if ppu.etc.dot + 1 == Self::WRAP && u16::from(ly) == Self::LAST - 1 {
+            ppu.reg.ly.store(0);
+        }
+        // Increment scanline
+        if ppu.etc.dot + 1 == HBlank::NEXT && ly != 0 {
+            ppu.reg.ly.store(ly + 1);
+        }
+
+        // Check for OAM corruption
+        if ppu.etc.dot >= HBlank::NEXT && ly == 0 {
+            if let Some(idu) = ppu.idu.take() {
+                Scan::corrupt(ppu, idu);
+            }
+        }
+
         // Determine next mode
-        if ppu.etc.dot + 1 < HBlank::DOTS {
+        if ppu.etc.dot + 1 < HBlank::DOTS || ly != 0 {
             // Continue vblank
             Mode::VBlank(self)
         } else {
-            // Increment scanline
-            let ly = ppu.reg.ly.load() + 1;
-            ppu.reg.ly.store(ly);
-
-            // Determine next mode
-            if u16::from(ly) < Self::LAST {
-                // Continue vblank
-                Mode::VBlank(self)
-            } else {
-                // Reset scanline
-                ppu.reg.ly.store(0);
-                // Enter scan
-                debug!("entered mode 2: scan OAM");
-                Mode::Scan(self.into())
-            }
+            // Enter scan
+            debug!("entered mode 2: scan OAM");
+            Mode::Scan(self.into())
         }
     }
 }
@@ -0,0 +1,113 @@
+//! Graphics memory locks.
+
+use std::io::{self, Read, Write};
+
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Byte, Shared, Word};
+
+/// Graphics memory access.
+///
+/// Tracks which graphics memory is in use by the PPU, and as such
+/// inaccessible to the CPU.
+#[derive(Debug, Default)]
+pub struct Access {
+    /// Object memory.
+    pub(super) oam: Lock,
+    /// Video memory.
+    pub(super) vram: Lock,
+}
+
+impl Snapshot for Access {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.oam.save(buf)?;
+        self.vram.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.oam.restore(buf)?;
+        self.vram.restore(buf)
+    }
+}
+
+/// Memory lock.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct Lock {
+    /// Reads are blocked.
+    pub read: bool,
+    /// Writes are blocked.
+    pub write: bool,
+}
+
+impl Snapshot for Lock {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.read.save(buf)?;
+        self.write.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.read.restore(buf)?;
+        self.write.restore(buf)
+    }
+}
+
+/// Graphics memory region.
+#[derive(Clone, Copy, Debug)]
+pub enum Region {
+    /// Object memory.
+    Oam,
+    /// Video memory.
+    Vram,
+}
+
+/// Graphics memory guard.
+///
+/// Blocks the CPU's accesses to a region of graphics memory while it is in use
+/// by the PPU, during which reads return `$FF` and writes are ignored. See more
+/// details [here][access].
+///
+/// [access]: https://gbdev.io/pandocs/Accessing_VRAM_and_OAM.html
+#[derive(Debug)]
+pub struct Guard {
+    /// Memory access.
+    acc: Shared<Access>,
+    /// Guarded region.
+    mem: Region,
+}
+
+impl Guard {
+    /// Constructs a new `Guard`.
+    #[must_use]
+    pub fn new(acc: Shared<Access>, mem: Region) -> Self {
+        Self { acc, mem }
+    }
+
+    /// Gets the guarded region's lock.
+    fn lock(&self) -> Lock {
+        let acc = self.acc.borrow();
+        match self.mem {
+            Region::Oam => acc.oam,
+            Region::Vram => acc.vram,
+        }
+    }
+}
+
+/// Accesses are only claimed while blocked, otherwise falling through to the
+/// underlying bus.
+impl Memory for Guard {
+    fn read(&self, _: Word) -> Result<Byte> {
+        if self.lock().read {
+            Ok(0xff)
+        } else {
+            Err(Error::Range)
+        }
+    }
+
+    fn write(&mut self, _: Word, _: Byte) -> Result<()> {
+        if self.lock().write {
+            Ok(())
+        } else {
+            Err(Error::Range)
+        }
+    }
+}
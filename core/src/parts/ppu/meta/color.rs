@@ -1,4 +1,7 @@
-use rugby_arch::Byte;
+use std::io::{self, Read, Write};
+
+use rugby_arch::snap::Snapshot;
+use rugby_arch::{Byte, Word};
 
 use crate::api::part::video::Pixel;
 
@@ -29,3 +32,59 @@ impl From<Byte> for Color {
 }
 
 impl Pixel for Color {}
+
+impl Snapshot for Color {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        (*self as Byte).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = Byte::decode(buf)?.into();
+        Ok(())
+    }
+}
+
+/// 15-bit RGB color.
+///
+/// Used by the CGB, with each channel encoded as 5-bits within
+/// `0bXBBBBBGGGGGRRRRR`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Rgb(pub Word);
+
+impl Rgb {
+    /// Gets the red channel.
+    #[must_use]
+    pub fn red(self) -> Byte {
+        (self.0 & 0x1f) as Byte
+    }
+
+    /// Gets the green channel.
+    #[must_use]
+    pub fn green(self) -> Byte {
+        (self.0 >> 5 & 0x1f) as Byte
+    }
+
+    /// Gets the blue channel.
+    #[must_use]
+    pub fn blue(self) -> Byte {
+        (self.0 >> 10 & 0x1f) as Byte
+    }
+}
+
+impl From<[Byte; 2]> for Rgb {
+    fn from(data: [Byte; 2]) -> Self {
+        Self(Word::from_le_bytes(data) & 0x7fff)
+    }
+}
+
+impl Pixel for Rgb {}
+
+impl Snapshot for Rgb {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.0.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.0.restore(buf)
+    }
+}
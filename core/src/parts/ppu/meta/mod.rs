@@ -1,13 +1,19 @@
 //! Graphics metadata.
 
+use std::io::{self, ErrorKind, Read, Write};
+
+use rugby_arch::snap::Snapshot;
+
 mod color;
 mod obj;
 mod pixel;
+mod tag;
 mod tile;
 
-pub use color::Color;
+pub use color::{Color, Rgb};
 pub use obj::{Attributes, Sprite};
 pub use pixel::{Meta, Pixel};
+pub use tag::{Source, Tagged};
 pub use tile::{Row, Tile};
 
 /// Graphics layer.
@@ -27,6 +33,19 @@ pub enum Layer {
     Sprite,
 }
 
+impl Snapshot for Layer {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        (*self as u8).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = *[Self::Background, Self::Window, Self::Sprite]
+            .get(usize::from(u8::decode(buf)?))
+            .ok_or(ErrorKind::InvalidData)?;
+        Ok(())
+    }
+}
+
 /// Monochrome palettes.
 ///
 /// This register reassigns color values to pixels according to how palette
@@ -52,3 +71,16 @@ impl Palette {
         [Self::Obp0, Self::Obp1][usize::from(flag)]
     }
 }
+
+impl Snapshot for Palette {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        (*self as u8).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = *[Self::BgWin, Self::Obp0, Self::Obp1]
+            .get(usize::from(u8::decode(buf)?))
+            .ok_or(ErrorKind::InvalidData)?;
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
+use std::io::{self, Read, Write};
+
+use rugby_arch::snap::Snapshot;
 use rugby_arch::Byte;
 
 use super::pixel::Meta;
 
 /// Sprite metadata.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Sprite {
     /// Byte 0: Y Position.
     pub ypos: Byte,
@@ -40,15 +43,36 @@ impl From<[Byte; 4]> for Sprite {
     }
 }
 
+impl Snapshot for Sprite {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.ypos.save(buf)?;
+        self.xpos.save(buf)?;
+        self.tnum.save(buf)?;
+        self.attr.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.ypos.restore(buf)?;
+        self.xpos.restore(buf)?;
+        self.tnum.restore(buf)?;
+        self.attr.restore(buf)
+    }
+}
+
 /// Sprite attributes.
 ///
-/// Attributes are encoded as `0bZYXP0000`, where:
+/// Attributes are encoded as `0bZYXPBCCC`, where:
+/// - `C` is the color palette (CGB).
+/// - `B` is the VRAM bank (CGB).
 /// - `P` is the object palette.
 /// - `X` is the x-flip flag.
 /// - `Y` is the y-flip flag.
 /// - `Z` is the priority flag.
+///
+/// On the CGB, background map attributes share this encoding (other than `P`,
+/// which is unused).
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Attributes {
     /// Priority flag.
     ///
@@ -67,6 +91,14 @@ pub struct Attributes {
     ///
     /// Selects between using `obp0` or `obp1`.
     pub objp: bool,
+    /// VRAM bank (CGB).
+    ///
+    /// Selects which VRAM bank tile data is read from.
+    pub bank: bool,
+    /// Color palette (CGB).
+    ///
+    /// Selects which color palette is used.
+    pub cpal: Byte,
 }
 
 impl Attributes {
@@ -85,6 +117,30 @@ impl From<Byte> for Attributes {
             yflip: byte & (1 << 6) != 0,
             xflip: byte & (1 << 5) != 0,
             objp:  byte & (1 << 4) != 0,
+            bank:  byte & (1 << 3) != 0,
+            cpal:  byte & 0b0000_0111,
         }
     }
 }
+
+impl From<&Attributes> for Byte {
+    fn from(attr: &Attributes) -> Self {
+        Byte::from(attr.prty) << 7
+            | Byte::from(attr.yflip) << 6
+            | Byte::from(attr.xflip) << 5
+            | Byte::from(attr.objp) << 4
+            | Byte::from(attr.bank) << 3
+            | attr.cpal & 0b0000_0111
+    }
+}
+
+impl Snapshot for Attributes {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        Byte::from(self).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        *self = Byte::decode(buf)?.into();
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
-use super::{Attributes, Color, Palette};
+use std::io::{self, Read, Write};
+
+use rugby_arch::snap::Snapshot;
+use rugby_arch::Byte;
+
+use super::{Attributes, Color, Layer, Palette};
 
 /// Pre-rendered pixel.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Pixel {
     /// Color value.
     pub col: Color,
@@ -27,10 +32,10 @@ impl Pixel {
         if sprite.col == Color::C0 {
             winbg
         }
-        // 2. If the BG-to-OBJ priority bit is 1 and the color number of the
+        // 2. If either BG-to-OBJ priority bit is 1 and the color number of the
         //    background pixel is anything other than 0, the background pixel is
-        //    pushed to the LCD.
-        else if sprite.meta.bgp && winbg.col != Color::C0 {
+        //    pushed to the LCD. (Only sprites have this bit, except on CGB.)
+        else if (sprite.meta.bgp || winbg.meta.bgp) && winbg.col != Color::C0 {
             winbg
         }
         // 3. If none of the above conditions apply, the Sprite Pixel is pushed
@@ -42,13 +47,29 @@ impl Pixel {
     }
 }
 
+impl Snapshot for Pixel {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.col.save(buf)?;
+        self.meta.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.col.restore(buf)?;
+        self.meta.restore(buf)
+    }
+}
+
 /// Pixel metadata.
 #[derive(Clone, Debug)]
 pub struct Meta {
+    /// Graphics layer.
+    pub layer: Layer,
     /// Monochrome palette.
     pub pal: Palette,
     /// Background priority.
     pub bgp: bool,
+    /// Color palette (CGB).
+    pub cpal: Byte,
 }
 
 impl Meta {
@@ -56,8 +77,21 @@ impl Meta {
     #[must_use]
     pub fn bgwin() -> Self {
         Self {
+            layer: Layer::Background,
             pal: Palette::BgWin,
             bgp: false,
+            cpal: 0,
+        }
+    }
+
+    /// Constructs background or window metadata from map attributes (CGB).
+    #[must_use]
+    pub fn tile(layer: Layer, attr: &Attributes) -> Self {
+        Self {
+            layer,
+            pal: Palette::BgWin,
+            bgp: attr.prty,
+            cpal: attr.cpal,
         }
     }
 
@@ -65,8 +99,32 @@ impl Meta {
     #[must_use]
     pub fn sprite(attr: &Attributes) -> Self {
         Self {
+            layer: Layer::Sprite,
             pal: Palette::obp(attr.objp),
             bgp: attr.prty,
+            cpal: attr.cpal,
         }
     }
 }
+
+impl Default for Meta {
+    fn default() -> Self {
+        Self::bgwin()
+    }
+}
+
+impl Snapshot for Meta {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.layer.save(buf)?;
+        self.pal.save(buf)?;
+        self.bgp.save(buf)?;
+        self.cpal.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.layer.restore(buf)?;
+        self.pal.restore(buf)?;
+        self.bgp.restore(buf)?;
+        self.cpal.restore(buf)
+    }
+}
@@ -0,0 +1,74 @@
+use std::io::{self, ErrorKind, Read, Write};
+
+use rugby_arch::snap::Snapshot;
+use rugby_arch::Byte;
+
+use super::{Color, Layer, Meta, Palette};
+use crate::api::part::video::Pixel;
+
+/// Pixel source.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Source {
+    /// Background layer.
+    #[default]
+    Background,
+    /// Window layer.
+    Window,
+    /// Sprite using object palette 0.
+    Obp0,
+    /// Sprite using object palette 1.
+    Obp1,
+}
+
+impl From<&Meta> for Source {
+    fn from(meta: &Meta) -> Self {
+        match (meta.layer, meta.pal) {
+            (_, Palette::Obp0) => Self::Obp0,
+            (_, Palette::Obp1) => Self::Obp1,
+            (Layer::Window, Palette::BgWin) => Self::Window,
+            (_, Palette::BgWin) => Self::Background,
+        }
+    }
+}
+
+/// Layer-tagged pixel.
+///
+/// Records which layer and palette produced a pixel, allowing it to be
+/// recolored separately from other layers.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Tagged {
+    /// Pixel source.
+    pub src: Source,
+    /// Raw color index.
+    ///
+    /// Color prior to being assigned by the palette register.
+    pub idx: Color,
+    /// Palette color.
+    ///
+    /// Color assigned by the palette register, as is drawn to the screen.
+    pub col: Color,
+}
+
+impl Pixel for Tagged {}
+
+/// Tagged pixels are packed into a single byte, as `0b00SSIICC`.
+impl Snapshot for Tagged {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        ((self.src as Byte) << 4 | (self.idx as Byte) << 2 | self.col as Byte).save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        let data = Byte::decode(buf)?;
+        self.src = *[
+            Source::Background,
+            Source::Window,
+            Source::Obp0,
+            Source::Obp1,
+        ]
+        .get(usize::from(data >> 4))
+        .ok_or(ErrorKind::InvalidData)?;
+        self.idx = Color::from(data >> 2);
+        self.col = Color::from(data);
+        Ok(())
+    }
+}
@@ -1,12 +1,16 @@
 //! Graphics model.
 
+use std::io::{self, Read, Write};
+
 use rugby_arch::mem::Ram;
 use rugby_arch::mio::{Bus, Mmio};
 use rugby_arch::reg::{Port, Register};
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared};
 
+use self::cgb::Cgb;
 use self::exec::hblank::HBlank;
-use self::exec::vblank::VBlank;
+use super::cpu::sm83::Idu;
 use super::dma::Control as Dma;
 use super::pic::{self, Interrupt};
 use crate::api::part::video::{self, Aspect, Video as Api};
@@ -14,13 +18,16 @@ use crate::dmg::pcb::Sram;
 
 mod blk;
 mod exec;
+mod lock;
 
+pub mod cgb;
 #[cfg(feature = "debug")]
 pub mod dbg;
 pub mod meta;
 
 pub use self::exec::Mode;
-pub use self::meta::Color;
+pub use self::lock::{Access, Guard, Region};
+pub use self::meta::{Color, Rgb, Source, Tagged};
 
 /// Frame rate.
 ///
@@ -35,6 +42,9 @@ pub const LCD: Aspect = Aspect { wd: 160, ht: 144 };
 /// Display framebuffer.
 pub type Frame = video::Frame<Color, { LCD.depth() }>;
 
+/// Layer-tagged framebuffer.
+pub type Layers = video::Frame<Tagged, { LCD.depth() }>;
+
 /// Video RAM.
 ///
 /// 8 KiB RAM used to store tile [data][tdata] and [maps][tmaps].
@@ -137,6 +147,12 @@ pub struct Ppu {
     etc: Internal,
     /// Interrupt line.
     int: pic::Line,
+    /// IDU access line.
+    idu: Shared<Option<Idu>>,
+    /// Memory access.
+    acc: Shared<Access>,
+    /// Color extension (CGB).
+    pub cgb: Option<Cgb>,
 }
 
 /// Graphics internals.
@@ -144,10 +160,31 @@ pub struct Ppu {
 struct Internal {
     /// Framebuffer.
     buf: Frame,
+    /// Layer-tagged framebuffer.
+    tag: Box<Layers>,
     /// Cycle count.
     dot: u16,
     /// Window line.
     ywin: Byte,
+    /// First scanline since enabling.
+    init: bool,
+    /// Previous palettes.
+    ///
+    /// Holds the value of each monochrome palette as of the previous dot.
+    pal: [Byte; 3],
+    /// Previous LCD control.
+    ///
+    /// Holds the value of `LCDC` as of the previous dot.
+    lcdc: Byte,
+    /// Previous window X positions.
+    ///
+    /// Holds the value of `WX` as of the previous two dots.
+    wx: [Byte; 2],
+    /// Interrupt line.
+    ///
+    /// Holds the state of the OR'ed `STAT` interrupt sources as of the
+    /// previous dot.
+    line: bool,
     /// Graphics mode.
     mode: Mode,
 }
@@ -158,12 +195,52 @@ impl Internal {
     }
 }
 
+/// The framebuffer is rebuilt from the layer-tagged framebuffer, so is not
+/// saved.
+impl Snapshot for Internal {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.tag.save(buf)?;
+        self.dot.save(buf)?;
+        self.ywin.save(buf)?;
+        self.init.save(buf)?;
+        self.pal.save(buf)?;
+        self.lcdc.save(buf)?;
+        self.wx.save(buf)?;
+        self.line.save(buf)?;
+        self.mode.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.tag.restore(buf)?;
+        for (px, tag) in self.buf.iter_mut().zip(self.tag.iter()) {
+            *px = tag.col;
+        }
+        self.dot.restore(buf)?;
+        self.ywin.restore(buf)?;
+        self.init.restore(buf)?;
+        self.pal.restore(buf)?;
+        self.lcdc.restore(buf)?;
+        self.wx.restore(buf)?;
+        self.line.restore(buf)?;
+        self.mode.restore(buf)
+    }
+}
+
 impl Default for Internal {
     fn default() -> Self {
         Self {
             buf: [Color::default(); LCD.depth()],
+            tag: vec![Tagged::default(); LCD.depth()]
+                .into_boxed_slice()
+                .try_into()
+                .unwrap(),
             dot: u16::default(),
             ywin: Byte::default(),
+            init: bool::default(),
+            pal: <[Byte; 3]>::default(),
+            lcdc: Byte::default(),
+            wx: <[Byte; 2]>::default(),
+            line: bool::default(),
             mode: Mode::default(),
         }
     }
@@ -172,15 +249,48 @@ impl Default for Internal {
 impl Ppu {
     /// Constructs a new `Ppu`.
     #[must_use]
-    pub fn new(vram: Shared<Vram>, oam: Shared<Oam>, dma: Shared<Dma>, int: pic::Line) -> Self {
+    pub fn new(
+        vram: Shared<Vram>,
+        oam: Shared<Oam>,
+        dma: Shared<Dma>,
+        int: pic::Line,
+        idu: Shared<Option<Idu>>,
+        acc: Shared<Access>,
+    ) -> Self {
         Self {
             reg: Control::new(dma),
             mem: Bank { vram, oam },
             etc: Internal::default(),
             int,
+            idu,
+            acc,
+            cgb: None,
         }
     }
 
+    /// Constructs a new `Ppu` with the color extension (CGB).
+    #[must_use]
+    pub fn with(
+        vram: Shared<Vram>,
+        oam: Shared<Oam>,
+        dma: Shared<Dma>,
+        int: pic::Line,
+        idu: Shared<Option<Idu>>,
+        acc: Shared<Access>,
+        cgb: Cgb,
+    ) -> Self {
+        Self {
+            cgb: Some(cgb),
+            ..Self::new(vram, oam, dma, int, idu, acc)
+        }
+    }
+
+    /// Checks if running in native color mode (CGB).
+    #[must_use]
+    pub fn native(&self) -> bool {
+        self.cgb.as_ref().is_some_and(|cgb| !cgb.compat())
+    }
+
     /// Gets the current execution cycle.
     #[must_use]
     pub fn dot(&self) -> u16 {
@@ -204,6 +314,41 @@ impl Ppu {
     pub fn screen(&self) -> &Frame {
         &self.etc.buf
     }
+
+    /// Get a reference to the PPU's layer-tagged screen.
+    ///
+    /// Parallel to the [screen](Self::screen), additionally recording the
+    /// source of each pixel.
+    #[must_use]
+    pub fn layers(&self) -> &Layers {
+        &self.etc.tag
+    }
+
+    /// Idles while the LCD is disabled.
+    ///
+    /// Disabling the LCD holds the PPU just before the first scanline, with
+    /// LY and the STAT mode reading as zero. Once re-enabled, this first
+    /// scanline does not scan OAM, instead idling in mode 0. The `STAT`
+    /// interrupt line retains its state throughout, whereas graphics memory
+    /// is left accessible.
+    pub fn idle(&mut self) {
+        /// Dot at which the PPU resumes.
+        const DOT: u16 = HBlank::EARLY;
+
+        // Discard IDU accesses
+        self.idu.take();
+        // Unlock graphics memory
+        std::mem::take(&mut *self.acc.borrow_mut());
+        // Reset to the first scanline
+        if self.etc.init && self.etc.dot == DOT {
+            return;
+        }
+        self.reg.ly.store(0);
+        self.reg.stat.store(self.reg.stat.load() & !0x03 | 0x80);
+        self.etc.dot = DOT;
+        self.etc.init = true;
+        self.etc.mode = Mode::HBlank(HBlank);
+    }
 }
 
 impl Api for Ppu {
@@ -220,7 +365,9 @@ impl Api for Ppu {
         // 2. Mode is vertical blank
         let vblank = matches!(self.etc.mode, Mode::VBlank(_));
         // 3. Last scanline of frame
-        let bottom = (VBlank::LAST - 1) == self.reg.ly.load().into();
+        //
+        // NOTE: `LY` has already been reset by the end of the last scanline.
+        let bottom = self.reg.ly.load() == 0;
         // 4. Last dot of scanline
         let finish = (HBlank::DOTS - 1) == self.etc.dot;
         //
@@ -245,12 +392,40 @@ impl Block for Ppu {
     fn reset(&mut self) {
         self.reg.reset();
         self.etc.reset();
+        std::mem::take(&mut *self.acc.borrow_mut());
+        self.cgb.as_mut().map(Block::reset);
     }
 }
 
 impl Mmio for Ppu {
     fn attach(&self, bus: &mut Bus) {
         self.reg.attach(bus);
+        if let Some(cgb) = &self.cgb {
+            cgb.attach(bus);
+        }
+    }
+}
+
+/// Graphics memory is not saved, as it is owned by the motherboard.
+impl Snapshot for Ppu {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.reg.save(buf)?;
+        self.etc.save(buf)?;
+        self.acc.save(buf)?;
+        if let Some(cgb) = &self.cgb {
+            cgb.save(buf)?;
+        }
+        Ok(())
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.reg.restore(buf)?;
+        self.etc.restore(buf)?;
+        self.acc.restore(buf)?;
+        if let Some(cgb) = &mut self.cgb {
+            cgb.restore(buf)?;
+        }
+        Ok(())
     }
 }
 
@@ -380,6 +555,37 @@ impl Mmio for Control {
     }
 }
 
+/// The DMA register is not saved, as it is owned by the DMA controller.
+impl Snapshot for Control {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.lcdc.save(buf)?;
+        self.stat.save(buf)?;
+        self.scy.save(buf)?;
+        self.scx.save(buf)?;
+        self.ly.save(buf)?;
+        self.lyc.save(buf)?;
+        self.bgp.save(buf)?;
+        self.obp0.save(buf)?;
+        self.obp1.save(buf)?;
+        self.wy.save(buf)?;
+        self.wx.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.lcdc.restore(buf)?;
+        self.stat.restore(buf)?;
+        self.scy.restore(buf)?;
+        self.scx.restore(buf)?;
+        self.ly.restore(buf)?;
+        self.lyc.restore(buf)?;
+        self.bgp.restore(buf)?;
+        self.obp0.restore(buf)?;
+        self.obp1.restore(buf)?;
+        self.wy.restore(buf)?;
+        self.wx.restore(buf)
+    }
+}
+
 /// Graphics memory.
 ///
 /// |     Address     |  Size  | Name | Description   |
@@ -455,6 +661,9 @@ pub enum Lcdc {
     /// When cleared, both background and window become blank (white), and
     /// `LCDC[5]` (window enable) is ignored. Only objects may still be
     /// displayed if enabled with `LCDC[1]` (object enable).
+    ///
+    /// In CGB mode, this instead acts as a master priority, such that objects
+    /// are always drawn above the background and window when cleared.
     BgWinEnable = 0b0000_0001,
 }
 
@@ -1,11 +1,12 @@
 //! Serial interface.
 
 use std::collections::VecDeque;
-use std::io::{BufRead, Write};
+use std::io::{self, BufRead, Read, Write};
 
 use log::{debug, trace};
 use rugby_arch::mio::{Bus, Mmio};
 use rugby_arch::reg::{Port, Register};
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared};
 
 use super::pic::{self, Interrupt};
@@ -166,6 +167,32 @@ impl Mmio for Serial {
     }
 }
 
+impl Snapshot for Serial {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        // Registers
+        self.reg.sb.save(buf)?;
+        let sc = self.reg.sc.borrow();
+        [sc.ena, sc.clk].save(buf)?;
+        sc.bit.save(buf)?;
+        // Internals
+        self.etc.ip.save(buf)?;
+        self.etc.rx.save(buf)?;
+        self.etc.tx.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        // Registers
+        self.reg.sb.restore(buf)?;
+        let sc = &mut *self.reg.sc.borrow_mut();
+        [sc.ena, sc.clk] = Snapshot::decode(buf)?;
+        sc.bit.restore(buf)?;
+        // Internals
+        self.etc.ip.restore(buf)?;
+        self.etc.rx.restore(buf)?;
+        self.etc.tx.restore(buf)
+    }
+}
+
 impl Port<Byte> for Serial {
     type Select = Select;
 
@@ -1,8 +1,11 @@
 //! Hardware timer.
 
+use std::io::{self, Read, Write};
+
 use log::{debug, trace};
 use rugby_arch::mio::{Bus, Mmio};
 use rugby_arch::reg::{Port, Register};
+use rugby_arch::snap::Snapshot;
 use rugby_arch::{Block, Byte, Shared};
 
 use super::pic::{self, Interrupt};
@@ -87,15 +90,19 @@ impl Block for Timer {
         self.reg.div.borrow_mut().inc();
 
         // Reload TIMA
-        let reload = self.reg.tima.borrow().rel == reg::Reload::Now;
+        let rel = self.reg.tima.borrow().rel.clone();
         self.reg.tima.borrow_mut().rel.tick();
-        if reload {
+        if rel == reg::Reload::Now {
             // Reload from TMA
             let tma = self.reg.tma.load();
-            self.reg.tima.store(tma);
+            self.reg.tima.borrow_mut().reg.store(tma);
             debug!("timer reloaded");
             // Request an interrupt
             self.int.raise(Interrupt::Timer);
+        } else if rel == reg::Reload::Done {
+            // Writes to TMA during the reload cycle are also reloaded
+            let tma = self.reg.tma.load();
+            self.reg.tima.borrow_mut().reg.store(tma);
         }
 
         // Check if TIMA should be incremented
@@ -127,6 +134,18 @@ impl Mmio for Timer {
     }
 }
 
+impl Snapshot for Timer {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.reg.save(buf)?;
+        self.etc.and.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.reg.restore(buf)?;
+        self.etc.and.restore(buf)
+    }
+}
+
 impl Port<Byte> for Timer {
     type Select = Select;
 
@@ -187,11 +206,30 @@ impl Mmio for Control {
     }
 }
 
+impl Snapshot for Control {
+    fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.div.save(buf)?;
+        self.tima.save(buf)?;
+        self.tma.save(buf)?;
+        self.tac.save(buf)
+    }
+
+    fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+        self.div.restore(buf)?;
+        self.tima.restore(buf)?;
+        self.tma.restore(buf)?;
+        self.tac.restore(buf)
+    }
+}
+
 /// Timer register models.
 pub mod reg {
+    use std::io::{self, ErrorKind, Read, Write};
+
     use log::debug;
     use rugby_arch::mem::Memory;
     use rugby_arch::reg::Register;
+    use rugby_arch::snap::Snapshot;
     use rugby_arch::{Byte, Word};
 
     /// Divider register.
@@ -208,7 +246,7 @@ pub mod reg {
 
         /// Gets the full internal register value.
         #[must_use]
-        pub(super) fn div(&self) -> Word {
+        pub(crate) fn div(&self) -> Word {
             self.0.load()
         }
 
@@ -219,6 +257,16 @@ pub mod reg {
         }
     }
 
+    impl Snapshot for Div {
+        fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+            self.0.save(buf)
+        }
+
+        fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+            self.0.restore(buf)
+        }
+    }
+
     impl Memory for Div {
         fn read(&self, _: Word) -> rugby_arch::mem::Result<Byte> {
             Ok(self.load())
@@ -264,6 +312,8 @@ pub mod reg {
         Wait(u8),
         /// Reload occurring this cycle.
         Now,
+        /// Reload occurred last cycle.
+        Done,
     }
 
     impl Reload {
@@ -277,6 +327,7 @@ pub mod reg {
         pub(super) fn tick(&mut self) {
             *self = match self {
                 Reload::Wait(0) => Reload::Now,
+                Reload::Now => Reload::Done,
                 // Decrement cycles until reload
                 Reload::Wait(mut count) => {
                     // Decrement cycles until reload
@@ -300,6 +351,33 @@ pub mod reg {
         }
     }
 
+    impl Snapshot for Tima {
+        fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+            self.reg.save(buf)?;
+            match self.rel {
+                Reload::None => 0u8.save(buf),
+                Reload::Wait(count) => {
+                    1u8.save(buf)?;
+                    count.save(buf)
+                }
+                Reload::Now => 2u8.save(buf),
+                Reload::Done => 3u8.save(buf),
+            }
+        }
+
+        fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+            self.reg.restore(buf)?;
+            self.rel = match u8::decode(buf)? {
+                0 => Reload::None,
+                1 => Reload::Wait(u8::decode(buf)?),
+                2 => Reload::Now,
+                3 => Reload::Done,
+                _ => return Err(ErrorKind::InvalidData.into()),
+            };
+            Ok(())
+        }
+    }
+
     impl Memory for Tima {
         fn read(&self, _: Word) -> rugby_arch::mem::Result<Byte> {
             Ok(self.load())
@@ -319,8 +397,8 @@ pub mod reg {
         }
 
         fn store(&mut self, value: Self::Value) {
-            // Ignore stores to TIMA right before a reload occurs
-            if self.rel != Reload::Now {
+            // Ignore stores to TIMA during a reload
+            if !matches!(self.rel, Reload::Now | Reload::Done) {
                 self.rel = Reload::None;
                 self.reg.store(value);
             }
@@ -354,6 +432,16 @@ pub mod reg {
         }
     }
 
+    impl Snapshot for Tac {
+        fn save(&self, buf: &mut impl Write) -> io::Result<()> {
+            self.0.save(buf)
+        }
+
+        fn restore(&mut self, buf: &mut impl Read) -> io::Result<()> {
+            self.0.restore(buf)
+        }
+    }
+
     impl Memory for Tac {
         fn read(&self, _: Word) -> rugby_arch::mem::Result<Byte> {
             Ok(self.load())
@@ -560,9 +648,41 @@ mod tests {
                 assert_eq!(timer.reg.tima.load(), 0x00);
                 timer.cycle();
             } // reload    -> 0xfe
+            timer.reg.tima.store(0xfd); // write ignored!
+            for _ in 4..64 {
+                assert_eq!(timer.reg.tima.load(), 0xfe);
+                timer.cycle();
+            } // increment -> 0xff
+            assert_eq!(timer.reg.tima.load(), 0xff);
+        }
+
+        // Test 6
+        {
+            // Configure 65536 Hz timer (64 cycles)
+            let mut timer = Timer::new(line.clone());
+            timer.reg.tac.store(0b110);
+            timer.reg.tma.store(0xfe);
+            timer.reg.tima.store(0xfe);
+
+            for _ in 0..64 {
+                assert_eq!(timer.reg.tima.load(), 0xfe);
+                timer.cycle();
+            } // increment -> 0xff
+            for _ in 0..64 {
+                assert_eq!(timer.reg.tima.load(), 0xff);
+                timer.cycle();
+            } // overflow  -> 0x00
+            for _ in 0..4 {
+                assert_eq!(timer.reg.tima.load(), 0x00);
+                timer.cycle();
+            } // reload    -> 0xfe
+            for _ in 4..5 {
+                assert_eq!(timer.reg.tima.load(), 0xfe);
+                timer.cycle();
+            }
             timer.reg.tima.store(0xfd);
             // overwrite   -> 0xfd
-            for _ in 4..64 {
+            for _ in 5..64 {
                 assert_eq!(timer.reg.tima.load(), 0xfd);
                 timer.cycle();
             } // increment -> 0xfe
@@ -717,8 +837,44 @@ mod tests {
                 assert_eq!(timer.reg.tima.load(), 0x00);
                 timer.cycle();
             } // reload    -> 0xfe
+            timer.reg.tma.store(0x69); // still reloading
+            {
+                assert_eq!(timer.reg.tima.load(), 0xfe);
+                timer.cycle();
+            } // reload    -> 0x69
+            for _ in 5..64 {
+                assert_eq!(timer.reg.tima.load(), 0x69);
+                timer.cycle();
+            } // increment -> 0x6a
+            assert_eq!(timer.reg.tima.load(), 0x6a);
+        }
+
+        // Test 6
+        {
+            // Configure 65536 Hz timer (64 cycles)
+            let mut timer = Timer::new(line.clone());
+            timer.reg.tac.store(0b110);
+            timer.reg.tma.store(0xfe);
+            timer.reg.tima.store(0xfe);
+
+            for _ in 0..64 {
+                assert_eq!(timer.reg.tima.load(), 0xfe);
+                timer.cycle();
+            } // increment -> 0xff
+            for _ in 0..64 {
+                assert_eq!(timer.reg.tima.load(), 0xff);
+                timer.cycle();
+            } // overflow  -> 0x00
+            for _ in 0..4 {
+                assert_eq!(timer.reg.tima.load(), 0x00);
+                timer.cycle();
+            } // reload    -> 0xfe
+            for _ in 4..5 {
+                assert_eq!(timer.reg.tima.load(), 0xfe);
+                timer.cycle();
+            }
             timer.reg.tma.store(0x69); // too late!
-            for _ in 4..64 {
+            for _ in 5..64 {
                 assert_eq!(timer.reg.tima.load(), 0xfe);
                 timer.cycle();
             } // increment -> 0xff
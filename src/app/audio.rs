@@ -1,4 +1,19 @@
 //! Audio API.
 
+use std::io;
+
+use crate::emu::part::audio::Sample;
+
 /// Audio interface.
-pub trait Audio {}
+pub trait Audio {
+    /// Plays audio samples.
+    ///
+    /// Receives samples drained from the emulator, at the sample rate
+    /// configured on its [audio](crate::emu::part::audio::Audio) interface.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the samples could not be
+    /// output by the frontend.
+    fn play(&mut self, buf: &[Sample]) -> io::Result<()>;
+}
@@ -4,6 +4,7 @@ use crate::emu::core::Core;
 
 pub mod audio;
 pub mod joypad;
+pub mod motion;
 pub mod serial;
 pub mod video;
 
@@ -0,0 +1,11 @@
+//! Motion API.
+
+use crate::emu::part::motion::Tilt;
+
+/// Motion interface.
+pub trait Motion {
+    /// Polls motion input for the emulator.
+    ///
+    /// The produced value represents the current tilt of the device.
+    fn tilt(&mut self) -> Tilt;
+}
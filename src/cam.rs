@@ -0,0 +1,158 @@
+//! PNG image source.
+
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::emu::part::camera::{Source, HEIGHT, WIDTH};
+
+/// PNG image sequence.
+///
+/// Supplies decoded images to a camera, advancing to the next image upon each
+/// capture and looping once the sequence ends. Images are converted to
+/// grayscale and scaled to the sensor's resolution.
+#[derive(Debug)]
+pub struct Png {
+    /// Decoded images.
+    imgs: Vec<Box<[u8]>>,
+    /// Next image index.
+    idx: usize,
+}
+
+impl Png {
+    /// Opens a single PNG image.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the file could not be opened
+    /// or decoded.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::sequence([path])
+    }
+
+    /// Opens a sequence of PNG images.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when any file could not be opened
+    /// or decoded, or if the sequence is empty.
+    pub fn sequence(paths: impl IntoIterator<Item = impl AsRef<Path>>) -> Result<Self> {
+        let imgs = paths
+            .into_iter()
+            .map(|path| Ok(BufReader::new(File::open(path)?)))
+            .map(|file| file.and_then(decode))
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(imgs)
+    }
+
+    /// Decodes a sequence of in-memory PNG images.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when any image could not be
+    /// decoded, or if the sequence is empty.
+    pub fn decode<R: Read>(data: impl IntoIterator<Item = R>) -> Result<Self> {
+        let imgs = data.into_iter().map(decode).collect::<Result<Vec<_>>>()?;
+        Self::new(imgs)
+    }
+
+    /// Constructs a new `Png` from decoded images.
+    fn new(imgs: Vec<Box<[u8]>>) -> Result<Self> {
+        if imgs.is_empty() {
+            return Err(Error::Empty);
+        }
+        Ok(Self { imgs, idx: 0 })
+    }
+}
+
+impl Source for Png {
+    fn capture(&mut self) -> Box<[u8]> {
+        let img = self.imgs[self.idx].clone();
+        self.idx = (self.idx + 1) % self.imgs.len();
+        img
+    }
+}
+
+/// Decodes a PNG image into sensor luminance values.
+fn decode(data: impl Read) -> Result<Box<[u8]>> {
+    // Build a reader using a decoder
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    // Read the first frame
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf)?;
+    let (wd, ht) = (info.width as usize, info.height as usize);
+    let chans = info.color_type.samples();
+    // Convert to luminance
+    let luma = |pix: &[u8]| match info.color_type {
+        png::ColorType::Rgb | png::ColorType::Rgba => {
+            let [r, g, b] = [pix[0], pix[1], pix[2]].map(u32::from);
+            u8::try_from((299 * r + 587 * g + 114 * b) / 1000).unwrap_or(u8::MAX)
+        }
+        _ => pix[0],
+    };
+    // Scale to sensor resolution
+    Ok((0..HEIGHT)
+        .flat_map(|y| (0..WIDTH).map(move |x| (x * wd / WIDTH, y * ht / HEIGHT)))
+        .map(|(x, y)| (y * wd + x) * chans)
+        .map(|idx| luma(&buf[idx..idx + chans]))
+        .collect())
+}
+
+/// A convenient type alias for [`Result`](std::result::Result).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error caused by loading an image source.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Image could not be read.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Image could not be decoded.
+    #[error(transparent)]
+    Decode(#[from] png::DecodingError),
+    /// Sequence contains no images.
+    #[error("no images provided")]
+    Empty,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes an 8-bit grayscale PNG.
+    fn encode(wd: u32, ht: u32, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut enc = png::Encoder::new(&mut out, wd, ht);
+        enc.set_color(png::ColorType::Grayscale);
+        enc.set_depth(png::BitDepth::Eight);
+        enc.write_header().unwrap().write_image_data(data).unwrap();
+        out
+    }
+
+    #[test]
+    fn sequence_works() {
+        let dark = encode(1, 1, &[0x20]);
+        let light = encode(2, 1, &[0xa0, 0xe0]);
+        let mut src = Png::decode([dark.as_slice(), light.as_slice()]).unwrap();
+        // Images are scaled to sensor resolution
+        let img = src.capture();
+        assert_eq!(img.len(), WIDTH * HEIGHT);
+        assert!(img.iter().all(|&pix| pix == 0x20));
+        let img = src.capture();
+        assert_eq!((img[0], img[WIDTH - 1]), (0xa0, 0xe0));
+        // Sequence loops
+        assert_eq!(src.capture()[0], 0x20);
+    }
+
+    #[test]
+    fn empty_fails() {
+        assert!(matches!(
+            Png::decode(Vec::<&[u8]>::new()),
+            Err(Error::Empty)
+        ));
+    }
+}
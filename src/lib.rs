@@ -2,6 +2,8 @@
 //!
 //! Emulator implementations for the following Game Boy models:
 //! - [`DMG`](crate::core::dmg): [Game Boy]
+//! - [`CGB`](crate::core::cgb): [Game Boy Color]
+//! - [`SGB`](crate::core::sgb): [Super Game Boy]
 //!
 //! # Examples
 //!
@@ -28,11 +30,16 @@
 //! ```
 //!
 //! [Game Boy]: https://en.wikipedia.org/wiki/Game_Boy
+//! [Game Boy Color]: https://en.wikipedia.org/wiki/Game_Boy_Color
+//! [Super Game Boy]: https://en.wikipedia.org/wiki/Super_Game_Boy
 
 #![warn(clippy::pedantic)]
 
 pub mod app;
+pub mod cam;
 pub mod pal;
+pub mod vid;
+pub mod wav;
 
 #[doc(inline)]
 pub use rugby_arch as arch;
@@ -57,14 +64,19 @@ pub mod prelude {
     pub use crate::app::Frontend;
     pub use crate::app::audio::Audio as _;
     pub use crate::app::joypad::Joypad as _;
+    pub use crate::app::motion::Motion as _;
     pub use crate::app::serial::Serial as _;
     pub use crate::app::video::Video as _;
 
     // Emulator
     pub use crate::emu::core::Core;
     pub use crate::emu::part::audio::Audio as _;
+    pub use crate::emu::part::camera::Camera as _;
     pub use crate::emu::part::joypad::Joypad as _;
+    pub use crate::emu::part::motion::Motion as _;
     pub use crate::emu::part::proc::Processor as _;
+    pub use crate::emu::part::rumble::Rumble as _;
     pub use crate::emu::part::serial::Serial as _;
+    pub use crate::emu::part::speaker::Speaker as _;
     pub use crate::emu::part::video::Video as _;
 }
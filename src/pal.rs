@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 
 pub use self::decl::*;
 
+pub mod cgb;
+
 /// 2-bit color palette.
 ///
 /// Used by the DMG model; the 2-bit palette depth supports a total of 4 colors.
@@ -30,6 +32,30 @@ impl Index<usize> for Palette {
     }
 }
 
+/// Layered color palettes.
+///
+/// Assigns separate [palettes](Palette) to the background and to each of the
+/// object palettes, as done by the CGB when running DMG games.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Scheme {
+    /// Background and window.
+    pub bg: Palette,
+    /// Object palette 0.
+    pub obj0: Palette,
+    /// Object palette 1.
+    pub obj1: Palette,
+}
+
+impl From<Palette> for Scheme {
+    fn from(pal: Palette) -> Self {
+        Self {
+            bg: pal.clone(),
+            obj0: pal.clone(),
+            obj1: pal,
+        }
+    }
+}
+
 #[allow(clippy::unreadable_literal)]
 mod decl {
     use super::{Color, Palette};
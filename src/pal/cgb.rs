@@ -0,0 +1,230 @@
+//! CGB compatibility palettes.
+//!
+//! When running a DMG game, the CGB boot ROM colorizes it using one of a fixed
+//! set of palette combinations. Games licensed by Nintendo are identified by
+//! their title's checksum, with the fourth letter of the title used to
+//! disambiguate titles sharing a checksum. All other games are assigned the
+//! default combination.
+//!
+//! See more details [here][compat].
+//!
+//! [compat]: https://gbdev.io/pandocs/Power_Up_Sequence.html#compatibility-palettes
+
+use rugby_core::dmg::cart::header::Header;
+
+use super::{Color, Palette, Scheme};
+
+/// Looks up the compatibility palettes for a cartridge.
+#[must_use]
+pub fn lookup(head: &Header) -> Scheme {
+    scheme(select(head))
+}
+
+/// Gets the default compatibility palettes.
+///
+/// Used for games which are not recognized by the boot ROM.
+#[must_use]
+pub fn default() -> Scheme {
+    scheme(0)
+}
+
+/// Selects the palette combination for a cartridge.
+fn select(head: &Header) -> usize {
+    // Only games licensed by Nintendo are recognized
+    if !head.nintendo {
+        return 0;
+    }
+    // Compute title checksum
+    //
+    // NOTE: The boot ROM sums the entire title area as raw bytes, including
+    //       the CGB flag and any non-ASCII bytes.
+    let chk = head
+        .ident
+        .iter()
+        .fold(0u8, |acc, &byte| acc.wrapping_add(byte));
+    // Search for a matching title
+    let letter = head.ident[3];
+    (0..COMBINE.len())
+        .find(|&idx| match idx.checked_sub(UNIQUE) {
+            // Checksum is unique
+            None => TITLES[idx] == chk,
+            // Checksum requires disambiguation
+            Some(dup) => {
+                TITLES[UNIQUE + dup % (TITLES.len() - UNIQUE)] == chk && LETTERS[dup] == letter
+            }
+        })
+        .map_or(0, |idx| COMBINE[idx])
+}
+
+/// Builds the palettes for a combination.
+fn scheme(idx: usize) -> Scheme {
+    let pal = |off: usize| {
+        Palette::new(std::array::from_fn(|col| {
+            let col = COLORS[off + col];
+            let chan = |shift: u16| {
+                let val = u8::try_from(col >> shift & 0x1f).unwrap();
+                val << 3 | val >> 2
+            };
+            Color::rgb(chan(0), chan(5), chan(10))
+        }))
+    };
+    let [obj0, obj1, bg] = PALETTES[idx];
+    Scheme {
+        bg: pal(bg),
+        obj0: pal(obj0),
+        obj1: pal(obj1),
+    }
+}
+
+/// Number of title checksums which are unique.
+const UNIQUE: usize = 0x41;
+
+/// Title checksums.
+///
+/// Checksums beyond the first [`UNIQUE`] entries are shared by several titles.
+#[rustfmt::skip]
+const TITLES: [u8; 0x4f] = [
+    // Unique
+    0x00, 0x88, 0x16, 0x36, 0xd1, 0xdb, 0xf2, 0x3c,
+    0x8c, 0x92, 0x3d, 0x5c, 0x58, 0xc9, 0x3e, 0x70,
+    0x1d, 0x59, 0x69, 0x19, 0x35, 0xa8, 0x14, 0xaa,
+    0x75, 0x95, 0x99, 0x34, 0x6f, 0x15, 0xff, 0x97,
+    0x4b, 0x90, 0x17, 0x10, 0x39, 0xf7, 0xf6, 0xa2,
+    0x49, 0x4e, 0x43, 0x68, 0xe0, 0x8b, 0xf0, 0xce,
+    0x0c, 0x29, 0xe8, 0xb7, 0x86, 0x9a, 0x52, 0x01,
+    0x9d, 0x71, 0x9c, 0xbd, 0x5d, 0x6d, 0x67, 0x3f,
+    0x6b,
+    // Duplicate
+    0xb3, 0x46, 0x28, 0xa5, 0xc6, 0xd3, 0x27, 0x61,
+    0x18, 0x66, 0x6a, 0xbf, 0x0d, 0xf4,
+];
+
+/// Title fourth letters.
+///
+/// Disambiguates duplicate checksums, which are repeated as necessary.
+const LETTERS: &[u8; 0x1d] = b"BEFAARBEKEK R-URAR INAILICE R";
+
+/// Palette combination for each title.
+#[rustfmt::skip]
+const COMBINE: [usize; UNIQUE + LETTERS.len()] = [
+    // Unique
+     0,  4,  5, 35, 34,  3, 31, 15,
+    10,  5, 19, 36,  7, 37, 30, 44,
+    21, 32, 31, 20,  5, 33, 13, 14,
+     5, 29,  5, 18,  9,  3,  2, 26,
+    25, 25, 41, 42, 26, 45, 42, 45,
+    36, 38, 26, 42, 42, 42, 26, 26,
+    42, 41, 25, 26, 42, 41, 45, 28,
+    42, 26, 41, 42, 26, 18,  5, 26,
+    45,
+    // Duplicate
+    42, 39, 43, 25, 28, 25, 24, 11,
+    25,  5, 42,  3, 17, 25, 38, 26,
+    22, 26, 45, 27, 25, 36, 24, 25,
+    40, 28, 12, 22,  7,
+];
+
+/// Palette combinations.
+///
+/// Each combination is given as the offset within [`COLORS`] of the palettes
+/// for object palette 0, object palette 1, and the background.
+#[rustfmt::skip]
+const PALETTES: [[usize; 3]; 51] = {
+    /// Offset of a whole palette.
+    const fn pal(obj0: usize, obj1: usize, bg: usize) -> [usize; 3] {
+        [4 * obj0, 4 * obj1, 4 * bg]
+    }
+
+    [
+        pal( 4,  4, 29), pal(18, 18, 18), pal(20, 20, 20), pal(24, 24, 24),
+        pal( 9,  9,  9), pal( 0,  0,  0), pal(27, 27, 27), pal( 5,  5,  5),
+        pal(12, 12, 12), pal(26, 26, 26), pal(16,  8,  8), pal( 4, 28, 28),
+        pal( 4,  2,  2), pal( 3,  4,  4), pal( 4, 29, 29), pal(28,  4, 28),
+        pal( 2, 17,  2), pal(16, 16,  8), pal( 4,  4,  7), pal( 4,  4, 18),
+        pal( 4,  4, 20), pal(19, 19,  9), [15, 15, 44],    pal(17, 17,  2),
+        pal( 4,  4,  2), pal( 4,  4,  3), pal(28, 28,  0), pal( 3,  3,  0),
+        pal( 0,  0,  1), pal(18, 22, 18), pal(20, 22, 20), pal(24, 22, 24),
+        pal(16, 22,  8), pal(17,  4, 13), [111, 0, 56],    [111, 16, 60],
+        pal(19, 22,  9), pal(16, 28, 10), pal( 4, 23, 28), pal(17, 22,  2),
+        pal( 4,  0,  2), pal( 4, 28,  3), pal(28,  3,  0), pal( 3, 28,  4),
+        pal(21, 28,  4), pal( 3, 28,  0), pal(25,  3, 28), pal( 0, 28,  8),
+        pal( 4,  3, 28), pal(28,  3,  6), pal( 4, 28, 29),
+    ]
+};
+
+/// Palette colors.
+///
+/// Stored as 15-bit colors, grouped into palettes of four. (Some combinations
+/// are offset to straddle two palettes.)
+#[rustfmt::skip]
+const COLORS: [u16; 4 * 30] = [
+    0x7fff, 0x32bf, 0x00d0, 0x0000,
+    0x639f, 0x4279, 0x15b0, 0x04cb,
+    0x7fff, 0x6e31, 0x454a, 0x0000,
+    0x7fff, 0x1bef, 0x0200, 0x0000,
+    0x7fff, 0x421f, 0x1cf2, 0x0000,
+    0x7fff, 0x5294, 0x294a, 0x0000,
+    0x7fff, 0x03ff, 0x012f, 0x0000,
+    0x7fff, 0x03ef, 0x01d6, 0x0000,
+    0x7fff, 0x42b5, 0x3dc8, 0x0000,
+    0x7e74, 0x03ff, 0x0180, 0x0000,
+    0x67ff, 0x77ac, 0x1a13, 0x2d6b,
+    0x7ed6, 0x4bff, 0x2175, 0x0000,
+    0x53ff, 0x4a5f, 0x7e52, 0x0000,
+    0x4fff, 0x7ed2, 0x3a4c, 0x1ce0,
+    0x03ed, 0x7fff, 0x255f, 0x0000,
+    0x036a, 0x021f, 0x03ff, 0x7fff,
+    0x7fff, 0x01df, 0x0112, 0x0000,
+    0x231f, 0x035f, 0x00f2, 0x0009,
+    0x7fff, 0x03ea, 0x011f, 0x0000,
+    0x299f, 0x001a, 0x000c, 0x0000,
+    0x7fff, 0x027f, 0x001f, 0x0000,
+    0x7fff, 0x03e0, 0x0206, 0x0120,
+    0x7fff, 0x7eeb, 0x001f, 0x7c00,
+    0x7fff, 0x3fff, 0x7e00, 0x001f,
+    0x7fff, 0x03ff, 0x001f, 0x0000,
+    0x03ff, 0x001f, 0x000c, 0x0000,
+    0x7fff, 0x033f, 0x0193, 0x0000,
+    0x0000, 0x4200, 0x037f, 0x7fff,
+    0x7fff, 0x7e8c, 0x7c00, 0x0000,
+    0x7fff, 0x1bef, 0x6180, 0x0000,
+];
+
+#[cfg(test)]
+#[allow(clippy::unreadable_literal)]
+mod tests {
+    use super::*;
+
+    /// Builds a cartridge header.
+    fn header(title: &[u8], lic: u8) -> Header {
+        let mut rom = vec![0; 0x150];
+        rom[0x134..][..title.len()].copy_from_slice(title);
+        rom[0x14b] = lic;
+        Header::unchecked(&rom).unwrap()
+    }
+
+    #[test]
+    fn select_works() {
+        // Unique checksum
+        assert_eq!(select(&header(b"POKEMON RED", 0x01)), 13);
+        // Duplicate checksum uses the fourth letter
+        assert_eq!(select(&header(b"POKEMON BLUE", 0x01)), 11);
+        assert_eq!(select(&header(b"SUPER MARIOLAND", 0x01)), 39);
+        assert_eq!(select(&header(b"SUEPR MARIOLAND", 0x01)), 0);
+        // Checksum covers the raw title area
+        assert_eq!(select(&header(b"POKEMON R\x88\x01", 0x01)), 13);
+        assert_ne!(select(&header(b"POKEMON RED\0\0\0\0\x80", 0x01)), 13);
+        // Other licensees use the default
+        assert_eq!(select(&header(b"POKEMON RED", 0x33)), 0);
+        assert_eq!(select(&header(b"POKEMON RED", 0x08)), 0);
+    }
+
+    #[test]
+    fn scheme_works() {
+        let pal = default();
+        assert_eq!(u32::from(pal.bg[0]), 0xffffff);
+        assert_eq!(u32::from(pal.bg[2]), 0x0063c6);
+        assert_eq!(u32::from(pal.obj0[1]), 0xff8484);
+        assert_eq!(u32::from(pal.obj1[3]), 0x000000);
+    }
+}
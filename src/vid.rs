@@ -0,0 +1,460 @@
+//! Video recording sink.
+//!
+//! Records frames after palette mapping, in any of the following formats:
+//! - [GIF]: Animated GIF.
+//! - [APNG]: Animated PNG.
+//! - [Y4M]: Raw YUV4MPEG2, suitable for piping into other encoders.
+//!
+//! As both GIF and APNG express frame delays in whole fractions of a second,
+//! rounding is accumulated across frames to maintain the correct average rate.
+//! Note that many GIF players enforce a minimum frame delay, so recording at
+//! the full frame rate may play back too slowly; skipping every other frame
+//! avoids this.
+//!
+//! [GIF]:  https://www.w3.org/Graphics/GIF/spec-gif89a.txt
+//! [APNG]: https://wiki.mozilla.org/APNG_Specification
+//! [Y4M]:  https://wiki.multimedia.cx/index.php/YUV4MPEG2
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::core::dmg::ppu::{Source, Tagged, RATE};
+use crate::core::dmg::{FREQ, LCD};
+use crate::pal::{Color, Scheme};
+
+/// Number of colors in a layered palette.
+const COLORS: usize = 12;
+
+/// Video file format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// Animated GIF.
+    Gif,
+    /// Animated PNG.
+    Apng,
+    /// Raw YUV4MPEG2.
+    Y4m,
+}
+
+impl Format {
+    /// Infers the format from a path's extension.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the extension is missing or unrecognized.
+    pub fn infer(path: &Path) -> Result<Self, Error> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .ok_or(Error::Extension)?
+            .parse()
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "gif" => Ok(Self::Gif),
+            "png" | "apng" => Ok(Self::Apng),
+            "y4m" => Ok(Self::Y4m),
+            _ => Err(Error::Extension),
+        }
+    }
+}
+
+/// Recording options.
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// Frames skipped after each recorded frame.
+    pub skip: u16,
+    /// Integer scaling factor.
+    pub scale: u8,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { skip: 0, scale: 1 }
+    }
+}
+
+/// Video file writer.
+///
+/// Each frame is recolored using the layered palettes, scaled, and then
+/// encoded. Any trailing data is written whenever the writer is
+/// [flushed](Vid::flush), as well as when it is dropped.
+pub struct Vid<W: Write + Seek> {
+    /// Output encoder.
+    enc: Encoder<W>,
+    /// Palette colors.
+    pal: [Color; COLORS],
+    /// Recording options.
+    cfg: Options,
+    /// Frames received.
+    seen: u64,
+    /// Frames recorded.
+    took: u32,
+}
+
+impl<W: Write + Seek> std::fmt::Debug for Vid<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Vid")
+            .field("fmt", &self.format())
+            .field("cfg", &self.cfg)
+            .field("seen", &self.seen)
+            .field("took", &self.took)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Format-specific encoder.
+enum Encoder<W: Write + Seek> {
+    /// Animated GIF.
+    Gif(gif::Encoder<W>),
+    /// Animated PNG.
+    Apng(W),
+    /// Raw YUV4MPEG2.
+    Y4m(W),
+}
+
+impl Vid<BufWriter<File>> {
+    /// Creates a video file at the provided path.
+    ///
+    /// The format is inferred from the path's extension.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the format is not recognized,
+    /// or the file could not be created or written to.
+    pub fn create(path: impl AsRef<Path>, pal: &Scheme, cfg: Options) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let fmt = Format::infer(path)?;
+        Ok(Self::new(
+            BufWriter::new(File::create(path)?),
+            fmt,
+            pal,
+            cfg,
+        )?)
+    }
+}
+
+impl<W: Write + Seek> Vid<W> {
+    /// Constructs a new `Vid`, writing the header to the output stream.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the header could not be
+    /// written.
+    pub fn new(mut out: W, fmt: Format, pal: &Scheme, cfg: Options) -> io::Result<Self> {
+        let cfg = Options {
+            scale: cfg.scale.max(1),
+            ..cfg
+        };
+        let pal = std::array::from_fn(|idx| [&pal.bg, &pal.obj0, &pal.obj1][idx / 4][idx % 4]);
+        let (wd, ht) = dims(cfg.scale);
+        let enc = match fmt {
+            Format::Gif => {
+                let rgb: Vec<u8> = pal.iter().copied().flat_map(rgb).collect();
+                let mut enc = gif::Encoder::new(out, wd, ht, &rgb).map_err(io::Error::other)?;
+                enc.set_repeat(gif::Repeat::Infinite)
+                    .map_err(io::Error::other)?;
+                Encoder::Gif(enc)
+            }
+            Format::Apng => {
+                out.write_all(b"\x89PNG\r\n\x1a\n")?;
+                // Image header
+                let mut ihdr = Vec::new();
+                ihdr.extend(u32::from(wd).to_be_bytes());
+                ihdr.extend(u32::from(ht).to_be_bytes());
+                ihdr.extend([8, 3, 0, 0, 0]); // 8-bit indexed color
+                chunk(&mut out, *b"IHDR", &ihdr)?;
+                // Animation control (patched on flush)
+                chunk(&mut out, *b"acTL", &[0; 8])?;
+                // Palette
+                let plte: Vec<u8> = pal.iter().copied().flat_map(rgb).collect();
+                chunk(&mut out, *b"PLTE", &plte)?;
+                Encoder::Apng(out)
+            }
+            Format::Y4m => {
+                let step = u64::from(RATE) * (u64::from(cfg.skip) + 1);
+                writeln!(out, "YUV4MPEG2 W{wd} H{ht} F{FREQ}:{step} Ip A1:1 C444")?;
+                Encoder::Y4m(out)
+            }
+        };
+        Ok(Self {
+            enc,
+            pal,
+            cfg,
+            seen: 0,
+            took: 0,
+        })
+    }
+
+    /// Gets the video format.
+    #[must_use]
+    pub fn format(&self) -> Format {
+        match self.enc {
+            Encoder::Gif(_) => Format::Gif,
+            Encoder::Apng(_) => Format::Apng,
+            Encoder::Y4m(_) => Format::Y4m,
+        }
+    }
+
+    /// Gets the number of recorded frames.
+    #[must_use]
+    pub fn frames(&self) -> u32 {
+        self.took
+    }
+
+    /// Appends a frame to the file.
+    ///
+    /// Frames are only recorded as permitted by the configured frame skip.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the frame could not be
+    /// written.
+    pub fn write(&mut self, frame: &[Tagged]) -> io::Result<()> {
+        // Skip frames as configured
+        let step = u64::from(self.cfg.skip) + 1;
+        let skip = !self.seen.is_multiple_of(step);
+        self.seen += 1;
+        if skip {
+            return Ok(());
+        }
+        // Recolor and scale frame
+        let data = self.scale(frame);
+        let (wd, ht) = dims(self.cfg.scale);
+        let seen = self.seen - 1;
+        // Encode frame
+        match &mut self.enc {
+            Encoder::Gif(enc) => {
+                let frame = gif::Frame {
+                    width: wd,
+                    height: ht,
+                    delay: delay(seen, step, 100),
+                    buffer: Cow::Owned(data),
+                    ..gif::Frame::default()
+                };
+                enc.write_frame(&frame).map_err(io::Error::other)?;
+            }
+            Encoder::Apng(out) => {
+                // Sequence numbers are shared by frame control and data chunks
+                let seq = self
+                    .took
+                    .checked_mul(2)
+                    .ok_or_else(|| io::Error::other("exceeded maximum number of frames"))?
+                    .saturating_sub(1);
+                // Frame control
+                let mut fctl = Vec::new();
+                fctl.extend(seq.to_be_bytes());
+                fctl.extend(u32::from(wd).to_be_bytes());
+                fctl.extend(u32::from(ht).to_be_bytes());
+                fctl.extend([0; 8]); // offset
+                fctl.extend(delay(seen, step, 1000).to_be_bytes());
+                fctl.extend(1000u16.to_be_bytes());
+                fctl.extend([0, 0]); // no disposal, no blending
+                chunk(out, *b"fcTL", &fctl)?;
+                // Frame data
+                let rows = data.chunks_exact(usize::from(wd));
+                let raw: Vec<u8> = rows.flat_map(|row| [&[0][..], row].concat()).collect();
+                let zlib = miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6);
+                if self.took == 0 {
+                    chunk(out, *b"IDAT", &zlib)?;
+                } else {
+                    let fdat = [&(seq + 1).to_be_bytes()[..], &zlib].concat();
+                    chunk(out, *b"fdAT", &fdat)?;
+                }
+            }
+            Encoder::Y4m(out) => {
+                let yuv = self.pal.map(yuv);
+                out.write_all(b"FRAME\n")?;
+                for plane in [0, 1, 2].map(|chan| yuv.map(|col| col[chan])) {
+                    let data: Vec<u8> = data.iter().map(|&idx| plane[usize::from(idx)]).collect();
+                    out.write_all(&data)?;
+                }
+            }
+        }
+        self.took += 1;
+        Ok(())
+    }
+
+    /// Flushes the output stream, writing any trailing data.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the output stream could not
+    /// be written to.
+    pub fn flush(&mut self) -> io::Result<()> {
+        match &mut self.enc {
+            // Trailer is written when dropped
+            Encoder::Gif(enc) => enc.get_mut().flush(),
+            Encoder::Apng(out) => {
+                // Patch the animation control
+                let pos = out.stream_position()?;
+                out.seek(SeekFrom::Start(33))?;
+                let mut actl = Vec::new();
+                actl.extend(self.took.to_be_bytes());
+                actl.extend(0u32.to_be_bytes()); // loop forever
+                chunk(out, *b"acTL", &actl)?;
+                // Write the trailer, to be overwritten by the next frame
+                out.seek(SeekFrom::Start(pos))?;
+                chunk(out, *b"IEND", &[])?;
+                out.seek(SeekFrom::Start(pos))?;
+                out.flush()
+            }
+            Encoder::Y4m(out) => out.flush(),
+        }
+    }
+
+    /// Recolors and scales a frame into palette indices.
+    fn scale(&self, frame: &[Tagged]) -> Vec<u8> {
+        let scale = usize::from(self.cfg.scale);
+        let index = |pix: &Tagged| {
+            let layer = match pix.src {
+                Source::Background | Source::Window => 0,
+                Source::Obp0 => 1,
+                Source::Obp1 => 2,
+            };
+            4 * layer + pix.col as u8
+        };
+        frame
+            .chunks_exact(LCD.wd.into())
+            .flat_map(|row| {
+                let row: Vec<u8> = row
+                    .iter()
+                    .flat_map(|pix| std::iter::repeat_n(index(pix), scale))
+                    .collect();
+                std::iter::repeat_n(row, scale).flatten()
+            })
+            .collect()
+    }
+}
+
+impl<W: Write + Seek> Drop for Vid<W> {
+    fn drop(&mut self) {
+        // Errors cannot be reported on drop
+        let _ = self.flush();
+    }
+}
+
+/// Computes the scaled frame dimensions.
+fn dims(scale: u8) -> (u16, u16) {
+    let scale = u16::from(scale);
+    (LCD.wd * scale, LCD.ht * scale)
+}
+
+/// Computes the delay of a frame, in the provided units per second.
+///
+/// Delays are rounded such that the error does not accumulate.
+fn delay(frame: u64, step: u64, unit: u64) -> u16 {
+    let time = |frame: u64| frame * u64::from(RATE) * unit / u64::from(FREQ);
+    u16::try_from(time(frame + step) - time(frame)).unwrap_or(u16::MAX)
+}
+
+/// Writes a PNG chunk.
+fn chunk(out: &mut impl Write, kind: [u8; 4], data: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(data.len()).map_err(|_| io::Error::other("chunk too large"))?;
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(&kind);
+    crc.update(data);
+    out.write_all(&len.to_be_bytes())?;
+    out.write_all(&kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc.finalize().to_be_bytes())
+}
+
+/// Splits a color into its RGB channels.
+fn rgb(col: Color) -> [u8; 3] {
+    let [_, r, g, b] = u32::from(col).to_be_bytes();
+    [r, g, b]
+}
+
+/// Converts a color into BT.601 limited range YCbCr.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn yuv(col: Color) -> [u8; 3] {
+    let [red, grn, blu] = rgb(col).map(f32::from);
+    let luma = 16. + (65.481 * red + 128.553 * grn + 24.966 * blu) / 255.;
+    let cb = 128. + (-37.797 * red - 74.203 * grn + 112.0 * blu) / 255.;
+    let cr = 128. + (112.0 * red - 93.786 * grn - 18.214 * blu) / 255.;
+    [luma, cb, cr].map(|val| val.round() as u8)
+}
+
+/// An error caused by creating a video recording.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Unrecognized file extension.
+    #[error("unknown video format (expected `.gif`, `.png`, or `.y4m`)")]
+    Extension,
+    /// I/O error.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::pal;
+
+    /// Builds a blank frame.
+    fn frame() -> Vec<Tagged> {
+        vec![Tagged::default(); LCD.depth()]
+    }
+
+    #[test]
+    fn vid_apng_works() {
+        let mut buf = Vec::new();
+        let cfg = Options { skip: 1, scale: 2 };
+        let mut vid =
+            Vid::new(Cursor::new(&mut buf), Format::Apng, &pal::MONO.into(), cfg).unwrap();
+        for _ in 0..5 {
+            vid.write(&frame()).unwrap();
+        }
+        assert_eq!(vid.frames(), 3);
+        drop(vid);
+        // Check header
+        assert_eq!(&buf[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(buf[16..20], 320u32.to_be_bytes());
+        assert_eq!(buf[20..24], 288u32.to_be_bytes());
+        // Check animation control
+        assert_eq!(&buf[37..41], b"acTL");
+        assert_eq!(buf[41..45], 3u32.to_be_bytes());
+        // Check trailer
+        assert_eq!(&buf[buf.len() - 8..buf.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn vid_y4m_works() {
+        let mut buf = Vec::new();
+        let mut vid = Vid::new(
+            Cursor::new(&mut buf),
+            Format::Y4m,
+            &pal::MONO.into(),
+            Options::default(),
+        )
+        .unwrap();
+        vid.write(&frame()).unwrap();
+        vid.write(&frame()).unwrap();
+        drop(vid);
+        // Check header
+        let head = b"YUV4MPEG2 W160 H144 F4194304:70224 Ip A1:1 C444\n";
+        assert_eq!(&buf[..head.len()], head);
+        // Check frames
+        assert_eq!(buf.len(), head.len() + 2 * (6 + 3 * LCD.depth()));
+    }
+
+    #[test]
+    fn delay_works() {
+        // Rounding error does not accumulate
+        let total: u64 = (0..60).map(|frame| u64::from(delay(frame, 1, 100))).sum();
+        assert_eq!(total, 100);
+        assert_eq!(delay(0, 2, 100), 3);
+    }
+}
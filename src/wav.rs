@@ -0,0 +1,161 @@
+//! WAV audio sink.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::app::audio::Audio;
+use crate::emu::part::audio::Sample;
+
+/// Number of channels.
+const CHANNELS: u16 = 2;
+
+/// Bits per sample.
+const DEPTH: u16 = 16;
+
+/// Size of the RIFF header.
+const HEADER: u32 = 44;
+
+/// WAV file writer.
+///
+/// Records stereo 16-bit PCM audio. The chunk sizes in the header are patched
+/// whenever the writer is [flushed](Wav::flush), as well as when it is
+/// dropped.
+#[derive(Debug)]
+pub struct Wav<W: Write + Seek> {
+    /// Output stream.
+    out: W,
+    /// Sample rate.
+    rate: u32,
+    /// Data length in bytes.
+    len: u32,
+}
+
+impl Wav<BufWriter<File>> {
+    /// Creates a WAV file at the provided path.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the file could not be created
+    /// or written to.
+    pub fn create(path: impl AsRef<Path>, rate: u32) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), rate)
+    }
+}
+
+impl<W: Write + Seek> Wav<W> {
+    /// Constructs a new `Wav`, writing the header to the output stream.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the header could not be
+    /// written.
+    pub fn new(out: W, rate: u32) -> io::Result<Self> {
+        let mut this = Self { out, rate, len: 0 };
+        this.header()?;
+        Ok(this)
+    }
+
+    /// Gets the sample rate.
+    #[must_use]
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Writes the RIFF header.
+    fn header(&mut self) -> io::Result<()> {
+        let align = CHANNELS * DEPTH / 8;
+        let bytes = self.rate * u32::from(align);
+        let out = &mut self.out;
+        // RIFF chunk
+        out.write_all(b"RIFF")?;
+        out.write_all(&(HEADER - 8 + self.len).to_le_bytes())?;
+        out.write_all(b"WAVE")?;
+        // Format chunk
+        out.write_all(b"fmt ")?;
+        out.write_all(&16u32.to_le_bytes())?;
+        out.write_all(&1u16.to_le_bytes())?; // PCM
+        out.write_all(&CHANNELS.to_le_bytes())?;
+        out.write_all(&self.rate.to_le_bytes())?;
+        out.write_all(&bytes.to_le_bytes())?;
+        out.write_all(&align.to_le_bytes())?;
+        out.write_all(&DEPTH.to_le_bytes())?;
+        // Data chunk
+        out.write_all(b"data")?;
+        out.write_all(&self.len.to_le_bytes())?;
+        Ok(())
+    }
+
+    /// Appends samples to the file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the samples could not be
+    /// written.
+    pub fn write(&mut self, buf: &[Sample]) -> io::Result<()> {
+        #[allow(clippy::cast_possible_truncation)]
+        let pcm = |amp: f32| (amp.clamp(-1., 1.) * f32::from(i16::MAX)) as i16;
+        let data: Vec<u8> = buf
+            .iter()
+            .flat_map(|smp| [pcm(smp.lt), pcm(smp.rt)])
+            .flat_map(i16::to_le_bytes)
+            .collect();
+        self.out.write_all(&data)?;
+        self.len = u32::try_from(data.len())
+            .ok()
+            .and_then(|len| self.len.checked_add(len))
+            .ok_or_else(|| io::Error::other("exceeded maximum file size"))?;
+        Ok(())
+    }
+
+    /// Flushes the output stream, patching the header's chunk sizes.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error when the output stream could not
+    /// be written to.
+    pub fn flush(&mut self) -> io::Result<()> {
+        let pos = self.out.stream_position()?;
+        self.out.seek(SeekFrom::Start(0))?;
+        self.header()?;
+        self.out.seek(SeekFrom::Start(pos))?;
+        self.out.flush()
+    }
+}
+
+impl<W: Write + Seek> Audio for Wav<W> {
+    fn play(&mut self, buf: &[Sample]) -> io::Result<()> {
+        self.write(buf)
+    }
+}
+
+impl<W: Write + Seek> Drop for Wav<W> {
+    fn drop(&mut self) {
+        // Errors cannot be reported on drop
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn wav_header_works() {
+        let mut buf = Vec::new();
+        let mut wav = Wav::new(Cursor::new(&mut buf), 48_000).unwrap();
+        wav.write(&[Sample { lt: 1., rt: -1. }; 4]).unwrap();
+        drop(wav);
+        // Check sizes
+        assert_eq!(buf.len(), 44 + 16);
+        assert_eq!(&buf[0..4], b"RIFF");
+        assert_eq!(buf[4..8], 52u32.to_le_bytes());
+        assert_eq!(buf[24..28], 48_000u32.to_le_bytes());
+        assert_eq!(buf[40..44], 16u32.to_le_bytes());
+        // Check samples
+        assert_eq!(buf[44..46], i16::MAX.to_le_bytes());
+        assert_eq!(buf[46..48], (-i16::MAX).to_le_bytes());
+    }
+}
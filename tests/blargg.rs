@@ -115,6 +115,28 @@ mod check {
             Err(Error::Running)
         }
     }
+
+    /// Check memory for test results, discarding overflowing text output.
+    ///
+    /// # About
+    ///
+    /// Behaves as [`memory`], but rewinds the text output pointer (stored at
+    /// $D883) before it runs off the end of cartridge RAM. Some tests print
+    /// more than the 8 KiB available, at which point the text spills into WRAM
+    /// and overwrites the code the test is running from. As the text is not
+    /// part of the result, rewinding it has no effect on the outcome.
+    pub fn rewind(emu: &mut GameBoy) -> Result<()> {
+        // Extract text output pointer
+        let addr = [0xd883, 0xd884].map(|addr| emu.inside().proc().read(addr));
+        // Rewind pointer well before it overflows
+        if u16::from_le_bytes(addr) >= 0xb000 {
+            let [lo, hi] = 0xa004u16.to_le_bytes();
+            emu.inside_mut().proc().write(0xd883, lo);
+            emu.inside_mut().proc().write(0xd884, hi);
+        }
+        // Check memory as usual
+        memory(emu)
+    }
 }
 
 /// A convenient type alias for [`Result`](std::result::Result).
@@ -201,6 +223,6 @@ test! {
     oam_bug_rom_singles_4_scanline_timing          = (check::memory,  "../roms/test/blargg/oam_bug/rom_singles/4-scanline_timing.gb");
     oam_bug_rom_singles_5_timing_bug               = (check::memory,  "../roms/test/blargg/oam_bug/rom_singles/5-timing_bug.gb");
     oam_bug_rom_singles_6_timing_no_bug            = (check::memory,  "../roms/test/blargg/oam_bug/rom_singles/6-timing_no_bug.gb");
-    oam_bug_rom_singles_7_timing_effect            = (check::memory,  "../roms/test/blargg/oam_bug/rom_singles/7-timing_effect.gb");
+    oam_bug_rom_singles_7_timing_effect            = (check::rewind,  "../roms/test/blargg/oam_bug/rom_singles/7-timing_effect.gb");
     oam_bug_rom_singles_8_instr_effect             = (check::memory,  "../roms/test/blargg/oam_bug/rom_singles/8-instr_effect.gb");
 }
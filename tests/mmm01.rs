@@ -0,0 +1,225 @@
+use std::fmt::{Debug, Display};
+
+use rugby::arch::reg::Port;
+use rugby::arch::{Block, Word};
+use rugby::core::dmg::cpu::Cpu;
+use rugby::core::dmg::{Cartridge, GameBoy};
+use rugby::prelude::*;
+use thiserror::Error;
+
+/// Number of cycles after which the test is considered to have failed due to a
+/// timeout error.
+const TIMEOUT: usize = 1_000_000;
+
+/// Size of a ROM bank.
+const BANK: usize = 0x4000;
+
+/// Number of ROM banks in the multicart.
+const BANKS: usize = 8;
+
+/// Builds a synthetic MMM01 multicart.
+///
+/// The multicart holds three 32 KiB games, followed by a menu in the last 32
+/// KiB of ROM. Every bank is tagged with its number in its last byte. Upon
+/// boot, the menu checks it is mapped as expected, then locks the mapper into
+/// the `game`'s window from a stub in WRAM. Each game checks that its banks
+/// are mapped, and that it cannot bank outside of its window.
+///
+/// All programs report results using the same convention as the Mooneye test
+/// suite.
+fn multicart(game: u8) -> Vec<u8> {
+    let mut rom = vec![0; BANK * BANKS];
+    for (idx, bank) in rom.chunks_mut(BANK).enumerate() {
+        // Tag bank with its number
+        bank[BANK - 1] = u8::try_from(idx).unwrap();
+        // Entry point
+        #[rustfmt::skip]
+        let entry = [
+            0x00,             // nop
+            0xc3, 0x50, 0x01, // jp $0150
+        ];
+        bank[0x0100..][..entry.len()].copy_from_slice(&entry);
+        // Failure routine
+        #[rustfmt::skip]
+        let fail = [
+            0x06, 0x42,       // ld b, $42
+            0x0e, 0x42,       // ld c, $42
+            0x16, 0x42,       // ld d, $42
+            0x1e, 0x42,       // ld e, $42
+            0x26, 0x42,       // ld h, $42
+            0x2e, 0x42,       // ld l, $42
+            0x18, 0xfe,       // jr @
+        ];
+        bank[0x0200..][..fail.len()].copy_from_slice(&fail);
+    }
+
+    // Games
+    for base in (0..BANKS - 2).step_by(2) {
+        let tag = u8::try_from(base).unwrap();
+        let bank = &mut rom[base * BANK..][..BANK];
+        #[rustfmt::skip]
+        let code = [
+            // Check bank 0 of window
+            0xfa, 0xff, 0x3f, // ld a, [$3fff]
+            0xfe, tag,        // cp tag
+            0xc2, 0x00, 0x02, // jp nz, $0200
+            // Check bank 1 of window
+            0xfa, 0xff, 0x7f, // ld a, [$7fff]
+            0xfe, tag + 1,    // cp tag + 1
+            0xc2, 0x00, 0x02, // jp nz, $0200
+            // Attempt to bank outside of window
+            0x3e, 0x1f,       // ld a, $1f
+            0xea, 0x00, 0x20, // ld [$2000], a
+            0xfa, 0xff, 0x7f, // ld a, [$7fff]
+            0xfe, tag + 1,    // cp tag + 1
+            0xc2, 0x00, 0x02, // jp nz, $0200
+            // Report success
+            0x06, 0x03,       // ld b, 3
+            0x0e, 0x05,       // ld c, 5
+            0x16, 0x08,       // ld d, 8
+            0x1e, 0x0d,       // ld e, 13
+            0x26, 0x15,       // ld h, 21
+            0x2e, 0x22,       // ld l, 34
+            0x18, 0xfe,       // jr @
+        ];
+        bank[0x0150..][..code.len()].copy_from_slice(&code);
+    }
+
+    // Menu
+    let menu = &mut rom[(BANKS - 2) * BANK..];
+    let last = u8::try_from(BANKS - 1).unwrap();
+    #[rustfmt::skip]
+    let code = [
+        // Check unlocked mapping
+        0xfa, 0xff, 0x7f, // ld a, [$7fff]
+        0xfe, last,       // cp last
+        0xc2, 0x00, 0x02, // jp nz, $0200
+        // Copy stub to WRAM
+        0x21, 0x80, 0x01, // ld hl, $0180
+        0x11, 0x00, 0xc0, // ld de, $c000
+        0x06, 0x20,       // ld b, $20
+        0x2a,             // ld a, [hl+]
+        0x12,             // ld [de], a
+        0x13,             // inc de
+        0x05,             // dec b
+        0xc2, 0x60, 0x01, // jp nz, $0160
+        0xc3, 0x00, 0xc0, // jp $c000
+    ];
+    menu[0x0150..][..code.len()].copy_from_slice(&code);
+    #[rustfmt::skip]
+    let stub = [
+        // Select game
+        0x3e, game * 2,   // ld a, game * 2
+        0xea, 0x00, 0x20, // ld [$2000], a
+        // Restrict to 32 KiB
+        0x3e, 0x3c,       // ld a, $3c
+        0xea, 0x00, 0x60, // ld [$6000], a
+        0xaf,             // xor a
+        0xea, 0x00, 0x40, // ld [$4000], a
+        // Lock mapping
+        0x3e, 0x40,       // ld a, $40
+        0xea, 0x00, 0x00, // ld [$0000], a
+        // Boot game
+        0xc3, 0x00, 0x01, // jp $0100
+    ];
+    menu[0x0180..][..stub.len()].copy_from_slice(&stub);
+
+    // Header
+    menu[0x0147] = 0x0b; // MMM01
+    menu[0x0148] = 0x02; // 128 KiB
+    menu[0x014d] = menu[0x0134..0x014d]
+        .iter()
+        .fold(0u8, |chk, byte| chk.wrapping_sub(*byte).wrapping_sub(1));
+
+    rom
+}
+
+/// Perform integration test emulation.
+fn emulate(rom: &[u8]) -> Result<()> {
+    // Instantiate a cartridge
+    let cart = Cartridge::new(rom).unwrap();
+    // Create an emulator instance
+    let mut emu = GameBoy::new();
+    // Load the cartridge
+    emu.insert(cart);
+
+    // Loop until completion or timeout
+    for _ in 0..TIMEOUT {
+        emu.cycle();
+
+        // Check for success or failure
+        match check(&emu) {
+            Err(Error::Running) => continue,
+            res => return res,
+        }
+    }
+
+    // Fail with timeout if reached
+    Err(Error::Timeout)
+}
+
+/// Check for test results.
+fn check(emu: &GameBoy) -> Result<()> {
+    type Select = <Cpu as Port<Word>>::Select;
+    // Extract register values
+    let cpu = emu.inside().proc();
+    let bc: u16 = cpu.load(Select::BC);
+    let de: u16 = cpu.load(Select::DE);
+    let hl: u16 = cpu.load(Select::HL);
+    // Calculate pass/fail conditions
+    let pass = (bc == 0x0305) && (de == 0x080d) && (hl == 0x1522);
+    let fail = (bc == 0x4242) && (de == 0x4242) && (hl == 0x4242);
+    // Report results
+    if fail {
+        Err(Error::Failed)
+    } else if pass {
+        Ok(())
+    } else {
+        Err(Error::Running)
+    }
+}
+
+/// A convenient type alias for [`Result`](std::result::Result).
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Failure conditions caused by a test.
+#[derive(Error)]
+enum Error {
+    /// Test has explicitly failed.
+    #[error("test failed")]
+    Failed,
+    /// Test is still in progress.
+    #[error("test in progress")]
+    Running,
+    /// Test has reached timeout.
+    #[error("timeout reached")]
+    Timeout,
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[test]
+fn header_located() {
+    let cart = Cartridge::new(&multicart(0)).unwrap();
+    assert_eq!(cart.header().romsz, BANK * BANKS);
+    assert_eq!(cart.header().info.to_string(), "MMM01");
+}
+
+#[test]
+fn boot_game_0() -> Result<()> {
+    emulate(&multicart(0))
+}
+
+#[test]
+fn boot_game_1() -> Result<()> {
+    emulate(&multicart(1))
+}
+
+#[test]
+fn boot_game_2() -> Result<()> {
+    emulate(&multicart(2))
+}
@@ -11,7 +11,7 @@ use thiserror::Error;
 
 /// Number of cycles after which the test is considered to have failed due to a
 /// timeout error.
-const TIMEOUT: usize = 10_000_000;
+const TIMEOUT: usize = 20_000_000;
 
 /// Perform integration test emulation.
 fn emulate(rom: &[u8]) -> Result<()> {